@@ -0,0 +1,677 @@
+//! Helpers for buffers whose pixels have one byte per channel, like RGBA8.
+//!
+//! The `_argb` and `_srgb` helpers in the crate root only take 32-bit packed
+//! pixels. The ones here take buffers of `[u8; N]` pixels, or raw byte slices,
+//! along with a [`ChannelLayout`] that says which byte holds which channel.
+//! Every byte is blurred as a channel of its own, with the same conversions
+//! and arithmetic that [`blur_argb`](crate::blur_argb) and
+//! [`blur_srgb`](crate::blur_srgb) use for the channels of a packed pixel, so
+//! the result is the same as packing every pixel into ARGB would give, but
+//! layouts with fewer than four channels only pay for the ones they have.
+//!
+//! The pixels are blurred as [`Channels`], not as an [`Argb`](crate::color::Argb),
+//! even for the four-channel layouts. An [`Argb`](crate::color::Argb) always has
+//! four channels and is made from a packed `u32` in ARGB order, while these
+//! layouts have one to four channels in several orders, so each pixel would
+//! have to be reordered and packed on the way in and back out. Since every
+//! channel is blurred the same way, the order doesn't matter to the blur, and
+//! the [`ChannelLayout`] is only needed for its number of channels.
+
+#[cfg(any(doc, feature = "simd"))]
+use std::simd::{LaneCount, SupportedLaneCount};
+
+use imgref::ImgRefMut;
+
+use crate::error::BlurError;
+use crate::iter::Kernel;
+use crate::color::{Channel, Channels, StackBlurrableU32, StackBlurrableU64};
+#[cfg(feature = "simd")]
+use crate::color::{ChannelxN, StackBlurrableU32xN, StackBlurrableU64xN};
+use crate::{check_radius, fits_u32, ARGB_CHANNEL_MAX};
+#[cfg(any(doc, feature = "blend-srgb"))]
+use crate::SRGB_CHANNEL_MAX;
+
+/// The order of the channels in a pixel, with one byte per channel.
+///
+/// Every channel is blurred the same way, so the helpers only use the layout to
+/// check that it has as many channels as the pixels have bytes.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ChannelLayout {
+	/// Red, green, blue, then alpha.
+	Rgba,
+	/// Blue, green, red, then alpha.
+	Bgra,
+	/// Alpha, then red, green and blue.
+	Argb,
+	/// Alpha, then blue, green and red.
+	Abgr,
+	/// Red, green, then blue, without alpha.
+	Rgb,
+	/// Blue, green, then red, without alpha.
+	Bgr,
+	/// A single gray channel, without alpha.
+	Gray,
+	/// Gray, then alpha.
+	GrayAlpha
+}
+
+impl ChannelLayout {
+	/// Returns the number of channels, and so bytes, in a pixel.
+	pub const fn channels(self) -> usize {
+		match self {
+			Self::Rgba | Self::Bgra | Self::Argb | Self::Abgr => 4,
+			Self::Rgb | Self::Bgr => 3,
+			Self::Gray => 1,
+			Self::GrayAlpha => 2
+		}
+	}
+}
+
+/// Panics if `layout` does not have `N` channels.
+fn assert_layout<const N: usize>(layout: ChannelLayout) {
+	assert_eq!(layout.channels(), N, "the layout must have as many channels as the pixels have bytes");
+}
+
+/// Returns an error if `layout` does not have `N` channels.
+fn check_layout<const N: usize>(layout: ChannelLayout) -> Result<(), BlurError> {
+	if layout.channels() == N {
+		Ok(())
+	} else {
+		Err(BlurError::UnsupportedParameter { parameter: "layout", reason: "must have as many channels as the pixels have bytes" })
+	}
+}
+
+/// Converts every byte of a linear pixel to a channel, from 0 to 255.
+fn from_bytes<C: Channel, const N: usize>(pixel: &[u8; N]) -> Channels<C, N> {
+	Channels(pixel.map(|i| C::from_u32(i as u32)))
+}
+
+/// The inverse of [`from_bytes`].
+fn to_bytes<C: Channel, const N: usize>(pixel: Channels<C, N>) -> [u8; N] {
+	pixel.0.map(|i| i.to_u32() as u8)
+}
+
+/// Converts every byte of an sRGB pixel to a channel in 12-bit linear light,
+/// from 0 to 4095.
+#[cfg(feature = "blend-srgb")]
+fn from_bytes_srgb<C: Channel, const N: usize>(pixel: &[u8; N]) -> Channels<C, N> {
+	use blend_srgb::convert::srgb8_to_rgb12;
+	Channels(pixel.map(|i| C::from_u32(srgb8_to_rgb12(i) as u32)))
+}
+
+/// The inverse of [`from_bytes_srgb`].
+#[cfg(feature = "blend-srgb")]
+fn to_bytes_srgb<C: Channel, const N: usize>(pixel: Channels<C, N>) -> [u8; N] {
+	use blend_srgb::convert::rgb12_to_srgb8;
+	pixel.0.map(|i| rgb12_to_srgb8(i.to_u32() as u16))
+}
+
+/// Like [`from_bytes`], but for `LANES` pixels at a time.
+#[allow(non_snake_case)]
+#[cfg(feature = "simd")]
+fn from_bytesxN<C: ChannelxN<LANES>, const N: usize, const LANES: usize>(pixels: [&[u8; N]; LANES]) -> Channels<C, N> {
+	Channels(std::array::from_fn(|channel| C::from_array(pixels.map(|pixel| pixel[channel] as u32))))
+}
+
+/// Like [`to_bytes`], but for `LANES` pixels at a time.
+#[allow(non_snake_case)]
+#[cfg(feature = "simd")]
+fn to_bytesxN<C: ChannelxN<LANES>, const N: usize, const LANES: usize>(pixels: Channels<C, N>) -> [[u8; N]; LANES] {
+	let channels = pixels.0.map(C::to_array);
+	std::array::from_fn(|lane| channels.map(|channel| channel[lane] as u8))
+}
+
+/// Like [`from_bytes_srgb`], but for `LANES` pixels at a time.
+#[allow(non_snake_case)]
+#[cfg(all(feature = "simd", feature = "blend-srgb"))]
+fn from_bytesxN_srgb<C: ChannelxN<LANES>, const N: usize, const LANES: usize>(pixels: [&[u8; N]; LANES]) -> Channels<C, N> {
+	use blend_srgb::convert::srgb8_to_rgb12;
+	Channels(std::array::from_fn(|channel| C::from_array(pixels.map(|pixel| srgb8_to_rgb12(pixel[channel]) as u32))))
+}
+
+/// Like [`to_bytes_srgb`], but for `LANES` pixels at a time.
+#[allow(non_snake_case)]
+#[cfg(all(feature = "simd", feature = "blend-srgb"))]
+fn to_bytesxN_srgb<C: ChannelxN<LANES>, const N: usize, const LANES: usize>(pixels: Channels<C, N>) -> [[u8; N]; LANES] {
+	use blend_srgb::convert::rgb12_to_srgb8;
+	let channels = pixels.0.map(C::to_array);
+	std::array::from_fn(|lane| channels.map(|channel| rgb12_to_srgb8(channel[lane] as u16)))
+}
+
+/// Splits a raw buffer into pixels of `N` bytes, with `stride` in bytes, and
+/// wraps them in an image.
+fn raw_image<const N: usize>(buffer: &mut [u8], width: usize, height: usize, stride: usize) -> Result<ImgRefMut<'_, [u8; N]>, BlurError> {
+	let stride = match (stride / N, stride % N) {
+		(stride, 0) if stride > 0 && stride >= width => stride,
+		_ => return Err(BlurError::UnsupportedParameter { parameter: "stride", reason: "must be a whole number of pixels, and at least `width` of them" })
+	};
+
+	let len = match height {
+		0 => Some(0),
+		height => (height - 1).checked_mul(stride).and_then(|len| len.checked_add(width))
+	};
+
+	if !matches!(len, Some(len) if len <= buffer.len() / N) {
+		return Err(BlurError::UnsupportedParameter { parameter: "buffer", reason: "must be large enough for `width`, `height` and `stride`" });
+	}
+
+	// `[u8; N]` has the same alignment as `u8`, and this only covers the whole
+	// pixels at the start of `buffer`.
+	let pixels = unsafe { std::slice::from_raw_parts_mut(buffer.as_mut_ptr().cast::<[u8; N]>(), buffer.len() / N) };
+	Ok(ImgRefMut::new_stride(pixels, width, height, stride))
+}
+
+/// Wraps a raw buffer in an image of `[u8; N]` pixels, where `N` is the number
+/// of channels in the layout, and evaluates `$blur` with `$image` set to it.
+/// Results in an error instead if the buffer does not fit the dimensions.
+macro_rules! with_image {
+	($buffer:expr, $width:expr, $height:expr, $stride:expr, $layout:expr, |$image:ident: $n:ident| $blur:expr) => {
+		match $layout.channels() {
+			1 => { const $n: usize = 1; raw_image::<$n>($buffer, $width, $height, $stride).map(|mut image| { let $image = &mut image; $blur }) }
+			2 => { const $n: usize = 2; raw_image::<$n>($buffer, $width, $height, $stride).map(|mut image| { let $image = &mut image; $blur }) }
+			3 => { const $n: usize = 3; raw_image::<$n>($buffer, $width, $height, $stride).map(|mut image| { let $image = &mut image; $blur }) }
+			4 => { const $n: usize = 4; raw_image::<$n>($buffer, $width, $height, $stride).map(|mut image| { let $image = &mut image; $blur }) }
+			_ => unreachable!()
+		}
+	};
+}
+
+/// Blurs a buffer of pixels with one byte per channel, in the given layout.
+///
+/// This blurs every channel of a pixel the same way that
+/// [`blur_argb`](crate::blur_argb) blurs those of a packed pixel, so it uses 32
+/// bits per channel, or 64 bits when the radius is larger than
+/// [`ARGB_U32_MAX_RADIUS`](crate::ARGB_U32_MAX_RADIUS).
+///
+/// Panics if `layout` does not have `N` channels.
+///
+/// Note that this function is *linear*. For sRGB, see [`blur_bytes_srgb`].
+pub fn blur_bytes<const N: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) {
+	assert_layout::<N>(layout);
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::blur(buffer, radius, from_bytes::<StackBlurrableU32, N>, to_bytes);
+	} else {
+		crate::blur(buffer, radius, from_bytes::<StackBlurrableU64, N>, to_bytes);
+	}
+}
+
+/// Like [`blur_bytes`], but returns a [`BlurError`] if the radius is too large
+/// or the layout does not match the pixels.
+pub fn try_blur_bytes<const N: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> {
+	check_layout::<N>(layout)?;
+	check_radius(ARGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::blur(buffer, radius, from_bytes::<StackBlurrableU32, N>, to_bytes);
+	} else {
+		crate::blur(buffer, radius, from_bytes::<StackBlurrableU64, N>, to_bytes);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of sRGB pixels with one byte per channel, in the given
+/// layout.
+///
+/// This blurs every channel of a pixel the same way that
+/// [`blur_srgb`](crate::blur_srgb) blurs those of a packed pixel, so it uses 32
+/// bits per channel, or 64 bits when the radius is larger than
+/// [`SRGB_U32_MAX_RADIUS`](crate::SRGB_U32_MAX_RADIUS).
+///
+/// Panics if `layout` does not have `N` channels.
+///
+/// Note that this function uses *sRGB*. For linear, see [`blur_bytes`].
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn blur_bytes_srgb<const N: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) {
+	assert_layout::<N>(layout);
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::blur(buffer, radius, from_bytes_srgb::<StackBlurrableU32, N>, to_bytes_srgb);
+	} else {
+		crate::blur(buffer, radius, from_bytes_srgb::<StackBlurrableU64, N>, to_bytes_srgb);
+	}
+}
+
+/// Like [`blur_bytes_srgb`], but returns a [`BlurError`] if the radius is too
+/// large or the layout does not match the pixels.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn try_blur_bytes_srgb<const N: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> {
+	check_layout::<N>(layout)?;
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::blur(buffer, radius, from_bytes_srgb::<StackBlurrableU32, N>, to_bytes_srgb);
+	} else {
+		crate::blur(buffer, radius, from_bytes_srgb::<StackBlurrableU64, N>, to_bytes_srgb);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of pixels with one byte per channel in parallel, in the given
+/// layout.
+///
+/// This blurs every channel of a pixel the same way that
+/// [`par_blur_argb`](crate::par_blur_argb) blurs those of a packed pixel, so it
+/// uses 32 bits per channel, or 64 bits when the radius is larger than
+/// [`ARGB_U32_MAX_RADIUS`](crate::ARGB_U32_MAX_RADIUS).
+///
+/// Panics if `layout` does not have `N` channels.
+///
+/// Note that this function is *linear*. For sRGB, see [`par_blur_bytes_srgb`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_bytes<const N: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) {
+	assert_layout::<N>(layout);
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::par_blur(buffer, radius, from_bytes::<StackBlurrableU32, N>, to_bytes);
+	} else {
+		crate::par_blur(buffer, radius, from_bytes::<StackBlurrableU64, N>, to_bytes);
+	}
+}
+
+/// Like [`par_blur_bytes`], but returns a [`BlurError`] if the radius is too
+/// large or the layout does not match the pixels.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_bytes<const N: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> {
+	check_layout::<N>(layout)?;
+	check_radius(ARGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::par_blur(buffer, radius, from_bytes::<StackBlurrableU32, N>, to_bytes);
+	} else {
+		crate::par_blur(buffer, radius, from_bytes::<StackBlurrableU64, N>, to_bytes);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of sRGB pixels with one byte per channel in parallel, in the
+/// given layout.
+///
+/// This blurs every channel of a pixel the same way that
+/// [`par_blur_srgb`](crate::par_blur_srgb) blurs those of a packed pixel, so it
+/// uses 32 bits per channel, or 64 bits when the radius is larger than
+/// [`SRGB_U32_MAX_RADIUS`](crate::SRGB_U32_MAX_RADIUS).
+///
+/// Panics if `layout` does not have `N` channels.
+///
+/// Note that this function uses *sRGB*. For linear, see [`par_blur_bytes`].
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn par_blur_bytes_srgb<const N: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) {
+	assert_layout::<N>(layout);
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::par_blur(buffer, radius, from_bytes_srgb::<StackBlurrableU32, N>, to_bytes_srgb);
+	} else {
+		crate::par_blur(buffer, radius, from_bytes_srgb::<StackBlurrableU64, N>, to_bytes_srgb);
+	}
+}
+
+/// Like [`par_blur_bytes_srgb`], but returns a [`BlurError`] if the radius is
+/// too large or the layout does not match the pixels.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn try_par_blur_bytes_srgb<const N: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> {
+	check_layout::<N>(layout)?;
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::par_blur(buffer, radius, from_bytes_srgb::<StackBlurrableU32, N>, to_bytes_srgb);
+	} else {
+		crate::par_blur(buffer, radius, from_bytes_srgb::<StackBlurrableU64, N>, to_bytes_srgb);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of pixels with one byte per channel with SIMD, in the given
+/// layout.
+///
+/// This blurs every channel of a pixel the same way that
+/// [`simd_blur_argb`](crate::simd_blur_argb) blurs those of a packed pixel, so
+/// it uses 32 bits per channel, or 64 bits when the radius is larger than
+/// [`ARGB_U32_MAX_RADIUS`](crate::ARGB_U32_MAX_RADIUS).
+///
+/// Panics if `layout` does not have `N` channels.
+///
+/// Note that this function is *linear*. For sRGB, see [`simd_blur_bytes_srgb`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_bytes<const N: usize, const LANES: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	assert_layout::<N>(layout);
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::simd_blur(buffer, radius,
+			from_bytesxN::<StackBlurrableU32xN<LANES>, N, LANES>, to_bytesxN,
+			from_bytes::<StackBlurrableU32, N>, to_bytes
+		);
+	} else {
+		crate::simd_blur(buffer, radius,
+			from_bytesxN::<StackBlurrableU64xN<LANES>, N, LANES>, to_bytesxN,
+			from_bytes::<StackBlurrableU64, N>, to_bytes
+		);
+	}
+}
+
+/// Like [`simd_blur_bytes`], but returns a [`BlurError`] if the radius is too
+/// large or the layout does not match the pixels.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur_bytes<const N: usize, const LANES: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_layout::<N>(layout)?;
+	check_radius(ARGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::simd_blur(buffer, radius,
+			from_bytesxN::<StackBlurrableU32xN<LANES>, N, LANES>, to_bytesxN,
+			from_bytes::<StackBlurrableU32, N>, to_bytes
+		);
+	} else {
+		crate::simd_blur(buffer, radius,
+			from_bytesxN::<StackBlurrableU64xN<LANES>, N, LANES>, to_bytesxN,
+			from_bytes::<StackBlurrableU64, N>, to_bytes
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of sRGB pixels with one byte per channel with SIMD, in the
+/// given layout.
+///
+/// This blurs every channel of a pixel the same way that
+/// [`simd_blur_srgb`](crate::simd_blur_srgb) blurs those of a packed pixel, so
+/// it uses 32 bits per channel, or 64 bits when the radius is larger than
+/// [`SRGB_U32_MAX_RADIUS`](crate::SRGB_U32_MAX_RADIUS).
+///
+/// Panics if `layout` does not have `N` channels.
+///
+/// Note that this function uses *sRGB*. For linear, see [`simd_blur_bytes`].
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn simd_blur_bytes_srgb<const N: usize, const LANES: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	assert_layout::<N>(layout);
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::simd_blur(buffer, radius,
+			from_bytesxN_srgb::<StackBlurrableU32xN<LANES>, N, LANES>, to_bytesxN_srgb,
+			from_bytes_srgb::<StackBlurrableU32, N>, to_bytes_srgb
+		);
+	} else {
+		crate::simd_blur(buffer, radius,
+			from_bytesxN_srgb::<StackBlurrableU64xN<LANES>, N, LANES>, to_bytesxN_srgb,
+			from_bytes_srgb::<StackBlurrableU64, N>, to_bytes_srgb
+		);
+	}
+}
+
+/// Like [`simd_blur_bytes_srgb`], but returns a [`BlurError`] if the radius is
+/// too large or the layout does not match the pixels.
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn try_simd_blur_bytes_srgb<const N: usize, const LANES: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_layout::<N>(layout)?;
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::simd_blur(buffer, radius,
+			from_bytesxN_srgb::<StackBlurrableU32xN<LANES>, N, LANES>, to_bytesxN_srgb,
+			from_bytes_srgb::<StackBlurrableU32, N>, to_bytes_srgb
+		);
+	} else {
+		crate::simd_blur(buffer, radius,
+			from_bytesxN_srgb::<StackBlurrableU64xN<LANES>, N, LANES>, to_bytesxN_srgb,
+			from_bytes_srgb::<StackBlurrableU64, N>, to_bytes_srgb
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of pixels with one byte per channel with SIMD in parallel, in
+/// the given layout.
+///
+/// This blurs every channel of a pixel the same way that
+/// [`par_simd_blur_argb`](crate::par_simd_blur_argb) blurs those of a packed
+/// pixel, so it uses 32 bits per channel, or 64 bits when the radius is larger
+/// than [`ARGB_U32_MAX_RADIUS`](crate::ARGB_U32_MAX_RADIUS).
+///
+/// Panics if `layout` does not have `N` channels.
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_simd_blur_bytes_srgb`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_bytes<const N: usize, const LANES: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	assert_layout::<N>(layout);
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::par_simd_blur(buffer, radius,
+			from_bytesxN::<StackBlurrableU32xN<LANES>, N, LANES>, to_bytesxN,
+			from_bytes::<StackBlurrableU32, N>, to_bytes
+		);
+	} else {
+		crate::par_simd_blur(buffer, radius,
+			from_bytesxN::<StackBlurrableU64xN<LANES>, N, LANES>, to_bytesxN,
+			from_bytes::<StackBlurrableU64, N>, to_bytes
+		);
+	}
+}
+
+/// Like [`par_simd_blur_bytes`], but returns a [`BlurError`] if the radius is
+/// too large or the layout does not match the pixels.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur_bytes<const N: usize, const LANES: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_layout::<N>(layout)?;
+	check_radius(ARGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::par_simd_blur(buffer, radius,
+			from_bytesxN::<StackBlurrableU32xN<LANES>, N, LANES>, to_bytesxN,
+			from_bytes::<StackBlurrableU32, N>, to_bytes
+		);
+	} else {
+		crate::par_simd_blur(buffer, radius,
+			from_bytesxN::<StackBlurrableU64xN<LANES>, N, LANES>, to_bytesxN,
+			from_bytes::<StackBlurrableU64, N>, to_bytes
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of sRGB pixels with one byte per channel with SIMD in
+/// parallel, in the given layout.
+///
+/// This blurs every channel of a pixel the same way that
+/// [`par_simd_blur_srgb`](crate::par_simd_blur_srgb) blurs those of a packed
+/// pixel, so it uses 32 bits per channel, or 64 bits when the radius is larger
+/// than [`SRGB_U32_MAX_RADIUS`](crate::SRGB_U32_MAX_RADIUS).
+///
+/// Panics if `layout` does not have `N` channels.
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_simd_blur_bytes`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn par_simd_blur_bytes_srgb<const N: usize, const LANES: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	assert_layout::<N>(layout);
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::par_simd_blur(buffer, radius,
+			from_bytesxN_srgb::<StackBlurrableU32xN<LANES>, N, LANES>, to_bytesxN_srgb,
+			from_bytes_srgb::<StackBlurrableU32, N>, to_bytes_srgb
+		);
+	} else {
+		crate::par_simd_blur(buffer, radius,
+			from_bytesxN_srgb::<StackBlurrableU64xN<LANES>, N, LANES>, to_bytesxN_srgb,
+			from_bytes_srgb::<StackBlurrableU64, N>, to_bytes_srgb
+		);
+	}
+}
+
+/// Like [`par_simd_blur_bytes_srgb`], but returns a [`BlurError`] if the radius
+/// is too large or the layout does not match the pixels.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn try_par_simd_blur_bytes_srgb<const N: usize, const LANES: usize>(buffer: &mut ImgRefMut<[u8; N]>, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_layout::<N>(layout)?;
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		crate::par_simd_blur(buffer, radius,
+			from_bytesxN_srgb::<StackBlurrableU32xN<LANES>, N, LANES>, to_bytesxN_srgb,
+			from_bytes_srgb::<StackBlurrableU32, N>, to_bytes_srgb
+		);
+	} else {
+		crate::par_simd_blur(buffer, radius,
+			from_bytesxN_srgb::<StackBlurrableU64xN<LANES>, N, LANES>, to_bytesxN_srgb,
+			from_bytes_srgb::<StackBlurrableU64, N>, to_bytes_srgb
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a raw buffer of pixels with one byte per channel, in the given layout.
+///
+/// This is a version of [`blur_bytes`] for buffers that are not split up into
+/// pixels. `stride` is the distance between the starts of two rows in bytes,
+/// which must be a whole number of pixels. Panics if it is not, or if the
+/// buffer is too small for `width`, `height` and `stride`.
+pub fn blur_raw_bytes(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) {
+	with_image!(buffer, width, height, stride, layout, |image: N| blur_bytes::<N>(image, layout, radius)).unwrap_or_else(|error| panic!("{}", error));
+}
+
+/// Like [`blur_raw_bytes`], but returns a [`BlurError`] if the radius is too
+/// large or the buffer does not fit the dimensions.
+pub fn try_blur_raw_bytes(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> {
+	with_image!(buffer, width, height, stride, layout, |image: N| try_blur_bytes::<N>(image, layout, radius))?
+}
+
+/// Blurs a raw buffer of sRGB pixels with one byte per channel, in the given
+/// layout.
+///
+/// This is a version of [`blur_bytes_srgb`] for buffers that are not split up
+/// into pixels. `stride` is the distance between the starts of two rows in
+/// bytes, which must be a whole number of pixels. Panics if it is not, or if
+/// the buffer is too small for `width`, `height` and `stride`.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn blur_raw_bytes_srgb(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) {
+	with_image!(buffer, width, height, stride, layout, |image: N| blur_bytes_srgb::<N>(image, layout, radius)).unwrap_or_else(|error| panic!("{}", error));
+}
+
+/// Like [`blur_raw_bytes_srgb`], but returns a [`BlurError`] if the radius is
+/// too large or the buffer does not fit the dimensions.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn try_blur_raw_bytes_srgb(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> {
+	with_image!(buffer, width, height, stride, layout, |image: N| try_blur_bytes_srgb::<N>(image, layout, radius))?
+}
+
+/// Blurs a raw buffer of pixels with one byte per channel in parallel, in the
+/// given layout.
+///
+/// This is a version of [`par_blur_bytes`] for buffers that are not split up
+/// into pixels. `stride` is the distance between the starts of two rows in
+/// bytes, which must be a whole number of pixels. Panics if it is not, or if
+/// the buffer is too small for `width`, `height` and `stride`.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_raw_bytes(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) {
+	with_image!(buffer, width, height, stride, layout, |image: N| par_blur_bytes::<N>(image, layout, radius)).unwrap_or_else(|error| panic!("{}", error));
+}
+
+/// Like [`par_blur_raw_bytes`], but returns a [`BlurError`] if the radius is
+/// too large or the buffer does not fit the dimensions.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_raw_bytes(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> {
+	with_image!(buffer, width, height, stride, layout, |image: N| try_par_blur_bytes::<N>(image, layout, radius))?
+}
+
+/// Blurs a raw buffer of sRGB pixels with one byte per channel in parallel, in
+/// the given layout.
+///
+/// This is a version of [`par_blur_bytes_srgb`] for buffers that are not split
+/// up into pixels. `stride` is the distance between the starts of two rows in
+/// bytes, which must be a whole number of pixels. Panics if it is not, or if
+/// the buffer is too small for `width`, `height` and `stride`.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn par_blur_raw_bytes_srgb(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) {
+	with_image!(buffer, width, height, stride, layout, |image: N| par_blur_bytes_srgb::<N>(image, layout, radius)).unwrap_or_else(|error| panic!("{}", error));
+}
+
+/// Like [`par_blur_raw_bytes_srgb`], but returns a [`BlurError`] if the radius
+/// is too large or the buffer does not fit the dimensions.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn try_par_blur_raw_bytes_srgb(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> {
+	with_image!(buffer, width, height, stride, layout, |image: N| try_par_blur_bytes_srgb::<N>(image, layout, radius))?
+}
+
+/// Blurs a raw buffer of pixels with one byte per channel with SIMD, in the
+/// given layout.
+///
+/// This is a version of [`simd_blur_bytes`] for buffers that are not split up
+/// into pixels. `stride` is the distance between the starts of two rows in
+/// bytes, which must be a whole number of pixels. Panics if it is not, or if
+/// the buffer is too small for `width`, `height` and `stride`.
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_raw_bytes<const LANES: usize>(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	with_image!(buffer, width, height, stride, layout, |image: N| simd_blur_bytes::<N, LANES>(image, layout, radius)).unwrap_or_else(|error| panic!("{}", error));
+}
+
+/// Like [`simd_blur_raw_bytes`], but returns a [`BlurError`] if the radius is
+/// too large or the buffer does not fit the dimensions.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur_raw_bytes<const LANES: usize>(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	with_image!(buffer, width, height, stride, layout, |image: N| try_simd_blur_bytes::<N, LANES>(image, layout, radius))?
+}
+
+/// Blurs a raw buffer of sRGB pixels with one byte per channel with SIMD, in
+/// the given layout.
+///
+/// This is a version of [`simd_blur_bytes_srgb`] for buffers that are not split
+/// up into pixels. `stride` is the distance between the starts of two rows in
+/// bytes, which must be a whole number of pixels. Panics if it is not, or if
+/// the buffer is too small for `width`, `height` and `stride`.
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn simd_blur_raw_bytes_srgb<const LANES: usize>(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	with_image!(buffer, width, height, stride, layout, |image: N| simd_blur_bytes_srgb::<N, LANES>(image, layout, radius)).unwrap_or_else(|error| panic!("{}", error));
+}
+
+/// Like [`simd_blur_raw_bytes_srgb`], but returns a [`BlurError`] if the radius
+/// is too large or the buffer does not fit the dimensions.
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn try_simd_blur_raw_bytes_srgb<const LANES: usize>(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	with_image!(buffer, width, height, stride, layout, |image: N| try_simd_blur_bytes_srgb::<N, LANES>(image, layout, radius))?
+}
+
+/// Blurs a raw buffer of pixels with one byte per channel with SIMD in
+/// parallel, in the given layout.
+///
+/// This is a version of [`par_simd_blur_bytes`] for buffers that are not split
+/// up into pixels. `stride` is the distance between the starts of two rows in
+/// bytes, which must be a whole number of pixels. Panics if it is not, or if
+/// the buffer is too small for `width`, `height` and `stride`.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_raw_bytes<const LANES: usize>(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	with_image!(buffer, width, height, stride, layout, |image: N| par_simd_blur_bytes::<N, LANES>(image, layout, radius)).unwrap_or_else(|error| panic!("{}", error));
+}
+
+/// Like [`par_simd_blur_raw_bytes`], but returns a [`BlurError`] if the radius
+/// is too large or the buffer does not fit the dimensions.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur_raw_bytes<const LANES: usize>(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	with_image!(buffer, width, height, stride, layout, |image: N| try_par_simd_blur_bytes::<N, LANES>(image, layout, radius))?
+}
+
+/// Blurs a raw buffer of sRGB pixels with one byte per channel with SIMD in
+/// parallel, in the given layout.
+///
+/// This is a version of [`par_simd_blur_bytes_srgb`] for buffers that are not
+/// split up into pixels. `stride` is the distance between the starts of two
+/// rows in bytes, which must be a whole number of pixels. Panics if it is not,
+/// or if the buffer is too small for `width`, `height` and `stride`.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn par_simd_blur_raw_bytes_srgb<const LANES: usize>(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	with_image!(buffer, width, height, stride, layout, |image: N| par_simd_blur_bytes_srgb::<N, LANES>(image, layout, radius)).unwrap_or_else(|error| panic!("{}", error));
+}
+
+/// Like [`par_simd_blur_raw_bytes_srgb`], but returns a [`BlurError`] if the
+/// radius is too large or the buffer does not fit the dimensions.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn try_par_simd_blur_raw_bytes_srgb<const LANES: usize>(buffer: &mut [u8], width: usize, height: usize, stride: usize, layout: ChannelLayout, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	with_image!(buffer, width, height, stride, layout, |image: N| try_par_simd_blur_bytes_srgb::<N, LANES>(image, layout, radius))?
+}
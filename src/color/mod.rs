@@ -1,101 +1,363 @@
-use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+//! The pixel types that the `_argb` and `_srgb` helpers in the crate root blur,
+//! for use with [`blur`](crate::blur), [`simd_blur`](crate::simd_blur) and the
+//! other generic helpers.
+//!
+//! [`Argb`] holds the four channels of a pixel, each as its own
+//! [`StackBlurrable`], and converts from and to 32-bit packed pixels
+//! (0xAARRGGBB). The channels are [`StackBlurrableU32`] or
+//! [`StackBlurrableU64`], which use wrapping arithmetic, or with the `simd`
+//! feature, [`StackBlurrableU32xN`] or [`StackBlurrableU64xN`], which hold the
+//! same channel of `N` pixels at once. [`Channels`] holds any number of
+//! channels of any [`StackBlurrable`], for pixels that are not ARGB.
+//!
+//! For example, this blurs pixels whose bytes are in RGBA order with the same
+//! conversions and arithmetic as [`blur_argb`](crate::blur_argb):
+//!
+//! ```
+//! use stackblur_iter::color::ArgbU32;
+//! # let mut pixels = vec![0u32; 16];
+//! # let mut buffer = stackblur_iter::imgref::ImgRefMut::new(&mut pixels, 4, 4);
+//!
+//! let to_blurrable = |rgba: &u32| ArgbU32::from_u32(rgba.rotate_right(8));
+//! let to_pixel = |argb: ArgbU32| argb.to_u32().rotate_left(8);
+//! stackblur_iter::blur(&mut buffer, 2, to_blurrable, to_pixel);
+//! ```
+
+use std::ops::{Add, AddAssign, BitOr, Div, Mul, Sub, SubAssign};
 use crate::StackBlurrable;
 
 pub mod serial;
 #[cfg(feature = "simd")]
 pub mod simd;
 
-use serial::StackBlurrableU32;
+pub use serial::{Channel, StackBlurrableU32, StackBlurrableU64};
+#[cfg(feature = "simd")]
+pub use simd::{ChannelxN, StackBlurrableU32xN, StackBlurrableU64xN};
+
+/// [`Argb`] with 32 bits per channel.
+pub type ArgbU32 = Argb<StackBlurrableU32>;
+/// [`Argb`] with 64 bits per channel, for radii that would overflow [`ArgbU32`].
+pub type ArgbU64 = Argb<StackBlurrableU64>;
+/// [`Argb`] with 32 bits per channel, `N` pixels at a time.
+#[cfg(feature = "simd")]
+pub type ArgbU32xN<const N: usize> = Argb<StackBlurrableU32xN<N>>;
+/// [`Argb`] with 64 bits per channel, `N` pixels at a time.
 #[cfg(feature = "simd")]
-use simd::StackBlurrableU32xN;
+pub type ArgbU64xN<const N: usize> = Argb<StackBlurrableU64xN<N>>;
+
+/// The scale that premultiplied channels are stored at. This leaves room for
+/// the 12-bit linear values of sRGB, and keeps some precision in the color of
+/// mostly transparent pixels.
+const PREMULTIPLIED_MAX: u32 = 4095;
+
+/// Premultiplies a pixel with straight alpha, after running its color channels
+/// through `decode`, which maps them to `0..=max`.
+fn premultiply(argb: u32, max: u32, decode: impl Fn(u8) -> u32) -> [u32; 4] {
+	let [a, r, g, b] = argb.to_be_bytes();
+	let alpha = (a as u32 * PREMULTIPLIED_MAX + 127) / 255;
+	let cvt = |i| (decode(i) * alpha + max / 2) / max;
+	[alpha, cvt(r), cvt(g), cvt(b)]
+}
+
+/// The inverse of [`premultiply`]. Fully transparent pixels come out black.
+fn unpremultiply(channels: [u32; 4], max: u32, encode: impl Fn(u32) -> u8) -> u32 {
+	let [alpha, r, g, b] = channels;
+	let a = (alpha * 255 + PREMULTIPLIED_MAX / 2) / PREMULTIPLIED_MAX;
+	let cvt = |i: u32| (i * max + alpha / 2).checked_div(alpha).map_or(0, |i| encode(i.min(max)));
+	u32::from_be_bytes([a as u8, cvt(r), cvt(g), cvt(b)])
+}
+
+/// Converts an 8-bit pixel with premultiplied alpha to straight alpha.
+#[cfg(feature = "blend-srgb")]
+fn straighten_u32(argb: u32) -> u32 {
+	let [a, r, g, b] = argb.to_be_bytes();
+	let cvt = |i: u8| (i as u32 * 255 + a as u32 / 2).checked_div(a as u32).map_or(0, |i| i.min(255) as u8);
+	u32::from_be_bytes([a, cvt(r), cvt(g), cvt(b)])
+}
+
+/// Converts an 8-bit pixel with straight alpha to premultiplied alpha.
+#[cfg(feature = "blend-srgb")]
+fn premultiply_u32(argb: u32) -> u32 {
+	let [a, r, g, b] = argb.to_be_bytes();
+	let cvt = |i: u8| ((i as u32 * a as u32 + 127) / 255) as u8;
+	u32::from_be_bytes([a, cvt(r), cvt(g), cvt(b)])
+}
+
+/// A set of the channels of a 32-bit packed pixel (0xAARRGGBB), for helpers
+/// like [`blur_argb_masked`](crate::blur_argb_masked) that only blur some of
+/// them. Masks can be combined with `|`, like
+/// `ChannelMask::RED | ChannelMask::ALPHA`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ChannelMask(u8);
+
+impl ChannelMask {
+	/// No channels at all.
+	pub const NONE: Self = Self(0);
+	/// The alpha channel, bits 24 to 31.
+	pub const ALPHA: Self = Self(1 << 3);
+	/// The red channel, bits 16 to 23.
+	pub const RED: Self = Self(1 << 2);
+	/// The green channel, bits 8 to 15.
+	pub const GREEN: Self = Self(1 << 1);
+	/// The blue channel, bits 0 to 7.
+	pub const BLUE: Self = Self(1);
+	/// The red, green and blue channels, but not alpha.
+	pub const COLOR: Self = Self(0b0111);
+	/// All four channels.
+	pub const ALL: Self = Self(0b1111);
+
+	/// Returns the channels that are in either mask.
+	pub const fn union(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+
+	/// Returns whether every channel in `other` is also in this mask.
+	pub const fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	/// Returns the number of channels in the mask.
+	pub const fn count(self) -> usize {
+		self.0.count_ones() as usize
+	}
+
+	/// Returns whether the mask has no channels.
+	pub const fn is_empty(self) -> bool {
+		self.0 == 0
+	}
+
+	/// Returns the bits of a packed pixel that the channels in the mask take up.
+	pub(crate) const fn bits(self) -> u32 {
+		let mut bits = 0;
+		let mut channel = 0;
+
+		while channel < 4 {
+			if self.0 & (1 << channel) != 0 {
+				bits |= 0xFF << (channel * 8);
+			}
+
+			channel += 1;
+		}
+
+		bits
+	}
+
+	/// Returns how far each channel in the mask is shifted in a packed pixel,
+	/// from alpha to blue. Panics if the mask does not have `K` channels.
+	pub(crate) fn shifts<const K: usize>(self) -> [u32; K] {
+		assert_eq!(self.count(), K, "the mask must have `K` channels");
+		let mut shifts = (0..4).rev().filter(|channel| self.0 & (1 << channel) != 0).map(|channel| channel * 8);
+		std::array::from_fn(|_| shifts.next().unwrap())
+	}
+}
 
+impl BitOr for ChannelMask {
+	type Output = Self;
+
+	fn bitor(self, rhs: Self) -> Self::Output {
+		self.union(rhs)
+	}
+}
+
+/// The alpha, red, green and blue channels of a pixel, each of which is blurred
+/// separately.
+///
+/// The conversions from and to packed pixels keep the channels in the ranges
+/// that the helpers in the crate root size their sums for: 0 to 255 for linear
+/// pixels, and 0 to 4095 for sRGB pixels, which are blurred in 12-bit linear
+/// light, and for pixels with straight alpha, which are premultiplied at 12
+/// bits. Each conversion to a packed pixel is the inverse of the conversion
+/// from one with the same name.
+///
+/// With channels that implement [`ChannelxN`], one [`Argb`] holds `N` pixels,
+/// and the `xN` conversions convert all of them at once.
 #[repr(transparent)]
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
 pub struct Argb<T: StackBlurrable>([T; 4]);
 
-impl Argb<StackBlurrableU32> {
+impl<T: StackBlurrable> Argb<T> {
+	/// Creates an [`Argb`] from its alpha, red, green and blue channels, in that
+	/// order.
+	pub const fn new(channels: [T; 4]) -> Self {
+		Self(channels)
+	}
+
+	/// Returns the alpha, red, green and blue channels, in that order.
+	pub fn channels(&self) -> &[T; 4] {
+		&self.0
+	}
+
+	/// Returns the alpha, red, green and blue channels, in that order.
+	pub fn into_channels(self) -> [T; 4] {
+		self.0
+	}
+}
+
+impl<T: Channel> Argb<T> {
+	/// Converts a linear packed pixel (0xAARRGGBB), with channels from 0 to 255.
 	pub fn from_u32(argb: u32) -> Self {
 		let [a, r, g, b] = argb.to_be_bytes();
-		let cvt = |i| StackBlurrableU32(i as u32);
+		let cvt = |i| T::from_u32(i as u32);
 		Self([cvt(a), cvt(r), cvt(g), cvt(b)])
 	}
 
+	/// Converts to a linear packed pixel (0xAARRGGBB).
 	pub fn to_u32(self) -> u32 {
 		let [a, r, g, b] = self.0;
-		let cvt = |i: StackBlurrableU32| i.0 as u8;
+		let cvt = |i: T| i.to_u32() as u8;
 		u32::from_be_bytes([cvt(a), cvt(r), cvt(g), cvt(b)])
 	}
 
+	/// Converts an sRGB packed pixel (0xAARRGGBB) to 12-bit linear light, with
+	/// channels from 0 to 4095.
 	#[cfg(feature = "blend-srgb")]
 	pub fn from_u32_srgb(argb: u32) -> Self {
 		use blend_srgb::convert::srgb8_to_rgb12;
 
 		let [a, r, g, b] = argb.to_be_bytes();
-		let cvt = |i| StackBlurrableU32(srgb8_to_rgb12(i) as u32);
+		let cvt = |i| T::from_u32(srgb8_to_rgb12(i) as u32);
 		Self([cvt(a), cvt(r), cvt(g), cvt(b)])
 	}
 
+	/// Converts from 12-bit linear light to an sRGB packed pixel (0xAARRGGBB).
 	#[cfg(feature = "blend-srgb")]
 	pub fn to_u32_srgb(self) -> u32 {
 		use blend_srgb::convert::rgb12_to_srgb8;
 
 		let [a, r, g, b] = self.0;
-		let [a, r, g, b] = [a.0 as u16, r.0 as u16, g.0 as u16, b.0 as u16];
+		let [a, r, g, b] = [a, r, g, b].map(|i| i.to_u32() as u16);
 		let cvt = |i| rgb12_to_srgb8(i) as u8;
 		u32::from_be_bytes([cvt(a), cvt(r), cvt(g), cvt(b)])
 	}
+
+	/// Converts a linear packed pixel (0xAARRGGBB) with straight alpha to
+	/// premultiplied alpha, with channels from 0 to 4095.
+	pub fn from_u32_straight(argb: u32) -> Self {
+		Self(premultiply(argb, 255, u32::from).map(T::from_u32))
+	}
+
+	/// Converts to a linear packed pixel (0xAARRGGBB) with straight alpha. Fully
+	/// transparent pixels come out black.
+	pub fn to_u32_straight(self) -> u32 {
+		unpremultiply(self.0.map(T::to_u32), 255, |i| i as u8)
+	}
+
+	/// Converts an sRGB packed pixel (0xAARRGGBB) with straight alpha to 12-bit
+	/// linear light with premultiplied alpha, with channels from 0 to 4095.
+	#[cfg(feature = "blend-srgb")]
+	pub fn from_u32_srgb_straight(argb: u32) -> Self {
+		use blend_srgb::convert::srgb8_to_rgb12;
+
+		Self(premultiply(argb, 4095, |i| srgb8_to_rgb12(i) as u32).map(T::from_u32))
+	}
+
+	/// Converts to an sRGB packed pixel (0xAARRGGBB) with straight alpha. Fully
+	/// transparent pixels come out black.
+	#[cfg(feature = "blend-srgb")]
+	pub fn to_u32_srgb_straight(self) -> u32 {
+		use blend_srgb::convert::rgb12_to_srgb8;
+
+		unpremultiply(self.0.map(T::to_u32), 4095, |i| rgb12_to_srgb8(i as u16))
+	}
+
+	/// Converts an sRGB packed pixel (0xAARRGGBB) with premultiplied alpha to
+	/// 12-bit linear light with premultiplied alpha, with channels from 0 to
+	/// 4095. The color is premultiplied in linear light rather than in sRGB.
+	///
+	/// sRGB can't be decoded to linear light until the alpha is divided back
+	/// out of it, so the pixel is converted to 8-bit straight alpha first. That
+	/// loses precision at low alpha, because a premultiplied channel only has
+	/// `alpha + 1` possible values: at an alpha of 4, the straight color can
+	/// only be one of 5 levels about 64 apart, and that is all the precision
+	/// that it has in linear light as well.
+	#[cfg(feature = "blend-srgb")]
+	pub fn from_u32_srgb_premultiplied(argb: u32) -> Self {
+		Self::from_u32_srgb_straight(straighten_u32(argb))
+	}
+
+	/// Converts to an sRGB packed pixel (0xAARRGGBB) with premultiplied alpha.
+	#[cfg(feature = "blend-srgb")]
+	pub fn to_u32_srgb_premultiplied(self) -> u32 {
+		premultiply_u32(self.to_u32_srgb_straight())
+	}
 }
 
 #[allow(non_snake_case)]
 #[cfg(feature = "simd")]
-impl<const N: usize> Argb<StackBlurrableU32xN<N>> where simd::LaneCount<N>: simd::SupportedLaneCount {
-	pub fn from_u32xN(pixels: [u32; N]) -> Self {
-		let arrs: [[u8; 4]; N] = pixels.map(u32::to_be_bytes);
-		let a = simd::Simd::<u32, N>::from_array(arrs.map(|a| a[0] as u32));
-		let r = simd::Simd::<u32, N>::from_array(arrs.map(|a| a[1] as u32));
-		let g = simd::Simd::<u32, N>::from_array(arrs.map(|a| a[2] as u32));
-		let b = simd::Simd::<u32, N>::from_array(arrs.map(|a| a[3] as u32));
-		let cvt = StackBlurrableU32xN::<N>;
-		Self([cvt(a), cvt(r), cvt(g), cvt(b)])
+impl<T: StackBlurrable> Argb<T> {
+	fn from_channels<const N: usize>(pixels: [[u32; 4]; N]) -> Self where T: ChannelxN<N> {
+		let cvt = |channel: usize| T::from_array(pixels.map(|pixel| pixel[channel]));
+		Self([cvt(0), cvt(1), cvt(2), cvt(3)])
 	}
 
-	pub fn to_u32xN(self) -> [u32; N] {
-		let [a, r, g, b] = self.0.map(|i| i.0.to_array());
+	fn to_channels<const N: usize>(self) -> [[u32; 4]; N] where T: ChannelxN<N> {
+		let [a, r, g, b] = self.0.map(T::to_array);
 
 		let mut countup = 0usize..;
 		[(); N].map(move |_| {
 			let i = countup.next().unwrap();
-			u32::from_be_bytes([a[i] as u8, r[i] as u8, g[i] as u8, b[i] as u8])
+			[a[i], r[i], g[i], b[i]]
 		})
 	}
 
+	/// Like [`from_u32`](Self::from_u32), but for `N` pixels at a time.
+	pub fn from_u32xN<const N: usize>(pixels: [u32; N]) -> Self where T: ChannelxN<N> {
+		Self::from_channels(pixels.map(|argb| argb.to_be_bytes().map(u32::from)))
+	}
+
+	/// Like [`to_u32`](Self::to_u32), but for `N` pixels at a time.
+	pub fn to_u32xN<const N: usize>(self) -> [u32; N] where T: ChannelxN<N> {
+		self.to_channels().map(|channels| u32::from_be_bytes(channels.map(|i| i as u8)))
+	}
+
+	/// Like [`from_u32_srgb`](Self::from_u32_srgb), but for `N` pixels at a time.
 	#[cfg(feature = "blend-srgb")]
-	pub fn from_u32xN_srgb(pixels: [u32; N]) -> Self {
+	pub fn from_u32xN_srgb<const N: usize>(pixels: [u32; N]) -> Self where T: ChannelxN<N> {
 		use blend_srgb::convert::srgb8_to_rgb12;
-		let arrs: [[u8; 4]; N] = pixels.map(u32::to_be_bytes);
-		let a = simd::Simd::<u32, N>::from_array(arrs.map(|a| srgb8_to_rgb12(a[0]) as u32));
-		let r = simd::Simd::<u32, N>::from_array(arrs.map(|a| srgb8_to_rgb12(a[1]) as u32));
-		let g = simd::Simd::<u32, N>::from_array(arrs.map(|a| srgb8_to_rgb12(a[2]) as u32));
-		let b = simd::Simd::<u32, N>::from_array(arrs.map(|a| srgb8_to_rgb12(a[3]) as u32));
-		let cvt = StackBlurrableU32xN;
-		Self([cvt(a), cvt(r), cvt(g), cvt(b)])
+		Self::from_channels(pixels.map(|argb| argb.to_be_bytes().map(|i| srgb8_to_rgb12(i) as u32)))
 	}
 
+	/// Like [`to_u32_srgb`](Self::to_u32_srgb), but for `N` pixels at a time.
 	#[cfg(feature = "blend-srgb")]
-	pub fn to_u32xN_srgb(self) -> [u32; N] {
+	pub fn to_u32xN_srgb<const N: usize>(self) -> [u32; N] where T: ChannelxN<N> {
 		use blend_srgb::convert::rgb12_to_srgb8;
-		let [a, r, g, b] = self.0.map(|i| i.0.to_array());
+		self.to_channels().map(|channels| u32::from_be_bytes(channels.map(|i| rgb12_to_srgb8(i as u16))))
+	}
 
-		let mut countup = 0usize..;
-		[(); N].map(move |_| {
-			let i = countup.next().unwrap();
-			u32::from_be_bytes([
-				rgb12_to_srgb8(a[i] as u16),
-				rgb12_to_srgb8(r[i] as u16),
-				rgb12_to_srgb8(g[i] as u16),
-				rgb12_to_srgb8(b[i] as u16)
-			])
-		})
+	/// Like [`from_u32_straight`](Self::from_u32_straight), but for `N` pixels at a time.
+	pub fn from_u32xN_straight<const N: usize>(pixels: [u32; N]) -> Self where T: ChannelxN<N> {
+		Self::from_channels(pixels.map(|argb| premultiply(argb, 255, u32::from)))
+	}
+
+	/// Like [`to_u32_straight`](Self::to_u32_straight), but for `N` pixels at a time.
+	pub fn to_u32xN_straight<const N: usize>(self) -> [u32; N] where T: ChannelxN<N> {
+		self.to_channels().map(|channels| unpremultiply(channels, 255, |i| i as u8))
+	}
+
+	/// Like [`from_u32_srgb_straight`](Self::from_u32_srgb_straight), but for `N` pixels at a time.
+	#[cfg(feature = "blend-srgb")]
+	pub fn from_u32xN_srgb_straight<const N: usize>(pixels: [u32; N]) -> Self where T: ChannelxN<N> {
+		use blend_srgb::convert::srgb8_to_rgb12;
+		Self::from_channels(pixels.map(|argb| premultiply(argb, 4095, |i| srgb8_to_rgb12(i) as u32)))
+	}
+
+	/// Like [`to_u32_srgb_straight`](Self::to_u32_srgb_straight), but for `N` pixels at a time.
+	#[cfg(feature = "blend-srgb")]
+	pub fn to_u32xN_srgb_straight<const N: usize>(self) -> [u32; N] where T: ChannelxN<N> {
+		use blend_srgb::convert::rgb12_to_srgb8;
+		self.to_channels().map(|channels| unpremultiply(channels, 4095, |i| rgb12_to_srgb8(i as u16)))
+	}
+
+	/// Like [`from_u32_srgb_premultiplied`](Self::from_u32_srgb_premultiplied), but for `N` pixels at a time.
+	#[cfg(feature = "blend-srgb")]
+	pub fn from_u32xN_srgb_premultiplied<const N: usize>(pixels: [u32; N]) -> Self where T: ChannelxN<N> {
+		Self::from_u32xN_srgb_straight(pixels.map(straighten_u32))
+	}
+
+	/// Like [`to_u32_srgb_premultiplied`](Self::to_u32_srgb_premultiplied), but for `N` pixels at a time.
+	#[cfg(feature = "blend-srgb")]
+	pub fn to_u32xN_srgb_premultiplied<const N: usize>(self) -> [u32; N] where T: ChannelxN<N> {
+		self.to_u32xN_srgb_straight().map(premultiply_u32)
 	}
 }
 
@@ -154,3 +416,52 @@ impl<T: StackBlurrable> Div<usize> for Argb<T> {
 		Self([a / rhs, r / rhs, g / rhs, b / rhs])
 	}
 }
+
+/// A [`StackBlurrable`] that holds `N` channels of a pixel, and blurs each of
+/// them separately. For example, `Channels<StackBlurrableF32, 4>` is an RGBA
+/// pixel with [`f32` channels](crate::float::StackBlurrableF32).
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Channels<T: StackBlurrable, const N: usize>(pub [T; N]);
+
+impl<T: StackBlurrable, const N: usize> Default for Channels<T, N> {
+	fn default() -> Self {
+		Self(std::array::from_fn(|_| T::default()))
+	}
+}
+
+impl<T: StackBlurrable, const N: usize> Add for Channels<T, N> {
+	type Output = Self;
+
+	fn add(mut self, rhs: Self) -> Self::Output {
+		self += rhs;
+		self
+	}
+}
+
+impl<T: StackBlurrable, const N: usize> AddAssign for Channels<T, N> {
+	fn add_assign(&mut self, rhs: Self) {
+		self.0.iter_mut().zip(rhs.0).for_each(|(channel, rhs)| *channel += rhs);
+	}
+}
+
+impl<T: StackBlurrable, const N: usize> SubAssign for Channels<T, N> {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.0.iter_mut().zip(rhs.0).for_each(|(channel, rhs)| *channel -= rhs);
+	}
+}
+
+impl<T: StackBlurrable, const N: usize> Mul<usize> for Channels<T, N> {
+	type Output = Self;
+
+	fn mul(self, rhs: usize) -> Self::Output {
+		Self(self.0.map(|channel| channel * rhs))
+	}
+}
+
+impl<T: StackBlurrable, const N: usize> Div<usize> for Channels<T, N> {
+	type Output = Self;
+
+	fn div(self, rhs: usize) -> Self::Output {
+		Self(self.0.map(|channel| channel / rhs))
+	}
+}
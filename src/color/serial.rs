@@ -1,5 +1,20 @@
+//! Channels that hold one pixel at a time.
+
 use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
 
+use crate::StackBlurrable;
+
+/// A [`StackBlurrable`] that holds one channel of a pixel.
+pub trait Channel: StackBlurrable + Copy {
+	/// Converts the value of a channel.
+	fn from_u32(value: u32) -> Self;
+	/// Converts back to the value of a channel. Only the low bits that the
+	/// channel uses are meaningful.
+	fn to_u32(self) -> u32;
+}
+
+/// A channel with 32 bits, which wraps around on overflow, like the sums that
+/// the `_argb` and `_srgb` helpers use at small radii.
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
 pub struct StackBlurrableU32(pub u32);
 
@@ -46,3 +61,72 @@ impl Div<usize> for StackBlurrableU32 {
 		Self(self.0.wrapping_div(rhs as u32))
 	}
 }
+
+impl Channel for StackBlurrableU32 {
+	fn from_u32(value: u32) -> Self {
+		Self(value)
+	}
+
+	fn to_u32(self) -> u32 {
+		self.0
+	}
+}
+
+/// A channel with 64 bits, which wraps around on overflow, for radii that are
+/// too large for [`StackBlurrableU32`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct StackBlurrableU64(pub u64);
+
+impl Add for StackBlurrableU64 {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		Self(self.0.wrapping_add(rhs.0))
+	}
+}
+
+impl Sub for StackBlurrableU64 {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		Self(self.0.wrapping_sub(rhs.0))
+	}
+}
+
+impl AddAssign for StackBlurrableU64 {
+	fn add_assign(&mut self, rhs: Self) {
+		self.0 = self.0.wrapping_add(rhs.0);
+	}
+}
+
+impl SubAssign for StackBlurrableU64 {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.0 = self.0.wrapping_sub(rhs.0);
+	}
+}
+
+impl Mul<usize> for StackBlurrableU64 {
+	type Output = Self;
+
+	fn mul(self, rhs: usize) -> Self::Output {
+		Self(self.0.wrapping_mul(rhs as u64))
+	}
+}
+
+impl Div<usize> for StackBlurrableU64 {
+	type Output = Self;
+
+	fn div(self, rhs: usize) -> Self::Output {
+		Self(self.0.wrapping_div(rhs as u64))
+	}
+}
+
+impl Channel for StackBlurrableU64 {
+	fn from_u32(value: u32) -> Self {
+		Self(value as u64)
+	}
+
+	fn to_u32(self) -> u32 {
+		self.0 as u32
+	}
+}
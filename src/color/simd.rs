@@ -1,7 +1,21 @@
+//! Channels that hold `N` pixels at a time, to blur `N` lines at once.
+
 use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+use std::simd::{LaneCount, Simd, SupportedLaneCount};
+
+use crate::StackBlurrable;
 
-pub use std::simd::{LaneCount, Simd, SupportedLaneCount};
+/// A [`StackBlurrable`] that holds one channel of `N` pixels.
+pub trait ChannelxN<const N: usize>: StackBlurrable + Copy {
+	/// Converts the values of the channel for each pixel.
+	fn from_array(values: [u32; N]) -> Self;
+	/// Converts back to the values of the channel for each pixel. Only the low
+	/// bits that the channel uses are meaningful.
+	fn to_array(self) -> [u32; N];
+}
 
+/// A channel of `N` pixels with 32 bits each, which wrap around on overflow,
+/// like [`StackBlurrableU32`](super::StackBlurrableU32).
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
 pub struct StackBlurrableU32xN<const N: usize>(pub Simd<u32, N>) where LaneCount<N>: SupportedLaneCount;
 
@@ -56,3 +70,72 @@ impl<const N: usize> Div<usize> for StackBlurrableU32xN<N> where LaneCount<N>: S
 		}
 	}
 }
+
+impl<const N: usize> ChannelxN<N> for StackBlurrableU32xN<N> where LaneCount<N>: SupportedLaneCount {
+	fn from_array(values: [u32; N]) -> Self {
+		Self(Simd::from_array(values))
+	}
+
+	fn to_array(self) -> [u32; N] {
+		self.0.to_array()
+	}
+}
+
+/// A channel of `N` pixels with 64 bits each, which wrap around on overflow,
+/// like [`StackBlurrableU64`](super::StackBlurrableU64).
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct StackBlurrableU64xN<const N: usize>(pub Simd<u64, N>) where LaneCount<N>: SupportedLaneCount;
+
+impl<const N: usize> Add for StackBlurrableU64xN<N> where LaneCount<N>: SupportedLaneCount {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		Self(self.0 + rhs.0)
+	}
+}
+
+impl<const N: usize> Sub for StackBlurrableU64xN<N> where LaneCount<N>: SupportedLaneCount {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		Self(self.0 - rhs.0)
+	}
+}
+
+impl<const N: usize> AddAssign for StackBlurrableU64xN<N> where LaneCount<N>: SupportedLaneCount {
+	fn add_assign(&mut self, rhs: Self) {
+		self.0 += rhs.0;
+	}
+}
+
+impl<const N: usize> SubAssign for StackBlurrableU64xN<N> where LaneCount<N>: SupportedLaneCount {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.0 -= rhs.0;
+	}
+}
+
+impl<const N: usize> Mul<usize> for StackBlurrableU64xN<N> where LaneCount<N>: SupportedLaneCount {
+	type Output = Self;
+
+	fn mul(self, rhs: usize) -> Self::Output {
+		Self(self.0 * Simd::<u64, N>::splat(rhs as u64))
+	}
+}
+
+impl<const N: usize> Div<usize> for StackBlurrableU64xN<N> where LaneCount<N>: SupportedLaneCount {
+	type Output = Self;
+
+	fn div(self, rhs: usize) -> Self::Output {
+		Self(self.0 / Simd::<u64, N>::splat(rhs as u64))
+	}
+}
+
+impl<const N: usize> ChannelxN<N> for StackBlurrableU64xN<N> where LaneCount<N>: SupportedLaneCount {
+	fn from_array(values: [u32; N]) -> Self {
+		Self(Simd::from_array(values.map(u64::from)))
+	}
+
+	fn to_array(self) -> [u32; N] {
+		self.0.to_array().map(|i| i as u32)
+	}
+}
@@ -0,0 +1,215 @@
+//! Differences between two blurs of the same image, like a difference of
+//! Gaussians.
+//!
+//! Subtracting a wide blur of an image from a narrow one keeps only the details
+//! that are larger than the narrow radius but smaller than the wide one. This
+//! band-pass filter is the basis of edge enhancement and of blob and feature
+//! detection. With a narrow radius of 0, it keeps all of the details smaller
+//! than the wide radius, which is the "high pass" that sharpening overlays are
+//! made of.
+//!
+//! The helpers in this module read every pixel of the input only once. Each row
+//! is blurred by both radii in a single scan, like a
+//! [`DifferenceBlur`](crate::iter::DifferenceBlur) does, into two planes of the
+//! [`StackBlurrable`] type, whose columns are then blurred separately and
+//! subtracted into the output. This needs room for two copies of the image in
+//! the [`StackBlurrable`] type, on top of the input and output.
+//!
+//! ## Signed output
+//!
+//! The difference is negative wherever the wide blur is brighter. The generic
+//! helpers, like [`blur_difference`], hand it to their conversion routine as
+//! the [`StackBlurrable`] type, which has to be able to hold it, the same way
+//! as for a [`DifferenceBlur`](crate::iter::DifferenceBlur#negative-differences).
+//!
+//! [`blur_difference_f32`] writes the difference of `f32` pixels as it is.
+//! [`blur_difference_argb`] writes the difference of every channel of packed
+//! ARGB pixels offset by 128 and clamped to a byte, so that 128 means no
+//! difference.
+
+use imgref::{ImgRef, ImgRefMut};
+
+use crate::traits::StackBlurrable;
+use crate::color::{Argb, ArgbU32, ArgbU64, Channel, ChannelMask};
+use crate::error::BlurError;
+use crate::float::StackBlurrableF32;
+use crate::iter::Kernel;
+use crate::{check_output_size, check_radius, fits_u32, lines, ARGB_CHANNEL_MAX};
+
+/// Offset-encodes the difference of every channel in `mask`, and keeps the
+/// other channels of `original`.
+fn encode_argb<C: Channel>(difference: Argb<C>, original: u32, mask: ChannelMask) -> u32 {
+	let encoded = u32::from_be_bytes(difference.into_channels().map(|channel| (channel.to_u32() as i32 + 128).clamp(0, 255) as u8));
+	let bits = mask.bits();
+	encoded & bits | original & !bits
+}
+
+/// Returns an error if the wider of the two radii is too large for the `_argb`
+/// helpers.
+fn check_radii(radius_narrow: usize, radius_wide: usize) -> Result<(), BlurError> {
+	check_radius(ARGB_CHANNEL_MAX, radius_narrow.max(radius_wide), |radius| Kernel::Tent.total_weight(radius))
+}
+
+/// Blurs an image by two radii, and writes the difference between the narrow
+/// and the wide blur to `output`, assuming one element per pixel.
+///
+/// `output` must be the same size as `input`, or this function panics. The
+/// rows and columns are both blurred by the same radii, and the edges are
+/// renormalized like in [`blur`](crate::blur). See the
+/// [module documentation](self) for how the difference is signed.
+///
+/// The provided closures are used to convert from the input's native pixel
+/// format to [`StackBlurrable`] values, and from their differences to the
+/// output's pixel format.
+pub fn blur_difference<T, S, B: StackBlurrable>(
+	input: ImgRef<T>,
+	output: &mut ImgRefMut<S>,
+	radius_narrow: usize,
+	radius_wide: usize,
+	to_blurrable: impl FnMut(&T) -> B,
+	mut to_output: impl FnMut(B) -> S
+) {
+	lines::blur_lines_difference(&input, output, radius_narrow, radius_wide, to_blurrable, |difference, _| to_output(difference));
+}
+
+/// Like [`blur_difference`], but returns a [`BlurError`] if a parameter is not
+/// supported.
+pub fn try_blur_difference<T, S, B: StackBlurrable>(
+	input: ImgRef<T>,
+	output: &mut ImgRefMut<S>,
+	radius_narrow: usize,
+	radius_wide: usize,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_output: impl FnMut(B) -> S
+) -> Result<(), BlurError> {
+	check_output_size("output", &input, output)?;
+
+	blur_difference(input, output, radius_narrow, radius_wide, to_blurrable, to_output);
+	Ok(())
+}
+
+/// Blurs an image by two radii in parallel, and writes the difference between
+/// the narrow and the wide blur to `output`, assuming one element per pixel.
+///
+/// See [`blur_difference`] for how the parameters are applied.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_difference<T: Sync, S: Send, B: StackBlurrable + Send + Sync>(
+	input: ImgRef<T>,
+	output: &mut ImgRefMut<S>,
+	radius_narrow: usize,
+	radius_wide: usize,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_output: impl Fn(B) -> S + Sync
+) {
+	lines::par_blur_lines_difference(&input, output, radius_narrow, radius_wide, to_blurrable, |difference, _| to_output(difference));
+}
+
+/// Like [`par_blur_difference`], but returns a [`BlurError`] if a parameter is
+/// not supported.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_difference<T: Sync, S: Send, B: StackBlurrable + Send + Sync>(
+	input: ImgRef<T>,
+	output: &mut ImgRefMut<S>,
+	radius_narrow: usize,
+	radius_wide: usize,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_output: impl Fn(B) -> S + Sync
+) -> Result<(), BlurError> {
+	check_output_size("output", &input, output)?;
+
+	lines::par_blur_lines_difference(&input, output, radius_narrow, radius_wide, to_blurrable, |difference, _| to_output(difference));
+	Ok(())
+}
+
+/// Writes the difference between a narrow and a wide blur of an image of `f32`
+/// pixels to `output`, with [`StackBlurrableF32`].
+///
+/// This is a version of [`blur_difference`] with pre-filled conversion
+/// routines. See the [`float`](crate::float) module for how precise it is.
+pub fn blur_difference_f32(input: ImgRef<f32>, output: &mut ImgRefMut<f32>, radius_narrow: usize, radius_wide: usize) {
+	blur_difference(input, output, radius_narrow, radius_wide, |i| StackBlurrableF32(*i), |i| i.0);
+}
+
+/// Like [`blur_difference_f32`], but returns a [`BlurError`] if `output` is not
+/// the same size as `input`.
+pub fn try_blur_difference_f32(input: ImgRef<f32>, output: &mut ImgRefMut<f32>, radius_narrow: usize, radius_wide: usize) -> Result<(), BlurError> {
+	try_blur_difference(input, output, radius_narrow, radius_wide, |i| StackBlurrableF32(*i), |i| i.0)
+}
+
+/// Writes the difference between a narrow and a wide blur of an image of `f32`
+/// pixels to `output` in parallel, with [`StackBlurrableF32`].
+///
+/// This is a version of [`par_blur_difference`] with pre-filled conversion
+/// routines. See the [`float`](crate::float) module for how precise it is.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_difference_f32(input: ImgRef<f32>, output: &mut ImgRefMut<f32>, radius_narrow: usize, radius_wide: usize) {
+	par_blur_difference(input, output, radius_narrow, radius_wide, |i| StackBlurrableF32(*i), |i| i.0);
+}
+
+/// Like [`par_blur_difference_f32`], but returns a [`BlurError`] if `output` is
+/// not the same size as `input`.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_difference_f32(input: ImgRef<f32>, output: &mut ImgRefMut<f32>, radius_narrow: usize, radius_wide: usize) -> Result<(), BlurError> {
+	try_par_blur_difference(input, output, radius_narrow, radius_wide, |i| StackBlurrableF32(*i), |i| i.0)
+}
+
+/// Writes the difference between a narrow and a wide blur of an image of 32-bit
+/// packed ARGB pixels (0xAARRGGBB) to `output`.
+///
+/// Every channel in `mask` becomes its difference plus 128, clamped to the
+/// range of a byte, and the others are copied from `input`. Pass
+/// [`ChannelMask::COLOR`] to keep the alpha of the input. Like
+/// [`blur_argb`](crate::blur_argb), this uses 32 bits per channel, or 64 bits
+/// when the wider radius is larger than
+/// [`ARGB_U32_MAX_RADIUS`](crate::ARGB_U32_MAX_RADIUS).
+///
+/// Note that this function is *linear*.
+pub fn blur_difference_argb(input: ImgRef<u32>, output: &mut ImgRefMut<u32>, radius_narrow: usize, radius_wide: usize, mask: ChannelMask) {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_narrow.max(radius_wide))) {
+		lines::blur_lines_difference(&input, output, radius_narrow, radius_wide, |i| ArgbU32::from_u32(*i), |i, original| encode_argb(i, *original, mask));
+	} else {
+		lines::blur_lines_difference(&input, output, radius_narrow, radius_wide, |i| ArgbU64::from_u32(*i), |i, original| encode_argb(i, *original, mask));
+	}
+}
+
+/// Like [`blur_difference_argb`], but returns a [`BlurError`] if a radius is
+/// too large or `output` is not the same size as `input`.
+pub fn try_blur_difference_argb(input: ImgRef<u32>, output: &mut ImgRefMut<u32>, radius_narrow: usize, radius_wide: usize, mask: ChannelMask) -> Result<(), BlurError> {
+	check_radii(radius_narrow, radius_wide)?;
+	check_output_size("output", &input, output)?;
+
+	blur_difference_argb(input, output, radius_narrow, radius_wide, mask);
+	Ok(())
+}
+
+/// Writes the difference between a narrow and a wide blur of an image of 32-bit
+/// packed ARGB pixels (0xAARRGGBB) to `output` in parallel.
+///
+/// See [`blur_difference_argb`] for how the difference is encoded.
+///
+/// Note that this function is *linear*.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_difference_argb(input: ImgRef<u32>, output: &mut ImgRefMut<u32>, radius_narrow: usize, radius_wide: usize, mask: ChannelMask) {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_narrow.max(radius_wide))) {
+		lines::par_blur_lines_difference(&input, output, radius_narrow, radius_wide, |i| ArgbU32::from_u32(*i), |i, original| encode_argb(i, *original, mask));
+	} else {
+		lines::par_blur_lines_difference(&input, output, radius_narrow, radius_wide, |i| ArgbU64::from_u32(*i), |i, original| encode_argb(i, *original, mask));
+	}
+}
+
+/// Like [`par_blur_difference_argb`], but returns a [`BlurError`] if a radius
+/// is too large or `output` is not the same size as `input`.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_difference_argb(input: ImgRef<u32>, output: &mut ImgRefMut<u32>, radius_narrow: usize, radius_wide: usize, mask: ChannelMask) -> Result<(), BlurError> {
+	check_radii(radius_narrow, radius_wide)?;
+	check_output_size("output", &input, output)?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_narrow.max(radius_wide))) {
+		lines::par_blur_lines_difference(&input, output, radius_narrow, radius_wide, |i| ArgbU32::from_u32(*i), |i, original| encode_argb(i, *original, mask));
+	} else {
+		lines::par_blur_lines_difference(&input, output, radius_narrow, radius_wide, |i| ArgbU64::from_u32(*i), |i, original| encode_argb(i, *original, mask));
+	}
+
+	Ok(())
+}
+
@@ -0,0 +1,55 @@
+//! The error returned by the fallible `try_` helpers in the crate root.
+
+use std::fmt::{self, Display, Formatter};
+
+/// The reason that one of the `try_` helpers, like
+/// [`try_blur_argb`](crate::try_blur_argb), refused to blur a buffer.
+///
+/// Every helper with a `try_` version checks its parameters before touching the
+/// buffer, so if one of them returns an error, the buffer is left unchanged.
+///
+/// Getting scratch space for the threads of the parallel helpers can't fail,
+/// so there is no error for it. The helpers set some aside for every thread of
+/// the current Rayon thread pool, and a line that ends up on any other thread
+/// is blurred with freshly allocated scratch space instead, which costs a
+/// little time but never changes the result.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum BlurError {
+	/// The radius is too large for the sums of the blur to fit in 64 bits per
+	/// channel. The generic helpers cannot know how much room their
+	/// [`StackBlurrable`] type has, so they only return this when the weights
+	/// of their kernel would not even add up in a `usize`.
+	///
+	/// For fractional radii and Gaussian blurs, `radius` is the radius of the
+	/// larger of the two [`StackBlur`]s that would have been used.
+	///
+	/// [`StackBlurrable`]: crate::traits::StackBlurrable
+	/// [`StackBlur`]: crate::iter::StackBlur
+	RadiusTooLarge {
+		/// The radius that was asked for.
+		radius: usize,
+		/// The largest radius that the same helper can handle.
+		max_radius: usize
+	},
+
+	/// A parameter has a value that the blur does not support, like a radius or
+	/// sigma that is NaN, infinite or negative, or a radius or weight map that
+	/// is not the same size as the buffer.
+	UnsupportedParameter {
+		/// The name of the parameter, as it appears in the function signature.
+		parameter: &'static str,
+		/// What the parameter should have looked like instead.
+		reason: &'static str
+	}
+}
+
+impl Display for BlurError {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::RadiusTooLarge { radius, max_radius } => write!(f, "radius {} is too large, the largest supported radius is {}", radius, max_radius),
+			Self::UnsupportedParameter { parameter, reason } => write!(f, "unsupported value for `{}`: {}", parameter, reason)
+		}
+	}
+}
+
+impl std::error::Error for BlurError {}
@@ -0,0 +1,314 @@
+//! Floating-point [`StackBlurrable`]s, and helpers for buffers of `f32` and
+//! `f64` pixels.
+//!
+//! Unlike the integer helpers in the crate root, these never round the pixels
+//! to a fixed number of bits, so they suit HDR colors, linear-light
+//! compositing, depth buffers and other data that is not 8 bits per channel.
+//!
+//! ## Rounding error
+//!
+//! Floating-point addition is not exact, so the sums that a [`StackBlur`]
+//! keeps up to date as it moves along a line do not cancel out perfectly when
+//! a value enters and later leaves the window. The error of every step is
+//! carried along to the end of the line, and since one of those sums is itself
+//! added to the other at every step, the error grows faster than the length of
+//! the line: roughly with its square. It is divided by the sum of the weights
+//! before it reaches the output, though, so it *shrinks* as the radius grows.
+//!
+//! With `f32` and random values between 0 and 1, the error at radius 1 is up to
+//! about 3e-4 on lines of 1 000 pixels, 1e-2 on lines of 10 000 pixels and
+//! 0.3 on lines of 100 000 pixels, and it shrinks at least tenfold every time
+//! the radius grows tenfold. `f64` has 29 more bits of precision, so its error
+//! should be smaller by about the same factor, which makes it the better choice
+//! for very long lines with small radii.
+//!
+//! The SIMD helpers for `f32`, like [`simd_blur_f32`], blur `LANES` lines at
+//! a time with [`StackBlurrableF32xN`], which does exactly the same arithmetic
+//! in every lane, so they give the same results as the other helpers.
+//!
+//! [`StackBlur`]: crate::iter::StackBlur
+
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+#[cfg(feature = "simd")]
+use std::simd::Simd;
+#[cfg(any(doc, feature = "simd"))]
+use std::simd::{LaneCount, SupportedLaneCount};
+
+use imgref::ImgRefMut;
+
+#[cfg(doc)]
+use crate::traits::StackBlurrable;
+use crate::color::Channels;
+
+/// A [`StackBlurrable`] that holds a single `f32`.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default)]
+pub struct StackBlurrableF32(pub f32);
+
+impl Add for StackBlurrableF32 {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		Self(self.0 + rhs.0)
+	}
+}
+
+impl Sub for StackBlurrableF32 {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		Self(self.0 - rhs.0)
+	}
+}
+
+impl AddAssign for StackBlurrableF32 {
+	fn add_assign(&mut self, rhs: Self) {
+		self.0 += rhs.0;
+	}
+}
+
+impl SubAssign for StackBlurrableF32 {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.0 -= rhs.0;
+	}
+}
+
+impl Mul<usize> for StackBlurrableF32 {
+	type Output = Self;
+
+	fn mul(self, rhs: usize) -> Self::Output {
+		Self(self.0 * rhs as f32)
+	}
+}
+
+impl Div<usize> for StackBlurrableF32 {
+	type Output = Self;
+
+	fn div(self, rhs: usize) -> Self::Output {
+		Self(self.0 / rhs as f32)
+	}
+}
+
+/// A [`StackBlurrable`] that holds one `f32` for each of `N` pixels, to blur
+/// `N` lines at once like [`StackBlurrableF32`] blurs one.
+#[cfg(feature = "simd")]
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct StackBlurrableF32xN<const N: usize>(pub Simd<f32, N>) where LaneCount<N>: SupportedLaneCount;
+
+#[cfg(feature = "simd")]
+impl<const N: usize> Add for StackBlurrableF32xN<N> where LaneCount<N>: SupportedLaneCount {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		Self(self.0 + rhs.0)
+	}
+}
+
+#[cfg(feature = "simd")]
+impl<const N: usize> Sub for StackBlurrableF32xN<N> where LaneCount<N>: SupportedLaneCount {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		Self(self.0 - rhs.0)
+	}
+}
+
+#[cfg(feature = "simd")]
+impl<const N: usize> AddAssign for StackBlurrableF32xN<N> where LaneCount<N>: SupportedLaneCount {
+	fn add_assign(&mut self, rhs: Self) {
+		self.0 += rhs.0;
+	}
+}
+
+#[cfg(feature = "simd")]
+impl<const N: usize> SubAssign for StackBlurrableF32xN<N> where LaneCount<N>: SupportedLaneCount {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.0 -= rhs.0;
+	}
+}
+
+#[cfg(feature = "simd")]
+impl<const N: usize> Mul<usize> for StackBlurrableF32xN<N> where LaneCount<N>: SupportedLaneCount {
+	type Output = Self;
+
+	fn mul(self, rhs: usize) -> Self::Output {
+		Self(self.0 * Simd::<f32, N>::splat(rhs as f32))
+	}
+}
+
+#[cfg(feature = "simd")]
+impl<const N: usize> Div<usize> for StackBlurrableF32xN<N> where LaneCount<N>: SupportedLaneCount {
+	type Output = Self;
+
+	fn div(self, rhs: usize) -> Self::Output {
+		Self(self.0 / Simd::<f32, N>::splat(rhs as f32))
+	}
+}
+
+/// A [`StackBlurrable`] that holds a single `f64`, for when
+/// [`StackBlurrableF32`] is not precise enough.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default)]
+pub struct StackBlurrableF64(pub f64);
+
+impl Add for StackBlurrableF64 {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		Self(self.0 + rhs.0)
+	}
+}
+
+impl Sub for StackBlurrableF64 {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		Self(self.0 - rhs.0)
+	}
+}
+
+impl AddAssign for StackBlurrableF64 {
+	fn add_assign(&mut self, rhs: Self) {
+		self.0 += rhs.0;
+	}
+}
+
+impl SubAssign for StackBlurrableF64 {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.0 -= rhs.0;
+	}
+}
+
+impl Mul<usize> for StackBlurrableF64 {
+	type Output = Self;
+
+	fn mul(self, rhs: usize) -> Self::Output {
+		Self(self.0 * rhs as f64)
+	}
+}
+
+impl Div<usize> for StackBlurrableF64 {
+	type Output = Self;
+
+	fn div(self, rhs: usize) -> Self::Output {
+		Self(self.0 / rhs as f64)
+	}
+}
+
+/// Blurs a buffer of `f32` pixels with [`StackBlurrableF32`].
+///
+/// This is a version of [`blur`](crate::blur) with pre-filled conversion
+/// routines. See the [module documentation](self) for how precise it is.
+pub fn blur_f32(buffer: &mut ImgRefMut<f32>, radius: usize) {
+	crate::blur(buffer, radius, |i| StackBlurrableF32(*i), |i| i.0);
+}
+
+/// Blurs a buffer of `f32` pixels with [`StackBlurrableF32`] in parallel.
+///
+/// This is a version of [`par_blur`](crate::par_blur) with pre-filled
+/// conversion routines. See the [module documentation](self) for how precise it
+/// is.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_f32(buffer: &mut ImgRefMut<f32>, radius: usize) {
+	crate::par_blur(buffer, radius, |i| StackBlurrableF32(*i), |i| i.0);
+}
+
+/// Blurs a buffer of `f32` pixels with [`StackBlurrableF32xN`] and SIMD.
+///
+/// This is a version of [`simd_blur`](crate::simd_blur) with pre-filled
+/// conversion routines. It gives the same results as [`blur_f32`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_f32<const LANES: usize>(buffer: &mut ImgRefMut<f32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	crate::simd_blur(buffer, radius, |i: [&f32; LANES]| StackBlurrableF32xN(Simd::from_array(i.map(|i| *i))), |i: StackBlurrableF32xN<LANES>| i.0.to_array(), |i| StackBlurrableF32(*i), |i| i.0);
+}
+
+/// Blurs a buffer of `f32` pixels with [`StackBlurrableF32xN`] and SIMD in
+/// parallel.
+///
+/// This is a version of [`par_simd_blur`](crate::par_simd_blur) with pre-filled
+/// conversion routines. It gives the same results as [`blur_f32`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_f32<const LANES: usize>(buffer: &mut ImgRefMut<f32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	crate::par_simd_blur(buffer, radius, |i: [&f32; LANES]| StackBlurrableF32xN(Simd::from_array(i.map(|i| *i))), |i: StackBlurrableF32xN<LANES>| i.0.to_array(), |i| StackBlurrableF32(*i), |i| i.0);
+}
+
+/// Blurs a buffer of pixels with `N` `f32` channels, like RGBA, with
+/// [`Channels`].
+///
+/// This is a version of [`blur`](crate::blur) with pre-filled conversion
+/// routines. See the [module documentation](self) for how precise it is.
+pub fn blur_f32_channels<const N: usize>(buffer: &mut ImgRefMut<[f32; N]>, radius: usize) {
+	crate::blur(buffer, radius, |i| Channels(i.map(StackBlurrableF32)), |i| i.0.map(|i| i.0));
+}
+
+/// Blurs a buffer of pixels with `N` `f32` channels, like RGBA, with
+/// [`Channels`] in parallel.
+///
+/// This is a version of [`par_blur`](crate::par_blur) with pre-filled
+/// conversion routines. See the [module documentation](self) for how precise it
+/// is.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_f32_channels<const N: usize>(buffer: &mut ImgRefMut<[f32; N]>, radius: usize) {
+	crate::par_blur(buffer, radius, |i| Channels(i.map(StackBlurrableF32)), |i| i.0.map(|i| i.0));
+}
+
+/// Blurs a buffer of pixels with `N` `f32` channels, like RGBA, with
+/// [`Channels`] and SIMD.
+///
+/// This is a version of [`simd_blur`](crate::simd_blur) with pre-filled
+/// conversion routines. It gives the same results as [`blur_f32_channels`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_f32_channels<const N: usize, const LANES: usize>(buffer: &mut ImgRefMut<[f32; N]>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	crate::simd_blur(buffer, radius, |i: [&[f32; N]; LANES]| Channels(std::array::from_fn(|channel| StackBlurrableF32xN(Simd::from_array(i.map(|i| i[channel]))))), |i: Channels<StackBlurrableF32xN<LANES>, N>| transpose(i.0.map(|i| i.0.to_array())), |i| Channels(i.map(StackBlurrableF32)), |i| i.0.map(|i| i.0));
+}
+
+/// Blurs a buffer of pixels with `N` `f32` channels, like RGBA, with
+/// [`Channels`] and SIMD in parallel.
+///
+/// This is a version of [`par_simd_blur`](crate::par_simd_blur) with pre-filled
+/// conversion routines. It gives the same results as [`blur_f32_channels`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_f32_channels<const N: usize, const LANES: usize>(buffer: &mut ImgRefMut<[f32; N]>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	crate::par_simd_blur(buffer, radius, |i: [&[f32; N]; LANES]| Channels(std::array::from_fn(|channel| StackBlurrableF32xN(Simd::from_array(i.map(|i| i[channel]))))), |i: Channels<StackBlurrableF32xN<LANES>, N>| transpose(i.0.map(|i| i.0.to_array())), |i| Channels(i.map(StackBlurrableF32)), |i| i.0.map(|i| i.0));
+}
+
+/// Blurs a buffer of `f64` pixels with [`StackBlurrableF64`].
+///
+/// This is a version of [`blur`](crate::blur) with pre-filled conversion
+/// routines. See the [module documentation](self) for how precise it is.
+pub fn blur_f64(buffer: &mut ImgRefMut<f64>, radius: usize) {
+	crate::blur(buffer, radius, |i| StackBlurrableF64(*i), |i| i.0);
+}
+
+/// Blurs a buffer of `f64` pixels with [`StackBlurrableF64`] in parallel.
+///
+/// This is a version of [`par_blur`](crate::par_blur) with pre-filled
+/// conversion routines. See the [module documentation](self) for how precise it
+/// is.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_f64(buffer: &mut ImgRefMut<f64>, radius: usize) {
+	crate::par_blur(buffer, radius, |i| StackBlurrableF64(*i), |i| i.0);
+}
+
+/// Blurs a buffer of pixels with `N` `f64` channels with [`Channels`].
+///
+/// This is a version of [`blur`](crate::blur) with pre-filled conversion
+/// routines. See the [module documentation](self) for how precise it is.
+pub fn blur_f64_channels<const N: usize>(buffer: &mut ImgRefMut<[f64; N]>, radius: usize) {
+	crate::blur(buffer, radius, |i| Channels(i.map(StackBlurrableF64)), |i| i.0.map(|i| i.0));
+}
+
+/// Blurs a buffer of pixels with `N` `f64` channels with [`Channels`] in
+/// parallel.
+///
+/// This is a version of [`par_blur`](crate::par_blur) with pre-filled
+/// conversion routines. See the [module documentation](self) for how precise it
+/// is.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_f64_channels<const N: usize>(buffer: &mut ImgRefMut<[f64; N]>, radius: usize) {
+	crate::par_blur(buffer, radius, |i| Channels(i.map(StackBlurrableF64)), |i| i.0.map(|i| i.0));
+}
+
+/// Turns `N` channels of `LANES` pixels into `LANES` pixels of `N` channels.
+#[cfg(feature = "simd")]
+fn transpose<const N: usize, const LANES: usize>(channels: [[f32; LANES]; N]) -> [[f32; N]; LANES] {
+	std::array::from_fn(|lane| std::array::from_fn(|channel| channels[channel][lane]))
+}
@@ -0,0 +1,318 @@
+//! He et al.'s guided filter, which smooths an image without blurring it across
+//! the edges of a guide image.
+//!
+//! Within every window, the guided filter fits the output as a linear function
+//! of the guide, `q = a * I + b`, that is as close to the input `p` as it can
+//! be, and then averages the `a` and `b` of all of the windows that overlap
+//! each pixel. The output can only have an edge where the guide has one, so
+//! the filter smooths noise away without softening the edges of the guide,
+//! and a rough alpha matte filtered with the photo it was cut out of as the
+//! guide snaps to the edges in the photo, without the halos that a plain blur
+//! would leave across them. To denoise an image, use it as its own guide with
+//! [`GuidedFilter::filter_self`].
+//!
+//! Every step of the filter is a mean over windows, so it is made entirely of
+//! blurs: one of `I`, `p`, `I * p` and `I * I` at once, and then one of `a`
+//! and `b`. With [`Kernel::Box`], which [`GuidedFilter::new`] picks, these are
+//! the box means of the original paper, and with [`Kernel::Tent`] they are the
+//! smoother weights of Stackblur. Either way, the cost per pixel does not
+//! depend on the radius.
+//!
+//! The means are accumulated with [`StackBlurrableF64`], because the variance
+//! of the guide is the difference between two means that can be much larger
+//! than it, which would lose most of the precision of an `f32`. This needs
+//! room for 8 bytes per mean per pixel: 32 bytes with a grayscale guide, and
+//! 104 bytes with a color guide.
+
+use imgref::{ImgRef, ImgRefMut, ImgVec};
+
+use crate::error::BlurError;
+use crate::color::Channels;
+use crate::float::StackBlurrableF64;
+use crate::iter::Kernel;
+use crate::{check_size, lines};
+
+/// A guided filter. See the [module documentation](self) for an overview.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GuidedFilter {
+	/// The radius of the windows that the output is fitted in.
+	pub radius: usize,
+	/// How much the output is smoothed, in units of the guide squared. Areas in
+	/// which the guide varies by much less than the square root of this are
+	/// smoothed flat, and edges in the guide that are much stronger than it are
+	/// kept. For guides from 0 to 1, values from 0.0001 to 0.01 are typical.
+	///
+	/// This must be positive, or flat areas of the guide come out as NaN.
+	pub epsilon: f32,
+	/// The shape of the windows that the means are taken over.
+	pub kernel: Kernel
+}
+
+impl GuidedFilter {
+	/// Creates a guided filter with the given radius and epsilon, and the box
+	/// windows of the original paper.
+	pub fn new(radius: usize, epsilon: f32) -> Self {
+		Self { radius, epsilon, kernel: Kernel::Box }
+	}
+
+	/// Filters a buffer of `f32` pixels in place, guided by an image of `f32`
+	/// pixels, which must be the same size as `buffer`, or this function
+	/// panics.
+	pub fn filter_gray(&self, guide: ImgRef<f32>, buffer: &mut ImgRefMut<f32>) {
+		guided(self, &guide, buffer, gray_stats, gray_coefficients, gray_apply);
+	}
+
+	/// Like [`filter_gray`](Self::filter_gray), but returns a [`BlurError`] if a
+	/// parameter is not supported.
+	pub fn try_filter_gray(&self, guide: ImgRef<f32>, buffer: &mut ImgRefMut<f32>) -> Result<(), BlurError> {
+		self.check()?;
+		check_size("guide", &guide, buffer)?;
+
+		self.filter_gray(guide, buffer);
+		Ok(())
+	}
+
+	/// Filters a buffer of `f32` pixels in place, guided by an image of pixels
+	/// with three `f32` channels, like RGB, which must be the same size as
+	/// `buffer`, or this function panics.
+	///
+	/// A color guide keeps edges between areas that differ in color but not in
+	/// brightness, which a grayscale guide would smooth over.
+	pub fn filter_color(&self, guide: ImgRef<[f32; 3]>, buffer: &mut ImgRefMut<f32>) {
+		guided(self, &guide, buffer, color_stats, color_coefficients, color_apply);
+	}
+
+	/// Like [`filter_color`](Self::filter_color), but returns a [`BlurError`]
+	/// if a parameter is not supported.
+	pub fn try_filter_color(&self, guide: ImgRef<[f32; 3]>, buffer: &mut ImgRefMut<f32>) -> Result<(), BlurError> {
+		self.check()?;
+		check_size("guide", &guide, buffer)?;
+
+		self.filter_color(guide, buffer);
+		Ok(())
+	}
+
+	/// Filters a buffer of `f32` pixels in place, using a copy of it as its own
+	/// guide, which smooths it while keeping its edges.
+	pub fn filter_self(&self, buffer: &mut ImgRefMut<f32>) {
+		let guide = copy(buffer);
+		self.filter_gray(guide.as_ref(), buffer);
+	}
+
+	/// Like [`filter_self`](Self::filter_self), but returns a [`BlurError`] if
+	/// a parameter is not supported.
+	pub fn try_filter_self(&self, buffer: &mut ImgRefMut<f32>) -> Result<(), BlurError> {
+		self.check()?;
+
+		self.filter_self(buffer);
+		Ok(())
+	}
+
+	/// Like [`filter_gray`](Self::filter_gray), but in parallel.
+	#[cfg(any(doc, feature = "rayon"))]
+	pub fn par_filter_gray(&self, guide: ImgRef<f32>, buffer: &mut ImgRefMut<f32>) {
+		par_guided(self, &guide, buffer, gray_stats, gray_coefficients, gray_apply);
+	}
+
+	/// Like [`par_filter_gray`](Self::par_filter_gray), but returns a [`BlurError`]
+	/// if a parameter is not supported.
+	#[cfg(any(doc, feature = "rayon"))]
+	pub fn try_par_filter_gray(&self, guide: ImgRef<f32>, buffer: &mut ImgRefMut<f32>) -> Result<(), BlurError> {
+		self.check()?;
+		check_size("guide", &guide, buffer)?;
+
+		par_guided(self, &guide, buffer, gray_stats, gray_coefficients, gray_apply);
+		Ok(())
+	}
+
+	/// Like [`filter_color`](Self::filter_color), but in parallel.
+	#[cfg(any(doc, feature = "rayon"))]
+	pub fn par_filter_color(&self, guide: ImgRef<[f32; 3]>, buffer: &mut ImgRefMut<f32>) {
+		par_guided(self, &guide, buffer, color_stats, color_coefficients, color_apply);
+	}
+
+	/// Like [`par_filter_color`](Self::par_filter_color), but returns a
+	/// [`BlurError`] if a parameter is not supported.
+	#[cfg(any(doc, feature = "rayon"))]
+	pub fn try_par_filter_color(&self, guide: ImgRef<[f32; 3]>, buffer: &mut ImgRefMut<f32>) -> Result<(), BlurError> {
+		self.check()?;
+		check_size("guide", &guide, buffer)?;
+
+		par_guided(self, &guide, buffer, color_stats, color_coefficients, color_apply);
+		Ok(())
+	}
+
+	/// Like [`filter_self`](Self::filter_self), but in parallel.
+	#[cfg(any(doc, feature = "rayon"))]
+	pub fn par_filter_self(&self, buffer: &mut ImgRefMut<f32>) {
+		let guide = copy(buffer);
+		self.par_filter_gray(guide.as_ref(), buffer);
+	}
+
+	/// Like [`par_filter_self`](Self::par_filter_self), but returns a [`BlurError`]
+	/// if a parameter is not supported.
+	#[cfg(any(doc, feature = "rayon"))]
+	pub fn try_par_filter_self(&self, buffer: &mut ImgRefMut<f32>) -> Result<(), BlurError> {
+		self.check()?;
+
+		let guide = copy(buffer);
+		par_guided(self, &guide.as_ref(), buffer, gray_stats, gray_coefficients, gray_apply);
+		Ok(())
+	}
+
+	/// Returns an error if the filter cannot be applied.
+	fn check(&self) -> Result<(), BlurError> {
+		if self.epsilon.is_finite() && self.epsilon > 0.0 {
+			Ok(())
+		} else {
+			Err(BlurError::UnsupportedParameter { parameter: "epsilon", reason: "must be finite and positive" })
+		}
+	}
+
+	/// Returns the line blur that takes the means.
+	fn line(&self) -> lines::Shaped {
+		lines::Shaped { radius: self.radius, kernel: self.kernel }
+	}
+}
+
+/// Returns a copy of a buffer of `f32` pixels.
+fn copy(buffer: &ImgRefMut<f32>) -> ImgVec<f32> {
+	ImgVec::new(buffer.as_ref().pixels().collect(), buffer.width(), buffer.height())
+}
+
+/// Returns `I`, `p`, `I * p` and `I * I`, for a grayscale guide.
+fn gray_stats(guide: &f32, input: f32) -> [f64; 4] {
+	let (guide, input) = (*guide as f64, input as f64);
+	[guide, input, guide * input, guide * guide]
+}
+
+/// Returns `a` and `b` from the means of [`gray_stats`].
+fn gray_coefficients(means: [f64; 4], epsilon: f64) -> [f64; 2] {
+	let [guide, input, product, square] = means;
+	let a = (product - guide * input) / (square - guide * guide + epsilon);
+	[a, input - a * guide]
+}
+
+/// Returns `a * I + b` from the means of [`gray_coefficients`].
+fn gray_apply(guide: &f32, means: [f64; 2]) -> f32 {
+	let [a, b] = means;
+	(a * *guide as f64 + b) as f32
+}
+
+/// Returns the three channels of `I`, `p`, the three channels of `I * p`, and
+/// the upper triangle of the 3×3 matrix `I * Iᵀ`, row by row, for a color
+/// guide.
+fn color_stats(guide: &[f32; 3], input: f32) -> [f64; 13] {
+	let [r, g, b] = guide.map(f64::from);
+	let input = input as f64;
+	[r, g, b, input, r * input, g * input, b * input, r * r, r * g, r * b, g * g, g * b, b * b]
+}
+
+/// Returns the three channels of `a`, and `b`, from the means of
+/// [`color_stats`].
+fn color_coefficients(means: [f64; 13], epsilon: f64) -> [f64; 4] {
+	let [r, g, b, input, rp, gp, bp, rr, rg, rb, gg, gb, bb] = means;
+	let covariance = [rp - r * input, gp - g * input, bp - b * input];
+
+	// The covariance matrix of the guide, plus epsilon along the diagonal.
+	let (rr, rg, rb) = (rr - r * r + epsilon, rg - r * g, rb - r * b);
+	let (gg, gb, bb) = (gg - g * g + epsilon, gb - g * b, bb - b * b + epsilon);
+
+	// It is symmetric, so its inverse is its adjugate, which is also
+	// symmetric, over its determinant.
+	let (irr, irg, irb) = (gg * bb - gb * gb, rb * gb - rg * bb, rg * gb - rb * gg);
+	let (igg, igb, ibb) = (rr * bb - rb * rb, rg * rb - rr * gb, rr * gg - rg * rg);
+	let det = rr * irr + rg * irg + rb * irb;
+
+	let [cr, cg, cb] = covariance;
+	let ar = (irr * cr + irg * cg + irb * cb) / det;
+	let ag = (irg * cr + igg * cg + igb * cb) / det;
+	let ab = (irb * cr + igb * cg + ibb * cb) / det;
+
+	[ar, ag, ab, input - ar * r - ag * g - ab * b]
+}
+
+/// Returns `a * I + b` from the means of [`color_coefficients`].
+fn color_apply(guide: &[f32; 3], means: [f64; 4]) -> f32 {
+	let [ar, ag, ab, b] = means;
+	let [red, green, blue] = guide.map(f64::from);
+	(ar * red + ag * green + ab * blue + b) as f32
+}
+
+/// Runs a guided filter over `buffer`. `stats` returns the values whose means
+/// are taken first, `fit` turns those means into the coefficients of the linear
+/// model, and `apply` turns the means of those into the output.
+fn guided<G, const N: usize, const M: usize>(
+	filter: &GuidedFilter,
+	guide: &ImgRef<G>,
+	buffer: &mut ImgRefMut<f32>,
+	stats: impl Fn(&G, f32) -> [f64; N],
+	fit: impl Fn([f64; N], f64) -> [f64; M],
+	apply: impl Fn(&G, [f64; M]) -> f32
+) {
+	assert_eq!((guide.width(), guide.height()), (buffer.width(), buffer.height()), "the guide must be the same size as the buffer");
+
+	let (width, height) = (buffer.width(), buffer.height());
+	let (line, epsilon) = (filter.line(), filter.epsilon as f64);
+
+	let mut means = Vec::with_capacity(width * height);
+	for (guide, read) in guide.rows().zip(buffer.rows()) {
+		means.extend(guide.iter().zip(read).map(|(guide, &input)| Channels(stats(guide, input).map(StackBlurrableF64))));
+	}
+
+	let mut means = ImgVec::new(means, width, height);
+	lines::blur_lines(&mut means.as_mut(), &line, &line, Channels::clone, |i| i);
+
+	let coefficients = means.pixels().map(|means| Channels(fit(means.0.map(|i| i.0), epsilon).map(StackBlurrableF64))).collect();
+	let mut coefficients = ImgVec::new(coefficients, width, height);
+	lines::blur_lines(&mut coefficients.as_mut(), &line, &line, Channels::clone, |i| i);
+
+	for ((write, guide), coefficients) in buffer.rows_mut().zip(guide.rows()).zip(coefficients.rows()) {
+		for ((place, guide), coefficients) in write.iter_mut().zip(guide).zip(coefficients) {
+			*place = apply(guide, coefficients.0.map(|i| i.0));
+		}
+	}
+}
+
+/// Like [`guided`], but in parallel.
+#[cfg(any(doc, feature = "rayon"))]
+fn par_guided<G: Sync, const N: usize, const M: usize>(
+	filter: &GuidedFilter,
+	guide: &ImgRef<G>,
+	buffer: &mut ImgRefMut<f32>,
+	stats: impl Fn(&G, f32) -> [f64; N] + Sync,
+	fit: impl Fn([f64; N], f64) -> [f64; M] + Sync,
+	apply: impl Fn(&G, [f64; M]) -> f32 + Sync
+) {
+	#[cfg(not(doc))]
+	use rayon::iter::{ParallelBridge, ParallelIterator};
+
+	assert_eq!((guide.width(), guide.height()), (buffer.width(), buffer.height()), "the guide must be the same size as the buffer");
+
+	let (width, height) = (buffer.width(), buffer.height());
+	let (line, epsilon) = (filter.line(), filter.epsilon as f64);
+
+	let mut means = ImgVec::new(vec![Channels::<StackBlurrableF64, N>::default(); width * height], width, height);
+	means.rows_mut().zip(guide.rows()).zip(buffer.rows()).par_bridge().for_each(|((write, guide), read)| {
+		for ((place, guide), &input) in write.iter_mut().zip(guide).zip(read) {
+			*place = Channels(stats(guide, input).map(StackBlurrableF64));
+		}
+	});
+
+	lines::par_blur_lines(&mut means.as_mut(), &line, &line, Channels::clone, |i| i);
+
+	let mut coefficients = ImgVec::new(vec![Channels::<StackBlurrableF64, M>::default(); width * height], width, height);
+	coefficients.rows_mut().zip(means.rows()).par_bridge().for_each(|(write, means)| {
+		for (place, means) in write.iter_mut().zip(means) {
+			*place = Channels(fit(means.0.map(|i| i.0), epsilon).map(StackBlurrableF64));
+		}
+	});
+
+	lines::par_blur_lines(&mut coefficients.as_mut(), &line, &line, Channels::clone, |i| i);
+
+	buffer.rows_mut().zip(guide.rows()).zip(coefficients.rows()).par_bridge().for_each(|((write, guide), coefficients)| {
+		for ((place, guide), coefficients) in write.iter_mut().zip(guide).zip(coefficients) {
+			*place = apply(guide, coefficients.0.map(|i| i.0));
+		}
+	});
+}
@@ -100,11 +100,214 @@
 //! sum and registering it in the cache becomes *O*(1) instead of *O*(2n+1)
 //! (where `n` is the radius). It's basically the most important thing that
 //! makes the algorithm constant-time.
+//!
+//! ## Edge modes
+//!
+//! The renormalization described above is the default, but [`StackBlur`] can
+//! also be told to make up the pixels off the edge of the image in one of the
+//! classic ways, by passing an [`EdgeMode`] to [`StackBlur::with_edge_mode`].
+//! In that case the input is extended by `radius` virtual pixels on each side,
+//! every output is the full weighted average divided by
+//! `radius * (radius + 2) + 1`, and the outputs for the virtual pixels are
+//! never yielded.
+//!
+//! ## Kernel shapes
+//!
+//! The rate-of-change trick is not specific to the tent-shaped kernel of
+//! Stackblur. A box blur needs only a sum, the tent needs a sum and a rate,
+//! and every further level of sums convolves the kernel with another box, which
+//! makes it smoother. [`KernelBlur`] runs any of these through the same
+//! streaming machinery, including the renormalized denominator, and lets you
+//! pick the shape with a [`Kernel`].
+//!
+//! [`WeightedBlur`] uses the same machinery, but gives every item its own
+//! weight, which is added to the denominator in place of the 1 that the others
+//! count each item as.
+//!
+//! ## Fractional radii
+//!
+//! A blur radius that can only be an integer makes the strength of the blur
+//! jump in visible steps when it is animated, especially at small radii.
+//! [`FractionalStackBlur`] smooths this over by running two Stackblurs over the
+//! same input at once, one with the radius rounded down and one with it rounded
+//! up, and blending their outputs by the fractional part of the radius. Both of
+//! them are fed from a single scan over the input, so this keeps the
+//! constant-time property, at about twice the cost of a single [`StackBlur`].
+//!
+//! ## Differences of blurs
+//!
+//! [`DifferenceBlur`] runs two Stackblurs with different radii over the same
+//! input in the same way, and subtracts the wider one from the narrower one.
+//! This is a band-pass filter, like a difference of Gaussians, which picks out
+//! edges and features between the two radii.
+//!
+//! ## Several radii at once
+//!
+//! [`MultiStackBlur`] blurs the same input by any number of radii at once, and
+//! yields an array with the output of each. Rather than running a [`StackBlur`]
+//! per radius, it keeps a single history of the items that the widest radius
+//! needs, and a few running sums for every radius that are updated from it, so
+//! each radius still costs the same no matter how large it is.
+//!
+//! ## Variable radii
+//!
+//! [`VariableStackBlur`] gives every item its own radius, for effects like
+//! depth of field where the strength of the blur changes across the image.
+//! Because the radius keeps changing, it can't stream like the others, and
+//! collects its entire input before yielding the first item.
+//!
+//! ## Dilation and erosion
+//!
+//! [`Dilate`] and [`Erode`] aren't blurs, but they share the same interface.
+//! Instead of averaging the items within the radius, they yield the largest or
+//! the smallest of them, which grows or shrinks the bright parts of an image.
+//! They work with any item that can be compared, and also stream in constant
+//! time per item no matter the radius.
 
 use std::collections::VecDeque;
 
 use crate::traits::StackBlurrable;
 
+/// How [`StackBlur`] treats the pixels that lie off the edge of its input.
+///
+/// Given the input `a b c d` and a radius of 2, each mode pretends the input
+/// looks like the following, where `|` marks the edges:
+///
+/// ```text
+/// Renormalize:       | a b c d |
+/// Clamp:         a a | a b c d | d d
+/// Mirror:        c b | a b c d | c b
+/// Wrap:          c d | a b c d | a b
+/// ```
+///
+/// If the input is shorter than the radius, [`EdgeMode::Mirror`] keeps
+/// reflecting back and forth and [`EdgeMode::Wrap`] keeps repeating, so every
+/// virtual pixel is always a copy of some real one.
+///
+/// Only [`EdgeMode::Renormalize`] and [`EdgeMode::Clamp`] stream the way the
+/// [`iter`](crate::iter) module describes. [`EdgeMode::Mirror`] and
+/// [`EdgeMode::Wrap`] pad the start of the input with items from further in or
+/// from its end, so they keep a copy of the entire input, and can't be used on
+/// endless iterators.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum EdgeMode {
+	/// Pixels off the edge are left out, and the weights of the pixels that
+	/// remain are renormalized, as described by the [`iter`][crate::iter]
+	/// module documentation. This is the only mode that never yields a value
+	/// weighted by anything other than the real input.
+	#[default]
+	Renormalize,
+	/// Pixels off the edge repeat the nearest edge pixel.
+	Clamp,
+	/// Pixels off the edge reflect the input around the edge pixel, without
+	/// repeating it.
+	Mirror,
+	/// Pixels off the edge wrap around to the other side of the input, which is
+	/// what you want for seamlessly tiling textures.
+	Wrap
+}
+
+/// An iterator that extends its input by `radius` virtual pixels on each side,
+/// according to an [`EdgeMode`].
+///
+/// [`EdgeMode::Renormalize`] passes the input through unchanged.
+struct Padded<T, I: Iterator<Item = T>> {
+	iter: I,
+	radius: usize,
+	edge_mode: EdgeMode,
+	/// For [`EdgeMode::Clamp`], the most recent edge pixel. For
+	/// [`EdgeMode::Mirror`] and [`EdgeMode::Wrap`], the entire input.
+	buffer: Vec<T>,
+	/// The number of copies of `buffer[0]` left to yield, for
+	/// [`EdgeMode::Clamp`].
+	pending: usize,
+	/// The position within the padded input, for [`EdgeMode::Mirror`] and
+	/// [`EdgeMode::Wrap`].
+	index: usize,
+	started: bool,
+	ended: bool
+}
+
+impl<T, I: Iterator<Item = T>> Padded<T, I> {
+	fn new(iter: I, radius: usize, edge_mode: EdgeMode) -> Self {
+		Self { iter, radius, edge_mode, buffer: Vec::new(), pending: 0, index: 0, started: false, ended: false }
+	}
+
+	/// Starts padding the inner iterator again as if it were a new input.
+	fn restart(&mut self) {
+		self.started = false;
+		self.ended = false;
+	}
+}
+
+impl<T: Clone, I: Iterator<Item = T>> Iterator for Padded<T, I> {
+	type Item = T;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		match self.edge_mode {
+			EdgeMode::Renormalize => self.iter.next(),
+
+			EdgeMode::Clamp => {
+				if !self.started {
+					let item = self.iter.next()?;
+					self.started = true;
+					self.pending = self.radius;
+					self.buffer.clear();
+					self.buffer.push(item.clone());
+					return Some(item);
+				}
+
+				if self.pending == 0 && !self.ended {
+					if let Some(item) = self.iter.next() {
+						self.buffer[0] = item.clone();
+						return Some(item);
+					}
+
+					self.ended = true;
+					self.pending = self.radius;
+				}
+
+				if self.pending > 0 {
+					self.pending -= 1;
+					Some(self.buffer[0].clone())
+				} else {
+					None
+				}
+			}
+
+			EdgeMode::Mirror | EdgeMode::Wrap => {
+				if !self.started {
+					self.started = true;
+					self.index = 0;
+					self.buffer.clear();
+					self.buffer.extend(&mut self.iter);
+				}
+
+				let len = self.buffer.len();
+				if len == 0 || self.index == len + self.radius * 2 {
+					return None;
+				}
+
+				let offset = self.index as isize - self.radius as isize;
+				self.index += 1;
+
+				let index = if self.edge_mode == EdgeMode::Wrap {
+					offset.rem_euclid(len as isize) as usize
+				} else if len == 1 {
+					0
+				} else {
+					let period = (len - 1) * 2;
+					let index = offset.rem_euclid(period as isize) as usize;
+					if index < len { index } else { period - index }
+				};
+
+				Some(self.buffer[index].clone())
+			}
+		}
+	}
+}
+
 /// An iterator that implements an improved Stackblur algorithm.
 ///
 /// For any [`StackBlurrable`] element `T` and any iterator `I` over items of
@@ -139,8 +342,18 @@ use crate::traits::StackBlurrable;
 ///
 /// After using the [`StackBlur`], you can retrieve the [`VecDeque`] back out
 /// of it by calling [`StackBlur::into_ops`].
+///
+/// ## Edge modes
+///
+/// By default, [`StackBlur`] renormalizes its weights near the edges of the
+/// input. Use [`StackBlur::with_edge_mode`] to pick another [`EdgeMode`].
+///
+/// [`EdgeMode::Clamp`] streams just like the default does, but
+/// [`EdgeMode::Mirror`] and [`EdgeMode::Wrap`] need to know where the input
+/// ends before they can produce the first item, so they collect the entire
+/// inner iterator on the first call to [`StackBlur::next`].
 pub struct StackBlur<T: StackBlurrable, I: Iterator<Item = T>> {
-	iter: I,
+	iter: Padded<T, I>,
 	radius: usize,
 	sum: T,
 	rate: T,
@@ -158,8 +371,16 @@ impl<T: StackBlurrable, I: Iterator<Item = T>> StackBlur<T, I> {
 	///
 	/// The iterator is not advanced until a call to [`StackBlur::next`].
 	pub fn new(iter: I, radius: usize, ops: VecDeque<T>) -> Self {
+		Self::with_edge_mode(iter, radius, EdgeMode::Renormalize, ops)
+	}
+
+	/// Creates a new [`StackBlur`] from the provided iterator, radius,
+	/// [`EdgeMode`], and [`VecDeque`].
+	///
+	/// The iterator is not advanced until a call to [`StackBlur::next`].
+	pub fn with_edge_mode(iter: I, radius: usize, edge_mode: EdgeMode, ops: VecDeque<T>) -> Self {
 		Self {
-			iter,
+			iter: Padded::new(iter, radius, edge_mode),
 			radius,
 			sum: T::default(),
 			rate: T::default(),
@@ -179,6 +400,7 @@ impl<T: StackBlurrable, I: Iterator<Item = T>> StackBlur<T, I> {
 
 	fn init(&mut self) {
 		self.done = false;
+		self.iter.restart();
 
 		for sub in 0..=self.radius {
 			let item = match self.iter.next() {
@@ -215,23 +437,18 @@ impl<T: StackBlurrable, I: Iterator<Item = T>> StackBlur<T, I> {
 
 		if self.dnom == 0 {
 			self.done = true;
+		} else if self.iter.edge_mode != EdgeMode::Renormalize {
+			// The padded input is always at least `radius * 2 + 1` long, so
+			// this can never run out of items.
+			for _ in 0..self.radius {
+				self.step();
+			}
 		}
 	}
-}
-
-impl<T: StackBlurrable, I: Iterator<Item = T>> Iterator for StackBlur<T, I> {
-	type Item = T;
 
+	/// Returns the current item and advances to the next one.
 	#[inline]
-	fn next(&mut self) -> Option<Self::Item> {
-		if self.done {
-			self.init();
-
-			if !std::mem::replace(&mut self.first, false) {
-				return None;
-			}
-		}
-
+	fn step(&mut self) -> T {
 		let result = self.sum.clone() / self.dnom;
 
 		self.rate += self.ops.pop_front().unwrap();
@@ -248,10 +465,12 @@ impl<T: StackBlurrable, I: Iterator<Item = T>> Iterator for StackBlur<T, I> {
 				self.rate += item.clone();
 				self.ops[self.radius] -= item.clone() * 2;
 				self.ops.push_back(item);
-			} else if self.radius > 0 {
+			} else if self.radius > 0 && self.iter.edge_mode == EdgeMode::Renormalize {
 				self.dnom -= self.radius + 1 - self.trailing;
 				self.trailing -= 1;
 			} else {
+				// In the padded modes, running out of input means the rest of
+				// the items would belong to virtual pixels.
 				self.done = true;
 			}
 		} else if self.trailing > 0 {
@@ -261,6 +480,1030 @@ impl<T: StackBlurrable, I: Iterator<Item = T>> Iterator for StackBlur<T, I> {
 			self.done = true;
 		}
 
-		Some(result)
+		result
+	}
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = T>> Iterator for StackBlur<T, I> {
+	type Item = T;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done {
+			self.init();
+
+			if self.done || !std::mem::replace(&mut self.first, false) {
+				return None;
+			}
+		}
+
+		Some(self.step())
+	}
+}
+
+/// The shape of the kernel that a [`KernelBlur`] weights its inputs by.
+///
+/// Every shape is a box blur convolved with itself some number of times, and
+/// spans `radius * 2 + 1` pixels. With a radius of 3, the weights are:
+///
+/// ```text
+/// Box:        1  1  1  1  1  1  1     / 7
+/// Tent:       1  2  3  4  3  2  1     / 16
+/// Quadratic:  1  3  6  7  6  3  1     / 27
+/// ```
+///
+/// The higher the order of the kernel, the closer it is to a Gaussian, and the
+/// larger its weights become. The sum of the weights is `radius * 2 + 1` for
+/// [`Kernel::Box`], `(radius + 1)²` for [`Kernel::Tent`], and about
+/// `((radius * 2 + 3) / 3)³` for [`Kernel::Quadratic`], so the
+/// [`StackBlurrable`] type needs correspondingly more headroom.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum Kernel {
+	/// A flat average, also known as a box blur. This is the cheapest, but it
+	/// has hard edges that show up as streaks around bright pixels.
+	Box,
+	/// A triangle, which is the kernel used by [`StackBlur`].
+	#[default]
+	Tent,
+	/// A piecewise quadratic curve, made of three box blurs of (nearly) equal
+	/// width. This is very close to a Gaussian.
+	Quadratic
+}
+
+impl Kernel {
+	/// Returns the sum of the weights of this kernel with the given radius,
+	/// saturating at [`usize::MAX`]. Blurring items of up to `max` needs a
+	/// [`StackBlurrable`] type that can hold `max` times this.
+	pub fn total_weight(self, radius: usize) -> usize {
+		let (widths, order) = self.widths(radius);
+		widths[..order].iter().fold(1usize, |total, &width| total.saturating_mul(width))
+	}
+
+	/// Returns the widths of the box blurs that make up this kernel, which add
+	/// up to `radius * 2 + order`, and the number of them (its order). Radii too
+	/// large for that to fit in a `usize` saturate instead of wrapping around.
+	fn widths(self, radius: usize) -> ([usize; 3], usize) {
+		match self {
+			Kernel::Box => ([radius.saturating_mul(2).saturating_add(1), 0, 0], 1),
+			Kernel::Tent => ([radius.saturating_add(1), radius.saturating_add(1), 0], 2),
+			Kernel::Quadratic => {
+				let total = radius.saturating_mul(2).saturating_add(3);
+				let width = |index: usize| total / 3 + usize::from(index < total % 3);
+				([width(0), width(1), width(2)], 3)
+			}
+		}
+	}
+}
+
+/// An iterator that blurs with a choice of [`Kernel`] shapes.
+///
+/// This is a generalization of [`StackBlur`] that is just as incremental.
+/// Where [`StackBlur`] keeps a sum and its rate of change, [`KernelBlur`] keeps
+/// one running sum for each box blur that makes up the kernel, each summing
+/// the one before it, and the `ops` queue holds the changes to the innermost
+/// one. The denominator is tracked the same way, so the edges are renormalized
+/// exactly like they are by [`StackBlur`], and the other [`EdgeMode`]s are
+/// supported through [`KernelBlur::with_edge_mode`].
+///
+/// With [`Kernel::Tent`] this yields the same items as a [`StackBlur`] with
+/// the same radius, but [`StackBlur`] is faster, so prefer it for that shape.
+/// Each additional box blur makes the kernel one order higher and costs one
+/// more addition per item, and the time taken does not depend on the radius.
+///
+/// Some of the intermediate sums go negative, so the [`StackBlurrable`] type
+/// must be signed or wrapping, as the trait documentation asks for.
+///
+/// ## Usage
+///
+/// Like [`StackBlur`], [`KernelBlur`] needs a [`VecDeque`] for its cache, which
+/// holds up to `radius * 2 + 4` elements, each paired with a count of items for
+/// the denominator. It can be retrieved again by calling
+/// [`KernelBlur::into_ops`].
+pub struct KernelBlur<T: StackBlurrable, I: Iterator<Item = T>> {
+	inner: Cascade<T, Unweighted<I>>
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = T>> KernelBlur<T, I> {
+	/// Creates a new [`KernelBlur`] from the provided iterator, radius,
+	/// [`Kernel`], and [`VecDeque`].
+	///
+	/// The iterator is not advanced until a call to [`KernelBlur::next`].
+	pub fn new(iter: I, radius: usize, kernel: Kernel, ops: VecDeque<(T, usize)>) -> Self {
+		Self::with_edge_mode(iter, radius, kernel, EdgeMode::Renormalize, ops)
+	}
+
+	/// Creates a new [`KernelBlur`] from the provided iterator, radius,
+	/// [`Kernel`], [`EdgeMode`], and [`VecDeque`].
+	///
+	/// The iterator is not advanced until a call to [`KernelBlur::next`].
+	pub fn with_edge_mode(iter: I, radius: usize, kernel: Kernel, edge_mode: EdgeMode, ops: VecDeque<(T, usize)>) -> Self {
+		Self { inner: Cascade::new(Unweighted(iter), radius, kernel, edge_mode, ops) }
+	}
+
+	/// Consumes this [`KernelBlur`] and returns the inner [`VecDeque`].
+	pub fn into_ops(self) -> VecDeque<(T, usize)> {
+		self.inner.ops
+	}
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = T>> Iterator for KernelBlur<T, I> {
+	type Item = T;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let (sum, dnom) = self.inner.next()?;
+		Some(sum / dnom)
+	}
+}
+
+/// An iterator that implements a weighted blur, also known as normalized
+/// convolution, with a choice of [`Kernel`] shapes.
+///
+/// The inner iterator yields each item along with its weight, and every output
+/// is the average of the items under the kernel, weighted by both the kernel
+/// and the weights of the items. An item with a weight of 0 does not affect the
+/// result at all, so this can fill in holes in an image, or blur samples with
+/// varying confidence without the bad ones dragging the rest along. Where the
+/// kernel covers no weight at all, the output is [`T::default()`][Default].
+///
+/// Each output comes with the sum of the weights that went into it (which is
+/// multiplied by the weights of the kernel), so blurring the output again with
+/// another [`WeightedBlur`] gives the same result as blurring the input once
+/// with both kernels convolved together. This is how the 2D helpers such as
+/// [`blur_weighted`][crate::blur_weighted] blur the columns.
+///
+/// Pixels off the edge of the input simply have no weight, so
+/// [`EdgeMode::Renormalize`] falls out naturally. The other [`EdgeMode`]s
+/// repeat the weights along with the items.
+///
+/// Every item is multiplied by its weight before being summed, so the
+/// [`StackBlurrable`] type needs as much headroom for the weights as it needs
+/// for the kernel. Small weights, like 0 and 1 or 0 to 255, go a long way.
+///
+/// ## Usage
+///
+/// [`WeightedBlur`] takes the same kind of [`VecDeque`] as [`KernelBlur`], with
+/// the sums of the weights in place of the counts of items.
+pub struct WeightedBlur<T: StackBlurrable, I: Iterator<Item = (T, usize)>> {
+	inner: Cascade<T, I>
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = (T, usize)>> WeightedBlur<T, I> {
+	/// Creates a new [`WeightedBlur`] from the provided iterator of items and
+	/// weights, radius, [`Kernel`], and [`VecDeque`].
+	///
+	/// The iterator is not advanced until a call to [`WeightedBlur::next`].
+	pub fn new(iter: I, radius: usize, kernel: Kernel, ops: VecDeque<(T, usize)>) -> Self {
+		Self::with_edge_mode(iter, radius, kernel, EdgeMode::Renormalize, ops)
+	}
+
+	/// Creates a new [`WeightedBlur`] from the provided iterator of items and
+	/// weights, radius, [`Kernel`], [`EdgeMode`], and [`VecDeque`].
+	///
+	/// The iterator is not advanced until a call to [`WeightedBlur::next`].
+	pub fn with_edge_mode(iter: I, radius: usize, kernel: Kernel, edge_mode: EdgeMode, ops: VecDeque<(T, usize)>) -> Self {
+		Self { inner: Cascade::new(iter, radius, kernel, edge_mode, ops) }
+	}
+
+	/// Consumes this [`WeightedBlur`] and returns the inner [`VecDeque`].
+	pub fn into_ops(self) -> VecDeque<(T, usize)> {
+		self.inner.ops
+	}
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = (T, usize)>> Iterator for WeightedBlur<T, I> {
+	type Item = (T, usize);
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let (sum, weight) = self.inner.next()?;
+
+		if weight == 0 {
+			Some((T::default(), 0))
+		} else {
+			Some((sum / weight, weight))
+		}
+	}
+}
+
+/// Gives every item of an iterator a weight of 1.
+struct Unweighted<I>(I);
+
+impl<T, I: Iterator<Item = T>> Iterator for Unweighted<I> {
+	type Item = (T, usize);
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.next().map(|item| (item, 1))
+	}
+}
+
+/// The engine behind [`KernelBlur`] and [`WeightedBlur`], which yields the sum
+/// of the weighted items under the kernel along with the sum of their weights.
+struct Cascade<T: StackBlurrable, I: Iterator<Item = (T, usize)>> {
+	iter: Padded<(T, usize), I>,
+	radius: usize,
+	/// The offsets into `ops` at which each new item is added or subtracted,
+	/// which together multiply out `(1 - z^width)` for every box width.
+	taps: [(usize, bool); 8],
+	order: usize,
+	sums: [T; 3],
+	dnoms: [usize; 3],
+	ops: VecDeque<(T, usize)>,
+	/// The number of items that have been read from the padded input.
+	time: usize,
+	/// The length of the padded input, once the end of it has been reached.
+	len: Option<usize>
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = (T, usize)>> Cascade<T, I> {
+	fn new(iter: I, radius: usize, kernel: Kernel, edge_mode: EdgeMode, mut ops: VecDeque<(T, usize)>) -> Self {
+		let (widths, order) = kernel.widths(radius);
+
+		let mut taps = [(0, false); 8];
+		for (subset, tap) in taps.iter_mut().enumerate().take(1 << order) {
+			let offset = (0..order).filter(|bit| subset & 1 << bit != 0).map(|bit| widths[bit]).sum();
+			*tap = (offset, subset.count_ones() % 2 == 1);
+		}
+
+		let span: usize = widths.iter().sum();
+		ops.clear();
+		ops.resize(span + 1, (T::default(), 0));
+
+		Self {
+			iter: Padded::new(iter, radius, edge_mode),
+			radius,
+			taps,
+			order,
+			sums: Default::default(),
+			dnoms: [0; 3],
+			ops,
+			time: 0,
+			len: None
+		}
+	}
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = (T, usize)>> Iterator for Cascade<T, I> {
+	type Item = (T, usize);
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		// The padded modes feed `radius` virtual items before the first real
+		// one, whose outputs are skipped.
+		let skip = if self.iter.edge_mode == EdgeMode::Renormalize { 0 } else { self.radius };
+
+		loop {
+			match self.len {
+				Some(len) if len == 0 || self.time >= len - skip + self.radius => return None,
+				Some(_) => (),
+				None => match self.iter.next() {
+					Some((item, weight)) => {
+						let item = item * weight;
+
+						for &(offset, negative) in &self.taps[..1 << self.order] {
+							let (value, count) = &mut self.ops[offset];
+
+							if negative {
+								*value -= item.clone();
+								*count = count.wrapping_sub(weight);
+							} else {
+								*value += item.clone();
+								*count = count.wrapping_add(weight);
+							}
+						}
+					}
+
+					None => {
+						self.len = Some(self.time);
+						continue;
+					}
+				}
+			}
+
+			let (mut value, mut count) = self.ops.pop_front().unwrap();
+			self.ops.push_back((T::default(), 0));
+
+			for stage in 0..self.order {
+				self.sums[stage] += value;
+				value = self.sums[stage].clone();
+				self.dnoms[stage] = self.dnoms[stage].wrapping_add(count);
+				count = self.dnoms[stage];
+			}
+
+			// The item read at `time` is the last one under the kernel centered
+			// `radius` items earlier.
+			self.time += 1;
+			if self.time > self.radius + skip {
+				return Some((value, count));
+			}
+		}
+	}
+}
+
+/// The fractional part of a radius is rounded to the nearest multiple of
+/// `1 / FRACTION_SCALE`.
+pub(crate) const FRACTION_SCALE: usize = 256;
+
+/// Splits a fractional radius into its integer part and its fractional part
+/// out of [`FRACTION_SCALE`].
+pub(crate) fn split_radius(radius: f32) -> (usize, usize) {
+	// This also sends NaN to 0.
+	let radius = radius.max(0.0);
+	let whole = radius.floor();
+	let fraction = ((radius - whole) * FRACTION_SCALE as f32).round() as usize;
+
+	if fraction == FRACTION_SCALE {
+		(whole as usize + 1, 0)
+	} else {
+		(whole as usize, fraction)
+	}
+}
+
+/// Hands out the items of an iterator while keeping a copy of each.
+struct Tee<T, I: Iterator<Item = T>> {
+	iter: I,
+	copies: Option<VecDeque<T>>
+}
+
+impl<T: Clone, I: Iterator<Item = T>> Iterator for Tee<T, I> {
+	type Item = T;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let item = self.iter.next()?;
+
+		if let Some(copies) = &mut self.copies {
+			copies.push_back(item.clone());
+		}
+
+		Some(item)
+	}
+}
+
+/// Hands out items that have been pushed into it ahead of time.
+struct Queue<T>(VecDeque<T>);
+
+impl<T> Iterator for Queue<T> {
+	type Item = T;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.pop_front()
+	}
+}
+
+/// An iterator that implements the improved Stackblur algorithm with a
+/// fractional radius.
+///
+/// This works like a [`StackBlur`], except the radius is an `f32` and the
+/// strength of the blur changes continuously with it. The output is a blend of
+/// the outputs of two [`StackBlur`]s, one with the radius rounded down and one
+/// with it rounded up, weighted by the fractional part of the radius, which is
+/// itself rounded to the nearest 1/256th.
+///
+/// Both [`StackBlur`]s are driven from a single pass over the inner iterator,
+/// so this is still constant-time, but it does twice the work. If the radius
+/// happens to be a whole number, only one [`StackBlur`] is run.
+///
+/// The blend multiplies each output by up to 256 before dividing it again, so
+/// [`StackBlurrable`] types used with this need 8 bits of headroom above the
+/// pixel format they represent.
+///
+/// ## Usage
+///
+/// [`FractionalStackBlur`] needs a cache for each of its [`StackBlur`]s, so it
+/// takes a pair of them (called `ops` by [`FractionalStackBlur::new`]), which
+/// can be retrieved again by calling [`FractionalStackBlur::into_ops`].
+pub struct FractionalStackBlur<T: StackBlurrable, I: Iterator<Item = T>> {
+	upper: StackBlur<T, Tee<T, I>>,
+	lower: StackBlur<T, Queue<T>>,
+	fraction: usize
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = T>> FractionalStackBlur<T, I> {
+	/// Creates a new [`FractionalStackBlur`] from the provided iterator,
+	/// radius, and pair of [`VecDeque`]s.
+	///
+	/// Negative radii are treated as 0. The iterator is not advanced until a
+	/// call to [`FractionalStackBlur::next`].
+	pub fn new(iter: I, radius: f32, ops: [VecDeque<T>; 2]) -> Self {
+		Self::with_edge_mode(iter, radius, EdgeMode::Renormalize, ops)
+	}
+
+	/// Creates a new [`FractionalStackBlur`] from the provided iterator,
+	/// radius, [`EdgeMode`], and pair of [`VecDeque`]s.
+	///
+	/// Negative radii are treated as 0. The iterator is not advanced until a
+	/// call to [`FractionalStackBlur::next`].
+	pub fn with_edge_mode(iter: I, radius: f32, edge_mode: EdgeMode, ops: [VecDeque<T>; 2]) -> Self {
+		let (radius, fraction) = split_radius(radius);
+		let [upper_ops, lower_ops] = ops;
+		let copies = if fraction > 0 { Some(VecDeque::new()) } else { None };
+		let upper_radius = if fraction > 0 { radius + 1 } else { radius };
+
+		Self {
+			upper: StackBlur::with_edge_mode(Tee { iter, copies }, upper_radius, edge_mode, upper_ops),
+			lower: StackBlur::with_edge_mode(Queue(VecDeque::new()), radius, edge_mode, lower_ops),
+			fraction
+		}
+	}
+
+	/// Consumes this [`FractionalStackBlur`] and returns the inner pair of
+	/// [`VecDeque`]s.
+	pub fn into_ops(self) -> [VecDeque<T>; 2] {
+		[self.upper.into_ops(), self.lower.into_ops()]
+	}
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = T>> Iterator for FractionalStackBlur<T, I> {
+	type Item = T;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let upper = self.upper.next();
+
+		if self.fraction == 0 {
+			return upper;
+		}
+
+		// The upper blur always reads further ahead than the lower one, so
+		// once it has been advanced, the lower one has everything it needs.
+		if let Some(copies) = &mut self.upper.iter.iter.copies {
+			self.lower.iter.iter.0.extend(copies.drain(..));
+		}
+
+		let lower = self.lower.next();
+
+		match (lower, upper) {
+			(Some(lower), Some(upper)) => Some((lower * (FRACTION_SCALE - self.fraction) + upper * self.fraction) / FRACTION_SCALE),
+			_ => None
+		}
+	}
+}
+
+/// Drives two [`StackBlur`]s with different radii from a single pass over the
+/// same input, and yields their outputs side by side, in the order that their
+/// radii were given in.
+pub(crate) struct BlurPair<T: StackBlurrable, I: Iterator<Item = T>> {
+	/// The [`StackBlur`] with the larger radius, which reads the furthest ahead.
+	ahead: StackBlur<T, Tee<T, I>>,
+	behind: StackBlur<T, Queue<T>>,
+	/// Whether the first radius is the one that `ahead` was given.
+	swapped: bool
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = T>> BlurPair<T, I> {
+	pub(crate) fn with_edge_mode(iter: I, radii: [usize; 2], edge_mode: EdgeMode, ops: [VecDeque<T>; 2]) -> Self {
+		let [first_ops, second_ops] = ops;
+		let swapped = radii[0] > radii[1];
+		let ((ahead, ahead_ops), (behind, behind_ops)) = if swapped {
+			((radii[0], first_ops), (radii[1], second_ops))
+		} else {
+			((radii[1], second_ops), (radii[0], first_ops))
+		};
+
+		Self {
+			ahead: StackBlur::with_edge_mode(Tee { iter, copies: Some(VecDeque::new()) }, ahead, edge_mode, ahead_ops),
+			behind: StackBlur::with_edge_mode(Queue(VecDeque::new()), behind, edge_mode, behind_ops),
+			swapped
+		}
+	}
+
+	pub(crate) fn into_ops(self) -> [VecDeque<T>; 2] {
+		let (ahead, behind) = (self.ahead.into_ops(), self.behind.into_ops());
+		if self.swapped { [ahead, behind] } else { [behind, ahead] }
+	}
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = T>> Iterator for BlurPair<T, I> {
+	type Item = (T, T);
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let ahead = self.ahead.next();
+
+		// Just like in `FractionalStackBlur`, once the blur that reads further
+		// ahead has been advanced, the other one has everything it needs.
+		if let Some(copies) = &mut self.ahead.iter.iter.copies {
+			self.behind.iter.iter.0.extend(copies.drain(..));
+		}
+
+		match (self.behind.next(), ahead) {
+			(Some(behind), Some(ahead)) => Some(if self.swapped { (ahead, behind) } else { (behind, ahead) }),
+			_ => None
+		}
+	}
+}
+
+/// An iterator that yields the difference between two Stackblurs of the same
+/// input with different radii.
+///
+/// Every output is what a [`StackBlur`] with the narrow radius would yield at
+/// that position, minus what one with the wide radius would yield. This keeps
+/// the details of the input that are larger than the narrow radius but smaller
+/// than the wide one, which makes it a band-pass filter, and the 1D building
+/// block of a difference of Gaussians. A narrow radius of 0 keeps everything
+/// that is smaller than the wide radius, which makes it a high-pass filter.
+///
+/// Both [`StackBlur`]s are driven from a single pass over the inner iterator,
+/// like the ones in a [`FractionalStackBlur`], so this costs about as much as
+/// two [`StackBlur`]s, but only reads the input once. If the narrow radius is
+/// larger than the wide one, the difference simply comes out negated.
+///
+/// ## Negative differences
+///
+/// The difference is negative wherever the wide blur is brighter, so the
+/// [`StackBlurrable`] type must either be signed, or wrap around on overflow,
+/// in which case negative differences come out in two's complement.
+///
+/// ## Usage
+///
+/// [`DifferenceBlur`] needs a cache for each of its [`StackBlur`]s, so it takes
+/// a pair of them (called `ops` by [`DifferenceBlur::new`]), which can be
+/// retrieved again by calling [`DifferenceBlur::into_ops`].
+pub struct DifferenceBlur<T: StackBlurrable, I: Iterator<Item = T>> {
+	pair: BlurPair<T, I>
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = T>> DifferenceBlur<T, I> {
+	/// Creates a new [`DifferenceBlur`] from the provided iterator, narrow and
+	/// wide radii, and pair of [`VecDeque`]s.
+	///
+	/// The iterator is not advanced until a call to [`DifferenceBlur::next`].
+	pub fn new(iter: I, radius_narrow: usize, radius_wide: usize, ops: [VecDeque<T>; 2]) -> Self {
+		Self::with_edge_mode(iter, radius_narrow, radius_wide, EdgeMode::Renormalize, ops)
+	}
+
+	/// Creates a new [`DifferenceBlur`] from the provided iterator, narrow and
+	/// wide radii, [`EdgeMode`], and pair of [`VecDeque`]s.
+	///
+	/// The iterator is not advanced until a call to [`DifferenceBlur::next`].
+	pub fn with_edge_mode(iter: I, radius_narrow: usize, radius_wide: usize, edge_mode: EdgeMode, ops: [VecDeque<T>; 2]) -> Self {
+		Self { pair: BlurPair::with_edge_mode(iter, [radius_narrow, radius_wide], edge_mode, ops) }
+	}
+
+	/// Consumes this [`DifferenceBlur`] and returns the inner pair of
+	/// [`VecDeque`]s.
+	pub fn into_ops(self) -> [VecDeque<T>; 2] {
+		self.pair.into_ops()
+	}
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = T>> Iterator for DifferenceBlur<T, I> {
+	type Item = T;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let (mut narrow, wide) = self.pair.next()?;
+		narrow -= wide;
+		Some(narrow)
+	}
+}
+
+/// The running sums that [`MultiStackBlur`] keeps for each of its radii,
+/// along with the weights that they were summed with.
+#[derive(Clone, Default)]
+struct Sums<T> {
+	/// The items within the radius, weighted like a tent.
+	sum: T,
+	/// The items from just after the center to one past the radius, which gain
+	/// weight when the center moves forward.
+	ahead: T,
+	/// The items from the radius before the center up to the center, which
+	/// lose weight when the center moves forward.
+	behind: T,
+	dnom: usize,
+	dnom_ahead: usize,
+	dnom_behind: usize
+}
+
+/// An iterator that blurs its inner iterator by several radii at once, and
+/// yields an array with the output of each radius.
+///
+/// Every output is exactly what a [`StackBlur`] with that radius would yield at
+/// that position, with the weights renormalized at the edges, but the inner
+/// iterator is only read once, and only a single history of at most
+/// `max_radius * 2 + 3` items is kept for all of the radii. Instead of the
+/// queue of pending updates that a [`StackBlur`] keeps, each radius has three
+/// running sums, which are updated from that history by a few additions and
+/// subtractions per item, however large the radius is.
+///
+/// ## Usage
+///
+/// [`MultiStackBlur`] stores its history in a [`VecDeque`] (called `ops` by
+/// [`MultiStackBlur::new`]), which can be retrieved again by calling
+/// [`MultiStackBlur::into_ops`].
+///
+/// ```
+/// # use std::collections::VecDeque;
+/// # use stackblur_iter::iter::MultiStackBlur;
+/// # use stackblur_iter::float::StackBlurrableF32;
+/// #
+/// let arr = [0.0, 0.0, 8.0, 0.0, 0.0].map(StackBlurrableF32);
+/// let blur = MultiStackBlur::new(arr.into_iter(), [0, 1], VecDeque::new());
+/// let blurred = blur.map(|items| items.map(|item| item.0)).collect::<Vec<_>>();
+/// assert_eq!(blurred, [[0.0, 0.0], [0.0, 2.0], [8.0, 4.0], [0.0, 2.0], [0.0, 0.0]]);
+/// ```
+///
+/// Like a [`StackBlur`], a [`MultiStackBlur`] always yields exactly as many
+/// items as its inner iterator does, and a non-fused iterator which repeats
+/// will cause it to repeat as well.
+pub struct MultiStackBlur<T: StackBlurrable, I: Iterator<Item = T>, const N: usize> {
+	iter: I,
+	radii: [usize; N],
+	max_radius: usize,
+	sums: [Sums<T>; N],
+	/// The items from `max_radius` before `index` onwards, as far as they have
+	/// been read.
+	ops: VecDeque<T>,
+	/// The position of the first item in `ops`.
+	start: usize,
+	/// The number of items read from `iter` so far.
+	read: usize,
+	/// The position of the next item to yield.
+	index: usize,
+	done: bool,
+	started: bool
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = T>, const N: usize> MultiStackBlur<T, I, N> {
+	/// Creates a new [`MultiStackBlur`] from the provided iterator, radii, and
+	/// [`VecDeque`].
+	///
+	/// The iterator is not advanced until a call to [`MultiStackBlur::next`].
+	pub fn new(iter: I, radii: [usize; N], ops: VecDeque<T>) -> Self {
+		Self {
+			iter,
+			radii,
+			max_radius: radii.into_iter().max().unwrap_or(0),
+			sums: std::array::from_fn(|_| Sums::default()),
+			ops,
+			start: 0,
+			read: 0,
+			index: 0,
+			done: false,
+			started: false
+		}
+	}
+
+	/// Consumes this [`MultiStackBlur`] and returns the inner [`VecDeque`].
+	pub fn into_ops(self) -> VecDeque<T> {
+		self.ops
+	}
+
+	/// Reads items from the inner iterator until the one at `position`, unless
+	/// it runs out first.
+	fn fill(&mut self, position: usize) {
+		while !self.done && self.read <= position {
+			match self.iter.next() {
+				Some(item) => {
+					self.ops.push_back(item);
+					self.read += 1;
+				}
+
+				None => self.done = true
+			}
+		}
+	}
+
+	/// Returns the item at `position` and its weight, which is 0 if it lies
+	/// off the edge of the input.
+	fn item(&self, position: isize) -> (T, usize) {
+		if position < self.start as isize || position >= self.read as isize {
+			(T::default(), 0)
+		} else {
+			(self.ops[position as usize - self.start].clone(), 1)
+		}
+	}
+
+	fn init(&mut self) {
+		self.started = true;
+		self.ops.clear();
+		self.start = 0;
+		self.read = 0;
+		self.index = 0;
+		self.done = false;
+		self.fill(self.max_radius + 1);
+
+		for n in 0..N {
+			let radius = self.radii[n];
+			let mut sums = Sums::default();
+
+			for position in 0..=radius {
+				let (item, weight) = self.item(position as isize);
+				sums.sum += item * (radius + 1 - position);
+				sums.dnom += weight * (radius + 1 - position);
+			}
+
+			for position in 1..=radius + 1 {
+				let (item, weight) = self.item(position as isize);
+				sums.ahead += item;
+				sums.dnom_ahead += weight;
+			}
+
+			let (item, weight) = self.item(0);
+			sums.behind = item;
+			sums.dnom_behind = weight;
+			self.sums[n] = sums;
+		}
+	}
+
+	/// Moves the center of every radius forward by one item.
+	fn step(&mut self) {
+		let center = self.index as isize;
+		self.fill(self.index + self.max_radius + 2);
+
+		let (next, next_weight) = self.item(center + 1);
+
+		for n in 0..N {
+			let radius = self.radii[n] as isize;
+			let (entering, entering_weight) = self.item(center + radius + 2);
+			let (leaving, leaving_weight) = self.item(center - radius);
+			let sums = &mut self.sums[n];
+
+			sums.sum += sums.ahead.clone();
+			sums.sum -= sums.behind.clone();
+			sums.dnom = sums.dnom + sums.dnom_ahead - sums.dnom_behind;
+
+			sums.ahead += entering;
+			sums.ahead -= next.clone();
+			sums.dnom_ahead = sums.dnom_ahead + entering_weight - next_weight;
+
+			sums.behind += next.clone();
+			sums.behind -= leaving;
+			sums.dnom_behind = sums.dnom_behind + next_weight - leaving_weight;
+		}
+
+		self.index += 1;
+
+		// The next step needs the items back to `max_radius` before the center.
+		while self.start + self.max_radius < self.index {
+			self.ops.pop_front();
+			self.start += 1;
+		}
+	}
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = T>, const N: usize> Iterator for MultiStackBlur<T, I, N> {
+	type Item = [T; N];
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.started {
+			self.step();
+		} else {
+			self.init();
+		}
+
+		if self.index >= self.read {
+			// Start over in case the inner iterator repeats.
+			self.started = false;
+			return None;
+		}
+
+		Some(std::array::from_fn(|n| self.sums[n].sum.clone() / self.sums[n].dnom))
+	}
+}
+
+/// An iterator that implements the improved Stackblur algorithm with a
+/// different radius for every item.
+///
+/// The inner iterator yields each item along with the radius of the blur at
+/// its position. Every output is exactly what a [`StackBlur`] with that radius
+/// would yield at that position, whatever the radii of its neighbors are, so an
+/// item with a radius of 0 is left untouched even right next to heavily blurred
+/// ones. Items with a large radius still gather from their sharp neighbors, the
+/// same way an out-of-focus background does in a photograph.
+///
+/// Since the radius can change from one item to the next, there is no single
+/// rolling sum to update. Instead, the entire inner iterator is collected on
+/// the first call to [`VariableStackBlur::next`] and summed up twice over, and
+/// each output is then read off three of those sums, so the cost per item still
+/// does not depend on the radius.
+///
+/// Those sums span the entire input, so they get much larger than the ones in
+/// [`StackBlur`]. [`StackBlurrable`] types with wrapping arithmetic work no
+/// matter how long the input is, because only the differences between the sums
+/// matter, but signed types need room for about `len * len / 2` times the
+/// largest item.
+///
+/// ## Usage
+///
+/// [`VariableStackBlur`] stores the sums along with the radii in a [`Vec`]
+/// (called `ops` by [`VariableStackBlur::new`]) of `len + 2` elements, which
+/// can be retrieved again by calling [`VariableStackBlur::into_ops`].
+pub struct VariableStackBlur<T: StackBlurrable, I: Iterator<Item = (T, usize)>> {
+	iter: I,
+	/// The sums of the sums of all items before each position, and the radius
+	/// at that position.
+	ops: Vec<(T, usize)>,
+	index: usize,
+	started: bool
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = (T, usize)>> VariableStackBlur<T, I> {
+	/// Creates a new [`VariableStackBlur`] from the provided iterator of items
+	/// and radii, and [`Vec`].
+	///
+	/// The iterator is not advanced until a call to [`VariableStackBlur::next`].
+	pub fn new(iter: I, ops: Vec<(T, usize)>) -> Self {
+		Self { iter, ops, index: 0, started: false }
+	}
+
+	/// Consumes this [`VariableStackBlur`] and returns the inner [`Vec`].
+	pub fn into_ops(self) -> Vec<(T, usize)> {
+		self.ops
+	}
+
+	/// Returns the sum of the sums of the items before `index`, as if the input
+	/// were surrounded by zeroes.
+	fn sum_at(&self, index: isize) -> T {
+		let len = self.ops.len() - 2;
+
+		if index <= 0 {
+			T::default()
+		} else if index as usize <= len + 1 {
+			self.ops[index as usize].0.clone()
+		} else {
+			let mut total = self.ops[len + 1].0.clone();
+			total -= self.ops[len].0.clone();
+			self.ops[len + 1].0.clone() + total * (index as usize - len - 1)
+		}
+	}
+
+	/// Returns what [`VariableStackBlur::sum_at`] would return if every item
+	/// were 1.
+	fn count_at(&self, index: isize) -> usize {
+		let len = self.ops.len() - 2;
+
+		if index <= 0 {
+			0
+		} else if index as usize <= len + 1 {
+			index as usize * (index as usize - 1) / 2
+		} else {
+			len * (len + 1) / 2 + (index as usize - len - 1) * len
+		}
+	}
+}
+
+impl<T: StackBlurrable, I: Iterator<Item = (T, usize)>> Iterator for VariableStackBlur<T, I> {
+	type Item = T;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		if !self.started {
+			self.started = true;
+			self.ops.clear();
+
+			let mut once = T::default();
+			let mut twice = T::default();
+
+			for (item, radius) in &mut self.iter {
+				self.ops.push((twice.clone(), radius));
+				twice += once.clone();
+				once += item;
+			}
+
+			// The sums just past the end are needed by the last item.
+			self.ops.push((twice.clone(), 0));
+			twice += once;
+			self.ops.push((twice, 0));
+		}
+
+		if self.index + 2 == self.ops.len() {
+			return None;
+		}
+
+		// The weighted sum of a tent is the sum of `radius + 1` boxes of width
+		// `radius + 1`, which is this difference of differences.
+		let index = self.index as isize;
+		let radius = self.ops[self.index].1 as isize;
+		let mut sum = self.sum_at(index + radius + 2) + self.sum_at(index - radius);
+		sum -= self.sum_at(index + 1) * 2;
+		let dnom = self.count_at(index + radius + 2) + self.count_at(index - radius) - self.count_at(index + 1) * 2;
+
+		self.index += 1;
+		Some(sum / dnom)
+	}
+}
+
+/// An iterator that yields the largest or the smallest item within a radius of
+/// each item of its inner iterator.
+///
+/// This is known as dilation ([`Dilate`]) or erosion ([`Erode`]) in
+/// mathematical morphology. Each output is taken from the item at the same
+/// position and the `radius` items on either side of it. Items off the edge of
+/// the input are left out, the same way [`EdgeMode::Renormalize`] leaves them
+/// out of a [`StackBlur`], so the window just gets narrower near the edges.
+///
+/// Items are kept in a queue of candidates that are sorted from best to worst,
+/// and every item enters and leaves that queue only once, so the cost per item
+/// does not depend on the radius. Only [`PartialOrd`] and [`Clone`] are needed,
+/// so this works with plain integers and floats as well. Items that can't be
+/// compared with each other, like NaN, may or may not end up in the output.
+///
+/// ## Usage
+///
+/// [`Morphology`] stores the candidates along with their positions in a
+/// [`VecDeque`] (called `ops` by [`Morphology::new`]) of at most
+/// `radius * 2 + 1` elements, which can be retrieved again by calling
+/// [`Morphology::into_ops`].
+///
+/// ```
+/// # use std::collections::VecDeque;
+/// # use stackblur_iter::iter::{Dilate, Erode};
+/// #
+/// let arr = [0u8, 0, 9, 0, 0, 5, 5, 5, 0];
+/// let dilated = Dilate::new(arr.iter().copied(), 1, VecDeque::new()).collect::<Vec<_>>();
+/// let eroded = Erode::new(arr.iter().copied(), 1, VecDeque::new()).collect::<Vec<_>>();
+/// assert_eq!(dilated, [0, 9, 9, 9, 5, 5, 5, 5, 5]);
+/// assert_eq!(eroded, [0, 0, 0, 0, 0, 0, 5, 0, 0]);
+/// ```
+///
+/// A [`Morphology`] always yields exactly as many items as its inner iterator
+/// does, and never yields an item before reading the one `radius` positions
+/// after it. Like a [`StackBlur`], a non-fused iterator which repeats will
+/// cause the [`Morphology`] to repeat as well.
+pub struct Morphology<T: PartialOrd + Clone, I: Iterator<Item = T>, const GROW: bool> {
+	iter: I,
+	radius: usize,
+	/// The items that could still be yielded, and their positions.
+	ops: VecDeque<(T, usize)>,
+	/// The number of items read from `iter` so far.
+	read: usize,
+	/// The position of the next item to yield.
+	index: usize,
+	done: bool
+}
+
+/// A [`Morphology`] that yields the largest item within the radius.
+pub type Dilate<T, I> = Morphology<T, I, true>;
+
+/// A [`Morphology`] that yields the smallest item within the radius.
+pub type Erode<T, I> = Morphology<T, I, false>;
+
+impl<T: PartialOrd + Clone, I: Iterator<Item = T>, const GROW: bool> Morphology<T, I, GROW> {
+	/// Creates a new [`Morphology`] from the provided iterator, radius, and
+	/// [`VecDeque`].
+	///
+	/// The iterator is not advanced until a call to [`Morphology::next`].
+	pub fn new(iter: I, radius: usize, mut ops: VecDeque<(T, usize)>) -> Self {
+		ops.clear();
+		Self { iter, radius, ops, read: 0, index: 0, done: false }
+	}
+
+	/// Consumes this [`Morphology`] and returns the inner [`VecDeque`].
+	pub fn into_ops(self) -> VecDeque<(T, usize)> {
+		self.ops
+	}
+
+	/// Returns whether `a` is at least as good a candidate as `b`.
+	#[inline]
+	fn beats(a: &T, b: &T) -> bool {
+		if GROW { a >= b } else { a <= b }
+	}
+}
+
+impl<T: PartialOrd + Clone, I: Iterator<Item = T>, const GROW: bool> Iterator for Morphology<T, I, GROW> {
+	type Item = T;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		while !self.done && self.read <= self.index + self.radius {
+			let item = match self.iter.next() {
+				Some(item) => item,
+				None => {
+					self.done = true;
+					break;
+				}
+			};
+
+			// Anything this beats can never be yielded again, because this
+			// stays in the window for longer.
+			while matches!(self.ops.back(), Some((back, _)) if Self::beats(&item, back)) {
+				self.ops.pop_back();
+			}
+
+			self.ops.push_back((item, self.read));
+			self.read += 1;
+		}
+
+		if self.index == self.read {
+			// Start over in case the inner iterator repeats.
+			self.ops.clear();
+			self.read = 0;
+			self.index = 0;
+			self.done = false;
+			return None;
+		}
+
+		// The last item read is always in the queue, so this never empties it.
+		while self.ops[0].1 + self.radius < self.index {
+			self.ops.pop_front();
+		}
+
+		self.index += 1;
+		Some(self.ops[0].0.clone())
 	}
 }
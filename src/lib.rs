@@ -44,28 +44,351 @@
 //! documentation, there are helper functions like [`blur`] and [`blur_argb`]
 //! that can be used to interact with 2D image buffers, due to the fact that
 //! doing so manually involves unsafe code (if you want no-copy).
+//!
+//! Most of the helper functions also have a `try_` version, like
+//! [`try_blur_argb`], that checks its parameters first and returns a
+//! [`BlurError`](error::BlurError) instead of panicking or overflowing.
+//!
+//! For images that are too large to hold in memory at once, or that arrive one
+//! row at a time, [`StreamingBlur`](stream::StreamingBlur) blurs the rows as
+//! they are pushed and only keeps as many of them as its vertical radius needs.
+//! Video can be blurred over time in the same way, one frame at a time, with
+//! [`TemporalBlur`](stream::TemporalBlur).
+//!
+//! Buffers whose pixels have one byte per channel, like RGBA8, RGB8 or gray,
+//! can be blurred with the helpers in the [`bytes`] module, like
+//! [`blur_bytes`](bytes::blur_bytes), instead of packing them into ARGB first.
+//! Pixels with floating-point channels can be blurred without any rounding by
+//! the helpers in the [`float`] module, like [`blur_f32`](float::blur_f32).
+//!
+//! The conversions and channel types behind the `_argb` and `_srgb` helpers are
+//! in the [`color`] module, for use with [`blur`] and the other generic helpers
+//! when a buffer needs a conversion of its own.
+//!
+//! To blur only some of the channels of packed pixels, like just alpha for a
+//! shadow, use [`blur_argb_masked`] and its relatives with a
+//! [`ChannelMask`](color::ChannelMask). They skip the channels that are not in
+//! the mask entirely and leave them exactly as they were.
+//!
+//! Drop shadows and outer glows, which blur the alpha of a layer and tint it,
+//! are in the [`shadow`] module.
+//!
+//! [`dilate`] and [`erode`] grow and shrink the bright parts of a buffer, for
+//! outlines and cleaning up masks. They take the largest or smallest value
+//! around each pixel instead of the average, with the iterators of the same
+//! names in the [`iter`] module.
+//!
+//! To blur the same image by several radii, like for bloom or scale-space
+//! analysis, [`blur_multi`] and [`blur_argb_multi`] read it only once and write
+//! every blur into its own output.
+//!
+//! To sharpen packed pixels instead, [`unsharp_mask_argb`] and its relatives
+//! add the difference between each pixel and a blurred copy of it back onto
+//! the pixel.
+//!
+//! The difference between two blurs of the same image at different radii, like
+//! a difference of Gaussians, is computed by the helpers in the [`difference`]
+//! module, which read the image only once.
+//!
+//! For smoothing that keeps the edges of an image, or of a guide image like
+//! the photo that an alpha matte belongs to, see the guided filter in the
+//! [`guided`] module.
+//!
+//! Gaussian and Laplacian pyramids, which blur and halve an image over and over
+//! for multi-scale work like blending and bloom, are in the [`pyramid`] module.
 
 #![cfg_attr(feature = "simd", feature(portable_simd))]
 #![cfg_attr(test, feature(test))]
 
-use std::collections::VecDeque;
 #[cfg(any(doc, feature = "simd"))]
 use std::simd::{LaneCount, SupportedLaneCount};
 
 pub extern crate imgref;
 
-use imgref::ImgRefMut;
+use imgref::{ImgRef, ImgRefMut, ImgVec};
 
 #[cfg(test)]
 mod test;
 
 pub mod traits;
 pub mod iter;
-mod color;
+pub mod error;
+pub mod stream;
+pub mod bytes;
+pub mod float;
+pub mod color;
+pub mod shadow;
+pub mod difference;
+pub mod guided;
+pub mod pyramid;
+mod lines;
 
 use traits::StackBlurrable;
-use iter::StackBlur;
-use color::Argb;
+use iter::{EdgeMode, Kernel};
+use error::BlurError;
+#[cfg(doc)]
+use iter::{FractionalStackBlur, KernelBlur, StackBlur, VariableStackBlur, WeightedBlur};
+use color::{ArgbU32, ArgbU64, Channel, ChannelMask, Channels, StackBlurrableU32, StackBlurrableU64};
+#[cfg(feature = "simd")]
+use color::{ArgbU32xN, ArgbU64xN, ChannelxN, StackBlurrableU32xN, StackBlurrableU64xN};
+
+/// The largest value of a channel in the linear `_argb` helpers.
+const ARGB_CHANNEL_MAX: usize = 255;
+
+/// The largest value of a channel in the `_srgb` helpers, which blur in 12-bit
+/// linear light, and in the `_straight` helpers, which premultiply into 12 bits.
+const SRGB_CHANNEL_MAX: usize = 4095;
+
+/// The largest radius at which [`blur_argb`] and the other linear `_argb`
+/// helpers blur with 32 bits per channel. Above it, they switch to 64 bits.
+///
+/// This is for a plain Stackblur. Helpers whose kernels have larger weights,
+/// like [`blur_argb_fractional`] and [`blur_argb_kernel`] with
+/// [`Kernel::Quadratic`], switch at smaller radii.
+pub const ARGB_U32_MAX_RADIUS: usize = max_radius(ARGB_CHANNEL_MAX, u32::MAX as u64);
+
+/// The largest radius at which [`blur_argb`] and the other linear `_argb`
+/// helpers can blur with 64 bits per channel. Larger radii may overflow.
+pub const ARGB_U64_MAX_RADIUS: usize = max_radius(ARGB_CHANNEL_MAX, u64::MAX);
+
+/// The largest radius at which [`blur_srgb`] and the other `_srgb` helpers, as
+/// well as the `_straight` helpers, blur with 32 bits per channel. Above it,
+/// they switch to 64 bits.
+///
+/// This is for a plain Stackblur, just like [`ARGB_U32_MAX_RADIUS`].
+pub const SRGB_U32_MAX_RADIUS: usize = max_radius(SRGB_CHANNEL_MAX, u32::MAX as u64);
+
+/// The largest radius at which [`blur_srgb`] and the other `_srgb` helpers, as
+/// well as the `_straight` helpers, can blur with 64 bits per channel. Larger
+/// radii may overflow.
+pub const SRGB_U64_MAX_RADIUS: usize = max_radius(SRGB_CHANNEL_MAX, u64::MAX);
+
+/// Returns the largest radius at which a [`StackBlur`] over channels of up to
+/// `channel_max` never sums up to more than `accumulator_max`.
+const fn max_radius(channel_max: usize, accumulator_max: u64) -> usize {
+	let limit = accumulator_max / channel_max as u64;
+
+	// Binary search for the largest `radius + 1` whose square fits in `limit`.
+	let (mut low, mut high) = (1u64, 1u64 << 32);
+	while high - low > 1 {
+		let mid = (low + high) / 2;
+		if mid * mid <= limit { low = mid; } else { high = mid; }
+	}
+
+	(low - 1) as usize
+}
+
+/// Returns whether blurring channels of up to `channel_max` with a kernel whose
+/// weights add up to `weight` fits in 32 bits.
+fn fits_u32(channel_max: usize, weight: usize) -> bool {
+	(channel_max as u64).saturating_mul(weight as u64) <= u32::MAX as u64
+}
+
+/// Returns the radius of the larger of the two [`StackBlur`]s that a
+/// [`FractionalStackBlur`] with the given radius is made of.
+fn upper_radius(radius: f32) -> usize {
+	let (whole, fraction) = iter::split_radius(radius);
+	whole + usize::from(fraction > 0)
+}
+
+/// Returns the sum of the weights of a [`FractionalStackBlur`] whose larger
+/// [`StackBlur`] has the given radius, including the factor that it blends its
+/// two [`StackBlur`]s by.
+fn fractional_weight(upper_radius: usize) -> usize {
+	Kernel::Tent.total_weight(upper_radius).saturating_mul(iter::FRACTION_SCALE)
+}
+
+/// Returns an error if blurring channels of up to `channel_max` with the given
+/// radius, and a kernel whose weights add up to `weight(radius)`, could
+/// overflow 64 bits.
+fn check_radius(channel_max: usize, radius: usize, weight: impl Fn(usize) -> usize) -> Result<(), BlurError> {
+	check_radius_fits(radius, |radius| channel_max as u128 * weight(radius) as u128 <= u64::MAX as u128)
+}
+
+/// Returns an error if the weights of a kernel, which add up to
+/// `weight(radius)`, would saturate the `usize` that every blur divides by.
+///
+/// This is the only limit that the generic helpers know of, because they
+/// cannot know how much room their [`StackBlurrable`] type has.
+fn check_weight(radius: usize, weight: impl Fn(usize) -> usize) -> Result<(), BlurError> {
+	check_radius_fits(radius, |radius| weight(radius) < usize::MAX)
+}
+
+/// Returns an error with the largest radius that `fits`, if `radius` doesn't.
+fn check_radius_fits(radius: usize, fits: impl Fn(usize) -> bool) -> Result<(), BlurError> {
+	if fits(radius) {
+		return Ok(());
+	}
+
+	// Binary search for the largest radius that fits, which is below `radius`.
+	let (mut low, mut high) = (0, radius);
+	while high - low > 1 {
+		let mid = low + (high - low) / 2;
+		if fits(mid) { low = mid; } else { high = mid; }
+	}
+
+	Err(BlurError::RadiusTooLarge { radius, max_radius: low })
+}
+
+/// Returns an error if `value` is NaN, infinite or negative.
+fn check_finite(parameter: &'static str, value: f32) -> Result<(), BlurError> {
+	if value.is_finite() && value >= 0.0 {
+		Ok(())
+	} else {
+		Err(BlurError::UnsupportedParameter { parameter, reason: "must be finite and not negative" })
+	}
+}
+
+/// Returns an error if `map` is not the same size as `buffer`.
+fn check_size<M, T>(parameter: &'static str, map: &ImgRef<M>, buffer: &ImgRefMut<T>) -> Result<(), BlurError> {
+	if (map.width(), map.height()) == (buffer.width(), buffer.height()) {
+		Ok(())
+	} else {
+		Err(BlurError::UnsupportedParameter { parameter, reason: "must be the same size as the buffer" })
+	}
+}
+
+/// Returns an error if `output` is not the same size as `input`.
+fn check_output_size<T, S>(parameter: &'static str, input: &ImgRef<T>, output: &ImgRefMut<S>) -> Result<(), BlurError> {
+	if (input.width(), input.height()) == (output.width(), output.height()) {
+		Ok(())
+	} else {
+		Err(BlurError::UnsupportedParameter { parameter, reason: "must be the same size as the input" })
+	}
+}
+
+/// Calls a generic function with its last const parameter, `K`, set to the
+/// number of channels in a [`ChannelMask`], which must not be empty.
+macro_rules! with_channel_count {
+	($mask:expr, $blur:ident::<$($generic:ty),*>($($arg:expr),*)) => {
+		match $mask.count() {
+			1 => $blur::<$($generic,)* 1>($($arg),*),
+			2 => $blur::<$($generic,)* 2>($($arg),*),
+			3 => $blur::<$($generic,)* 3>($($arg),*),
+			4 => $blur::<$($generic,)* 4>($($arg),*),
+			_ => unreachable!()
+		}
+	};
+}
+
+/// Blurs the `K` channels in `mask` of a buffer of 32-bit packed pixels as `C`,
+/// and leaves the others alone. `decode` and `encode` convert each channel to
+/// and from the values that are blurred.
+fn blur_masked<C: Channel, const K: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask, decode: impl Fn(u8) -> u32, encode: impl Fn(u32) -> u8) {
+	let (shifts, bits) = (mask.shifts::<K>(), mask.bits());
+	let line = lines::Stack { radius, edge_mode: EdgeMode::Renormalize };
+
+	lines::blur_lines_onto(buffer, &line, &line,
+		|pixel| Channels(shifts.map(|shift| C::from_u32(decode((pixel >> shift) as u8)))),
+		|channels, pixel| shifts.into_iter().zip(channels.0).fold(pixel & !bits, |pixel, (shift, channel)| pixel | (encode(channel.to_u32()) as u32) << shift)
+	);
+}
+
+/// Like [`blur_masked`], but in parallel.
+#[cfg(any(doc, feature = "rayon"))]
+fn par_blur_masked<C: Channel + Send + Sync, const K: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask, decode: impl Fn(u8) -> u32 + Sync, encode: impl Fn(u32) -> u8 + Sync) {
+	let (shifts, bits) = (mask.shifts::<K>(), mask.bits());
+	let line = lines::Stack { radius, edge_mode: EdgeMode::Renormalize };
+
+	lines::par_blur_lines_onto(buffer, &line, &line,
+		|pixel| Channels(shifts.map(|shift| C::from_u32(decode((pixel >> shift) as u8)))),
+		|channels, pixel| shifts.into_iter().zip(channels.0).fold(pixel & !bits, |pixel, (shift, channel)| pixel | (encode(channel.to_u32()) as u32) << shift)
+	);
+}
+
+/// Like [`blur_masked`], but with SIMD, where `Csimd` holds `LANES` of `C`.
+#[cfg(any(doc, feature = "simd"))]
+fn simd_blur_masked<C: Channel, Csimd: ChannelxN<LANES>, const LANES: usize, const K: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask, decode: impl Fn(u8) -> u32, encode: impl Fn(u32) -> u8) where LaneCount<LANES>: SupportedLaneCount {
+	let (shifts, bits) = (mask.shifts::<K>(), mask.bits());
+	let line = lines::Stack { radius, edge_mode: EdgeMode::Renormalize };
+
+	lines::simd_blur_lines_onto(buffer, &line, &line,
+		|pixels: [&u32; LANES]| Channels(shifts.map(|shift| Csimd::from_array(pixels.map(|pixel| decode((pixel >> shift) as u8))))),
+		|channels: Channels<Csimd, K>, pixels| {
+			let channels = channels.0.map(Csimd::to_array);
+			std::array::from_fn(|lane| shifts.into_iter().zip(&channels).fold(pixels[lane] & !bits, |pixel, (shift, channel)| pixel | (encode(channel[lane]) as u32) << shift))
+		},
+		|pixel| Channels(shifts.map(|shift| C::from_u32(decode((pixel >> shift) as u8)))),
+		|channels, pixel| shifts.into_iter().zip(channels.0).fold(pixel & !bits, |pixel, (shift, channel)| pixel | (encode(channel.to_u32()) as u32) << shift)
+	);
+}
+
+/// Like [`simd_blur_masked`], but in parallel.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+fn par_simd_blur_masked<C: Channel + Send + Sync, Csimd: ChannelxN<LANES> + Send + Sync, const LANES: usize, const K: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask, decode: impl Fn(u8) -> u32 + Sync, encode: impl Fn(u32) -> u8 + Sync) where LaneCount<LANES>: SupportedLaneCount {
+	let (shifts, bits) = (mask.shifts::<K>(), mask.bits());
+	let line = lines::Stack { radius, edge_mode: EdgeMode::Renormalize };
+
+	lines::par_simd_blur_lines_onto(buffer, &line, &line,
+		|pixels: [&u32; LANES]| Channels(shifts.map(|shift| Csimd::from_array(pixels.map(|pixel| decode((pixel >> shift) as u8))))),
+		|channels: Channels<Csimd, K>, pixels| {
+			let channels = channels.0.map(Csimd::to_array);
+			std::array::from_fn(|lane| shifts.into_iter().zip(&channels).fold(pixels[lane] & !bits, |pixel, (shift, channel)| pixel | (encode(channel[lane]) as u32) << shift))
+		},
+		|pixel| Channels(shifts.map(|shift| C::from_u32(decode((pixel >> shift) as u8)))),
+		|channels, pixel| shifts.into_iter().zip(channels.0).fold(pixel & !bits, |pixel, (shift, channel)| pixel | (encode(channel.to_u32()) as u32) << shift)
+	);
+}
+
+/// Returns a copy of a buffer of 32-bit packed pixels.
+fn copy_argb(buffer: &ImgRefMut<u32>) -> ImgVec<u32> {
+	ImgVec::new(buffer.as_ref().pixels().collect(), buffer.width(), buffer.height())
+}
+
+/// Sharpens the channels in `mask` of one row of 32-bit packed pixels, given
+/// the same row of a blurred copy. `decode` and `encode` convert each channel
+/// to and from the values from 0 to `channel_max` that are sharpened.
+///
+/// `threshold` is compared with the difference between the encoded bytes,
+/// before they are decoded, so for sRGB it is measured in sRGB steps even
+/// though the sharpening happens in linear light.
+#[allow(clippy::too_many_arguments)]
+fn unsharp_row(row: &mut [u32], blurred: &[u32], amount: f32, threshold: u8, mask: ChannelMask, channel_max: usize, decode: impl Fn(u8) -> u32, encode: impl Fn(u32) -> u8) {
+	let bits = mask.bits().to_be_bytes();
+
+	for (pixel, blurred) in row.iter_mut().zip(blurred) {
+		let (original, blurred) = (pixel.to_be_bytes(), blurred.to_be_bytes());
+
+		*pixel = u32::from_be_bytes(std::array::from_fn(|i| {
+			if bits[i] == 0 || original[i].abs_diff(blurred[i]) < threshold {
+				return original[i];
+			}
+
+			let (original, blurred) = (decode(original[i]) as f32, decode(blurred[i]) as f32);
+			encode((original + (original - blurred) * amount).round().clamp(0.0, channel_max as f32) as u32)
+		}));
+	}
+}
+
+/// Sharpens the channels in `mask` of a buffer of 32-bit packed pixels, given
+/// a blurred copy of it. See [`unsharp_row`].
+#[allow(clippy::too_many_arguments)]
+fn unsharp(buffer: &mut ImgRefMut<u32>, blurred: ImgRef<u32>, amount: f32, threshold: u8, mask: ChannelMask, channel_max: usize, decode: impl Fn(u8) -> u32, encode: impl Fn(u32) -> u8) {
+	for (row, blurred) in buffer.rows_mut().zip(blurred.rows()) {
+		unsharp_row(row, blurred, amount, threshold, mask, channel_max, &decode, &encode);
+	}
+}
+
+/// Like [`unsharp`], but in parallel.
+#[cfg(any(doc, feature = "rayon"))]
+#[allow(clippy::too_many_arguments)]
+fn par_unsharp(buffer: &mut ImgRefMut<u32>, blurred: ImgRef<u32>, amount: f32, threshold: u8, mask: ChannelMask, channel_max: usize, decode: impl Fn(u8) -> u32 + Sync, encode: impl Fn(u32) -> u8 + Sync) {
+	#[cfg(not(doc))]
+	use rayon::iter::{ParallelBridge, ParallelIterator};
+
+	buffer.rows_mut().zip(blurred.rows()).par_bridge().for_each(|(row, blurred)| {
+		unsharp_row(row, blurred, amount, threshold, mask, channel_max, &decode, &encode);
+	});
+}
+
+/// Returns an error if `amount` is NaN or infinite.
+fn check_amount(amount: f32) -> Result<(), BlurError> {
+	if amount.is_finite() {
+		Ok(())
+	} else {
+		Err(BlurError::UnsupportedParameter { parameter: "amount", reason: "must be finite" })
+	}
+}
 
 /// Blurs a buffer, assuming one element per pixel.
 ///
@@ -74,265 +397,4576 @@ use color::Argb;
 pub fn blur<T, B: StackBlurrable>(
 	buffer: &mut ImgRefMut<T>,
 	radius: usize,
-	mut to_blurrable: impl FnMut(&T) -> B,
-	mut to_pixel: impl FnMut(B) -> T
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
 ) {
-	use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut};
-	use imgref_iter::iter::{IterWindows, IterWindowsPtrMut};
-
-	let mut ops = VecDeque::new();
-
-	// This is needed to avoid Undefined Behavior. Writing to the rows of the
-	// must be done before constructing the columns iterators, because otherwise
-	// the writes would invalidate their borrows. However I don't want to
-	// duplicate this loop, so make it a closure.
-	let mut blur_windows = |writer: IterWindowsPtrMut<T>, reader: IterWindows<T>, mut ops: VecDeque<B>| {
-		for (write, read) in writer.zip(reader) {
-			let mut blur = StackBlur::new(read.map(&mut to_blurrable), radius, ops);
-			write.for_each(|place| unsafe { *place = to_pixel(blur.next().unwrap()) });
-			ops = blur.into_ops();
-		}
+	blur_xy(buffer, radius, radius, to_blurrable, to_pixel);
+}
 
-		ops
-	};
+/// Like [`blur`], but returns a [`BlurError`] if a radius is too large for the
+/// weights of the kernel to add up in a `usize`.
+pub fn try_blur<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	radius: usize,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) -> Result<(), BlurError> {
+	check_weight(radius, |radius| Kernel::Tent.total_weight(radius))?;
 
-	let buffer_ptr = buffer.as_mut_ptr();
-	ops = blur_windows(unsafe { buffer_ptr.iter_rows_ptr_mut() }, buffer.iter_rows(), ops);
-	blur_windows(unsafe { buffer_ptr.iter_cols_ptr_mut() }, buffer.iter_cols(), ops);
+	blur(buffer, radius, to_blurrable, to_pixel);
+	Ok(())
 }
 
-/// Blurs a buffer in parallel, assuming one element per pixel.
+/// Blurs a buffer with separate horizontal and vertical radii, assuming one
+/// element per pixel.
+///
+/// The rows are blurred by `radius_x` and the columns by `radius_y`. A radius
+/// of 0 skips that pass entirely, leaving the buffer untouched along that axis.
 ///
 /// The provided closures are used to convert from the buffer's native pixel
 /// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
-#[cfg(any(doc, feature = "rayon"))]
-pub fn par_blur<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+pub fn blur_xy<T, B: StackBlurrable>(
 	buffer: &mut ImgRefMut<T>,
-	radius: usize,
-	to_blurrable: impl Fn(&T) -> B + Sync,
-	to_pixel: impl Fn(B) -> T + Sync
+	radius_x: usize,
+	radius_y: usize,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
 ) {
-	use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut};
-	use imgref_iter::iter::{IterWindows, IterWindowsPtrMut};
-	#[cfg(not(doc))]
-	use rayon::iter::{ParallelBridge, ParallelIterator};
+	blur_with_edge_mode(buffer, radius_x, radius_y, EdgeMode::Renormalize, to_blurrable, to_pixel);
+}
 
-	let mut opses = vec![Some(VecDeque::new()); rayon::current_num_threads()];
-	let opses_ptr = unsafe { unique::Unique::new_unchecked(opses.as_mut_ptr()) };
-
-	let par_blur_windows = |writer: IterWindowsPtrMut<T>, reader: IterWindows<T>| {
-		writer.zip(reader).par_bridge().for_each(|(write, read)| {
-			let ops_ref = unsafe { &mut *opses_ptr.as_ptr().add(rayon::current_thread_index().unwrap()) };
-			let ops = ops_ref.take().unwrap();
-			let mut blur = StackBlur::new(read.map(&to_blurrable), radius, ops);
-			write.for_each(|place| unsafe { *place = to_pixel(blur.next().unwrap()) });
-			ops_ref.replace(blur.into_ops());
-		});
-	};
+/// Like [`blur_xy`], but returns a [`BlurError`] if a radius is too large for
+/// the weights of the kernel to add up in a `usize`.
+pub fn try_blur_xy<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) -> Result<(), BlurError> {
+	check_weight(radius_x, |radius| Kernel::Tent.total_weight(radius))?;
+	check_weight(radius_y, |radius| Kernel::Tent.total_weight(radius))?;
 
-	let buffer_ptr = buffer.as_mut_ptr();
-	par_blur_windows(unsafe { buffer_ptr.iter_rows_ptr_mut() }, buffer.iter_rows());
-	par_blur_windows(unsafe { buffer_ptr.iter_cols_ptr_mut() }, buffer.iter_cols());
+	blur_xy(buffer, radius_x, radius_y, to_blurrable, to_pixel);
+	Ok(())
 }
 
-/// Blurs a buffer with SIMD, assuming one element per pixel.
+/// Blurs a buffer with separate horizontal and vertical radii and a custom
+/// [`EdgeMode`], assuming one element per pixel.
+///
+/// See [`blur_xy`] for how the radii are applied, and [`EdgeMode`] for what
+/// happens at the edges of the buffer. [`blur_xy`] is the same as this function
+/// with [`EdgeMode::Renormalize`].
 ///
 /// The provided closures are used to convert from the buffer's native pixel
 /// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
-#[cfg(any(doc, feature = "simd"))]
-pub fn simd_blur<T, Bsimd: StackBlurrable, Bsingle: StackBlurrable, const LANES: usize>(
+pub fn blur_with_edge_mode<T, B: StackBlurrable>(
 	buffer: &mut ImgRefMut<T>,
-	radius: usize,
-	mut to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
-	mut to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
-	mut to_blurrable_single: impl FnMut(&T) -> Bsingle,
-	mut to_pixel_single: impl FnMut(Bsingle) -> T
-) where LaneCount<LANES>: SupportedLaneCount {
-	#[cfg(not(doc))]
-	use imgref_iter::traits::{ImgIterMut, ImgSimdIter, ImgSimdIterPtrMut};
-	#[cfg(not(doc))]
-	use imgref_iter::iter::{SimdIterWindow, SimdIterWindowPtrMut, SimdIterWindows, SimdIterWindowsPtrMut};
-
-	let mut ops_simd = VecDeque::new();
-	let mut ops_single = VecDeque::new();
-
-	let mut simd_blur_windows = |writer: SimdIterWindowsPtrMut<T, LANES>, reader: SimdIterWindows<T, LANES>, mut ops_simd: VecDeque<Bsimd>, mut ops_single: VecDeque<Bsingle>| {
-		for (write, read) in writer.zip(reader) {
-			match (write, read) {
-				(SimdIterWindowPtrMut::Simd(write), SimdIterWindow::Simd(read)) => {
-					let mut blur = StackBlur::new(read.map(&mut to_blurrable_simd), radius, ops_simd);
-					write.for_each(|place| place.into_iter().zip(to_pixel_simd(blur.next().unwrap())).for_each(|(place, pixel)| unsafe { *place = pixel }));
-					ops_simd = blur.into_ops();
-				}
-
-				(SimdIterWindowPtrMut::Single(write), SimdIterWindow::Single(read)) => {
-					let mut blur = StackBlur::new(read.map(&mut to_blurrable_single), radius, ops_single);
-					write.for_each(|place| unsafe { *place = to_pixel_single(blur.next().unwrap()) });
-					ops_single = blur.into_ops();
-				}
-
-				_ => unreachable!()
-			}
-		}
+	radius_x: usize,
+	radius_y: usize,
+	edge_mode: EdgeMode,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) {
+	lines::blur_lines(buffer, &lines::Stack { radius: radius_x, edge_mode }, &lines::Stack { radius: radius_y, edge_mode }, to_blurrable, to_pixel);
+}
 
-		(ops_simd, ops_single)
-	};
+/// Like [`blur_with_edge_mode`], but returns a [`BlurError`] if a radius is too
+/// large for the weights of the kernel to add up in a `usize`.
+pub fn try_blur_with_edge_mode<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	edge_mode: EdgeMode,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) -> Result<(), BlurError> {
+	check_weight(radius_x, |radius| Kernel::Tent.total_weight(radius))?;
+	check_weight(radius_y, |radius| Kernel::Tent.total_weight(radius))?;
 
-	let buffer_ptr = buffer.as_mut_ptr();
-	(ops_simd, ops_single) = simd_blur_windows(unsafe { buffer_ptr.simd_iter_rows_ptr_mut::<LANES>() }, buffer.simd_iter_rows::<LANES>(), ops_simd, ops_single);
-	simd_blur_windows(unsafe { buffer_ptr.simd_iter_cols_ptr_mut::<LANES>() }, buffer.simd_iter_cols::<LANES>(), ops_simd, ops_single);
+	blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, to_blurrable, to_pixel);
+	Ok(())
 }
 
-/// Blurs a buffer with SIMD in parallel, assuming one element per pixel.
+/// Blurs a buffer with fractional horizontal and vertical radii, assuming one
+/// element per pixel.
+///
+/// See [`FractionalStackBlur`] for how fractional radii are handled, and
+/// [`blur_xy`] for how the radii are applied.
 ///
 /// The provided closures are used to convert from the buffer's native pixel
 /// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
-#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
-pub fn par_simd_blur<T: Send + Sync, Bsimd: StackBlurrable + Send + Sync, Bsingle: StackBlurrable + Send + Sync, const LANES: usize>(
+pub fn blur_fractional<T, B: StackBlurrable>(
 	buffer: &mut ImgRefMut<T>,
-	radius: usize,
-	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
-	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
-	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
-	to_pixel_single: impl Fn(Bsingle) -> T + Sync
-) where LaneCount<LANES>: SupportedLaneCount {
-	#[cfg(not(doc))]
-	use imgref_iter::traits::{ImgIterMut, ImgSimdIter, ImgSimdIterPtrMut};
-	#[cfg(not(doc))]
-	use rayon::iter::{ParallelBridge, ParallelIterator};
-	#[cfg(not(doc))]
-	use imgref_iter::iter::{SimdIterWindow, SimdIterWindowPtrMut, SimdIterWindows, SimdIterWindowsPtrMut};
-
-	let mut opses_simd = vec![Some(VecDeque::new()); rayon::current_num_threads()];
-	let opses_simd_ptr = unsafe { unique::Unique::new_unchecked(opses_simd.as_mut_ptr()) };
-
-	let mut opses_single = vec![Some(VecDeque::new()); rayon::current_num_threads()];
-	let opses_single_ptr = unsafe { unique::Unique::new_unchecked(opses_single.as_mut_ptr()) };
-
-	let par_simd_blur_windows = |writer: SimdIterWindowsPtrMut<T, LANES>, reader: SimdIterWindows<T, LANES>| {
-		writer.zip(reader).par_bridge().for_each(|(write, read)| match (write, read) {
-			(SimdIterWindowPtrMut::Simd(write), SimdIterWindow::Simd(read)) => {
-				let ops_ref = unsafe { &mut *opses_simd_ptr.as_ptr().add(rayon::current_thread_index().unwrap()) };
-				let ops = ops_ref.take().unwrap();
-				let mut blur = StackBlur::new(read.map(&to_blurrable_simd), radius, ops);
-				write.for_each(|place| place.into_iter().zip(to_pixel_simd(blur.next().unwrap())).for_each(|(place, pixel)| unsafe { *place = pixel }));
-				ops_ref.replace(blur.into_ops());
-			}
+	radius_x: f32,
+	radius_y: f32,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) {
+	let rows = lines::Fractional { radius: radius_x, edge_mode: EdgeMode::Renormalize };
+	let cols = lines::Fractional { radius: radius_y, edge_mode: EdgeMode::Renormalize };
+	lines::blur_lines(buffer, &rows, &cols, to_blurrable, to_pixel);
+}
 
-			(SimdIterWindowPtrMut::Single(write), SimdIterWindow::Single(read)) => {
-				let ops_ref = unsafe { &mut *opses_single_ptr.as_ptr().add(rayon::current_thread_index().unwrap()) };
-				let ops = ops_ref.take().unwrap();
-				let mut blur = StackBlur::new(read.map(&to_blurrable_single), radius, ops);
-				write.for_each(|place| unsafe { *place = to_pixel_single(blur.next().unwrap()) });
-				ops_ref.replace(blur.into_ops());
-			}
+/// Like [`blur_fractional`], but returns a [`BlurError`] if a parameter is not
+/// supported.
+pub fn try_blur_fractional<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: f32,
+	radius_y: f32,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) -> Result<(), BlurError> {
+	check_finite("radius_x", radius_x)?;
+	check_finite("radius_y", radius_y)?;
 
-			_ => unreachable!()
-		});
-	};
+	let rows = lines::Fractional { radius: radius_x, edge_mode: EdgeMode::Renormalize };
+	let cols = lines::Fractional { radius: radius_y, edge_mode: EdgeMode::Renormalize };
+	lines::blur_lines(buffer, &rows, &cols, to_blurrable, to_pixel);
+	Ok(())
+}
+
+/// Blurs a buffer with the given [`Kernel`] shape and horizontal and vertical
+/// radii, assuming one element per pixel.
+///
+/// See [`KernelBlur`] for how the kernel shapes work, and [`blur_xy`] for how
+/// the radii are applied. With [`Kernel::Tent`] this gives the same result as
+/// [`blur_xy`], which is faster.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+pub fn blur_kernel<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) {
+	let rows = lines::Shaped { radius: radius_x, kernel };
+	let cols = lines::Shaped { radius: radius_y, kernel };
+	lines::blur_lines(buffer, &rows, &cols, to_blurrable, to_pixel);
+}
+
+/// Like [`blur_kernel`], but returns a [`BlurError`] if a radius is too large
+/// for the weights of the kernel to add up in a `usize`.
+pub fn try_blur_kernel<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) -> Result<(), BlurError> {
+	check_weight(radius_x, |radius| kernel.total_weight(radius))?;
+	check_weight(radius_y, |radius| kernel.total_weight(radius))?;
 
-	let buffer_ptr = buffer.as_mut_ptr();
-	par_simd_blur_windows(unsafe { buffer_ptr.simd_iter_rows_ptr_mut::<LANES>() }, buffer.simd_iter_rows::<LANES>());
-	par_simd_blur_windows(unsafe { buffer_ptr.simd_iter_cols_ptr_mut::<LANES>() }, buffer.simd_iter_cols::<LANES>());
+	blur_kernel(buffer, radius_x, radius_y, kernel, to_blurrable, to_pixel);
+	Ok(())
 }
 
-/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB).
+/// Blurs a buffer with a separate radius for every pixel, assuming one element
+/// per pixel.
 ///
-/// This is a version of [`blur`] with pre-filled conversion routines that
-/// provide good results for blur radii <= 4096. Larger radii may overflow.
+/// The radius of each pixel is read from the same position in `radii`, which
+/// must be the same size as `buffer`, or this function panics. Both the rows
+/// and the columns are blurred by a [`VariableStackBlur`], so each row and
+/// column sample is blurred with the radius of the pixel it lands on. Where the
+/// radius changes, the column pass mixes in neighbors that the row pass blurred
+/// with their own radii, so this is not quite what [`blur`] would give each
+/// pixel with its radius, but a pixel with a radius of 0 is still left exactly
+/// as it was. This is useful for depth of field, tilt-shift and vignette
+/// effects.
 ///
-/// Note that this function is *linear*. For sRGB, see [`blur_srgb`].
-pub fn blur_argb(buffer: &mut ImgRefMut<u32>, radius: usize) {
-	blur(buffer, radius, |i| Argb::from_u32(*i), Argb::to_u32);
+/// Unlike [`blur`], this needs the [`StackBlurrable`] type to hold sums over
+/// entire rows and columns, which take room for about `len * len / 2` times the
+/// largest pixel, where `len` is the length of the row or column. Wrapping
+/// integer types don't mind, but `f32` types lose precision badly on long
+/// lines. See [`VariableStackBlur`] for details.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+pub fn blur_variable<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	radii: ImgRef<u16>,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) {
+	lines::blur_lines_variable(buffer, &radii, to_blurrable, to_pixel);
 }
 
-/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB).
+/// Like [`blur_variable`], but returns a [`BlurError`] if a parameter is not
+/// supported.
+pub fn try_blur_variable<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	radii: ImgRef<u16>,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) -> Result<(), BlurError> {
+	check_size("radii", &radii, buffer)?;
+
+	lines::blur_lines_variable(buffer, &radii, to_blurrable, to_pixel);
+	Ok(())
+}
+
+/// Blurs a buffer with a separate weight for every pixel, assuming one element
+/// per pixel.
 ///
-/// This is a version of [`blur`] with pre-filled conversion routines that
-/// provide good results for blur radii <= 1536. Larger radii may overflow.
+/// The weight of each pixel is read from the same position in `weights`, which
+/// must be the same size as `buffer`, or this function panics. Each output
+/// pixel is the average of the pixels around it, weighted by both the
+/// [`Kernel`], the same way as in [`blur_kernel`], and their weights, so pixels
+/// with a weight of 0 are ignored and filled in from their neighbors. Pixels
+/// with no weight anywhere within the radii become the default value of the
+/// [`StackBlurrable`] type. See [`WeightedBlur`] for more details.
 ///
-/// Note that this function uses *sRGB*. For linear, see [`blur_argb`].
-#[cfg(any(doc, feature = "blend-srgb"))]
-pub fn blur_srgb(buffer: &mut ImgRefMut<u32>, radius: usize) {
-	blur(buffer, radius, |i| Argb::from_u32_srgb(*i), Argb::to_u32_srgb);
+/// The rows are blurred first, and the sums of the weights that come out of
+/// them are used as the weights of the columns, so the result is the same as a
+/// true 2D normalized convolution, apart from rounding in between. This means
+/// that the [`StackBlurrable`] type needs room for the largest pixel times 255
+/// times the [`total_weight`](Kernel::total_weight) of the kernel with each of
+/// the radii, which is `(radius_x + 1)²` times `(radius_y + 1)²` for
+/// [`Kernel::Tent`].
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+pub fn blur_weighted<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	weights: ImgRef<u8>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) {
+	lines::blur_lines_weighted(buffer, &weights, radius_x, radius_y, kernel, to_blurrable, to_pixel);
 }
 
-/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) in parallel.
+/// Like [`blur_weighted`], but returns a [`BlurError`] if a parameter is not
+/// supported.
+pub fn try_blur_weighted<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	weights: ImgRef<u8>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) -> Result<(), BlurError> {
+	check_size("weights", &weights, buffer)?;
+
+	lines::blur_lines_weighted(buffer, &weights, radius_x, radius_y, kernel, to_blurrable, to_pixel);
+	Ok(())
+}
+
+/// Returns the radius at which `passes` rounds of [`FractionalStackBlur`] add
+/// up to the same variance as a Gaussian blur with the standard deviation
+/// `sigma`.
 ///
-/// This is a version of [`par_blur`] with pre-filled conversion routines that
-/// provide good results for blur radii <= 4096. Larger radii may overflow.
+/// A [`StackBlur`] with the radius `r` has a variance of `(r * r + 2 * r) / 6`,
+/// and [`FractionalStackBlur`] blends between the variances of its two integer
+/// radii, so the variance can be matched exactly. Repeating the blur makes the
+/// shape of the kernel converge towards a Gaussian. For `sigma >= 2`, the
+/// result of blurring a line differs from a true Gaussian blur (integrated over
+/// each pixel) by at most this fraction of the range of the input:
 ///
-/// Note that this function is *linear*. For sRGB, see [`par_blur_srgb`].
-#[cfg(any(doc, feature = "rayon"))]
-pub fn par_blur_argb(buffer: &mut ImgRefMut<u32>, radius: usize) {
-	par_blur(buffer, radius, |i| Argb::from_u32(*i), Argb::to_u32);
+/// | `passes` | Maximum error |
+/// |----------|---------------|
+/// | 1        | 5.7%          |
+/// | 2        | 2.0%          |
+/// | 3        | 1.3%          |
+/// | 4        | 0.9%          |
+///
+/// These bounds apply to each axis separately, and do not include rounding
+/// done by the [`StackBlurrable`] type. Below `sigma = 2`, each pass gets such
+/// a small radius that adding more passes stops helping.
+///
+/// A `passes` of 0 is treated as 1, and a negative `sigma` as 0.
+pub fn sigma_to_radius(sigma: f32, passes: usize) -> f32 {
+	let sigma = sigma.max(0.0);
+	let variance = sigma * sigma / passes.max(1) as f32;
+	let variance_of = |radius: f32| (radius * radius + radius * 2.0) / 6.0;
+
+	let mut whole = ((variance * 6.0 + 1.0).sqrt() - 1.0).floor().max(0.0);
+
+	// Make up for any rounding error in the square root.
+	if variance_of(whole) > variance {
+		whole -= 1.0;
+	} else if variance_of(whole + 1.0) <= variance {
+		whole += 1.0;
+	}
+
+	// Past 2^24, `whole + 1.0` rounds back down to `whole` and this is NaN.
+	let fraction = (variance - variance_of(whole)) / (variance_of(whole + 1.0) - variance_of(whole));
+	if fraction.is_finite() { whole + fraction } else { whole }
 }
 
-/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) in parallel.
+/// Blurs a buffer with an approximation of a Gaussian blur with the standard
+/// deviations `sigma_x` and `sigma_y`, assuming one element per pixel.
 ///
-/// This is a version of [`par_blur`] with pre-filled conversion routines that
-/// provide good results for blur radii <= 1536. Larger radii may overflow.
+/// Each axis is blurred `passes` times by a [`FractionalStackBlur`] with the
+/// radius returned by [`sigma_to_radius`]. See that function for how closely
+/// this approximates a true Gaussian blur. A `sigma` of 0 or a `passes` of 0
+/// skips that axis entirely.
 ///
-/// Note that this function uses *sRGB*. For linear, see [`par_blur_argb`].
-#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
-pub fn par_blur_srgb(buffer: &mut ImgRefMut<u32>, radius: usize) {
-	par_blur(buffer, radius, |i| Argb::from_u32_srgb(*i), Argb::to_u32_srgb);
+/// The passes over each line are done back-to-back before it is converted back
+/// to pixels, so an extra pass costs about as much as an extra call to
+/// [`blur_fractional`], but the line is only converted to and from the pixel
+/// format once rather than once per pass. Any rounding done by the
+/// [`StackBlurrable`] type itself still happens on every pass.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+pub fn blur_gaussian<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	sigma_x: f32,
+	sigma_y: f32,
+	passes: usize,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) {
+	let rows = lines::Gaussian { radius: sigma_to_radius(sigma_x, passes), passes };
+	let cols = lines::Gaussian { radius: sigma_to_radius(sigma_y, passes), passes };
+	lines::blur_lines(buffer, &rows, &cols, to_blurrable, to_pixel);
 }
 
-/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD.
+/// Like [`blur_gaussian`], but returns a [`BlurError`] if a parameter is not
+/// supported.
+pub fn try_blur_gaussian<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	sigma_x: f32,
+	sigma_y: f32,
+	passes: usize,
+	to_blurrable: impl FnMut(&T) -> B,
+	to_pixel: impl FnMut(B) -> T
+) -> Result<(), BlurError> {
+	check_finite("sigma_x", sigma_x)?;
+	check_finite("sigma_y", sigma_y)?;
+
+	let rows = lines::Gaussian { radius: sigma_to_radius(sigma_x, passes), passes };
+	let cols = lines::Gaussian { radius: sigma_to_radius(sigma_y, passes), passes };
+	lines::blur_lines(buffer, &rows, &cols, to_blurrable, to_pixel);
+	Ok(())
+}
+
+/// Dilates a buffer, assuming one element per pixel.
 ///
-/// This is a version of [`simd_blur`] with pre-filled conversion routines that
-/// provide good results for blur radii <= 4096. Larger radii may overflow.
+/// Every pixel becomes the largest value within the square of
+/// `radius * 2 + 1` by `radius * 2 + 1` pixels around it, which grows the
+/// bright parts of the image by `radius` pixels. The rows and then the columns
+/// are passed through a [`Dilate`](iter::Dilate), so the square is clipped to
+/// the edges of the buffer.
 ///
-/// Note that this function is *linear*. For sRGB, see [`simd_blur_srgb`].
-#[cfg(any(doc, feature = "simd"))]
-pub fn simd_blur_argb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
-	simd_blur(buffer, radius,
-		|i: [&u32; LANES]| Argb::from_u32xN(i.map(u32::clone)), Argb::to_u32xN,
-		|i| Argb::from_u32(*i), Argb::to_u32
-	);
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to values that can be compared, and back.
+pub fn dilate<T, V: PartialOrd + Clone>(
+	buffer: &mut ImgRefMut<T>,
+	radius: usize,
+	to_value: impl FnMut(&T) -> V,
+	to_pixel: impl FnMut(V) -> T
+) {
+	lines::morph_lines::<T, V, true>(buffer, radius, to_value, to_pixel);
 }
 
-/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD.
+/// Erodes a buffer, assuming one element per pixel.
 ///
-/// This is a version of [`simd_blur`] with pre-filled conversion routines that
-/// provide good results for blur radii <= 1536. Larger radii may overflow.
+/// Every pixel becomes the smallest value within the square of
+/// `radius * 2 + 1` by `radius * 2 + 1` pixels around it, which shrinks the
+/// bright parts of the image by `radius` pixels. See [`dilate`] for how the
+/// square is applied.
 ///
-/// Note that this function uses *sRGB*. For linear, see [`simd_blur_argb`].
-#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
-pub fn simd_blur_srgb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
-	simd_blur(buffer, radius,
-		|i: [&u32; LANES]| Argb::from_u32xN_srgb(i.map(u32::clone)), Argb::to_u32xN_srgb,
-		|i| Argb::from_u32_srgb(*i), Argb::to_u32_srgb
-	);
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to values that can be compared, and back.
+pub fn erode<T, V: PartialOrd + Clone>(
+	buffer: &mut ImgRefMut<T>,
+	radius: usize,
+	to_value: impl FnMut(&T) -> V,
+	to_pixel: impl FnMut(V) -> T
+) {
+	lines::morph_lines::<T, V, false>(buffer, radius, to_value, to_pixel);
 }
 
-/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD in
-/// parallel.
+/// Blurs an image by several radii at once, and writes the blur by each radius
+/// to the output at the same index, assuming one element per pixel.
 ///
-/// This is a version of [`par_simd_blur`] with pre-filled conversion routines
-/// that provide good results for blur radii <= 4096. Larger radii may overflow.
+/// Every output must be the same size as `input`, or this function panics. Each
+/// output matches what [`blur`] would leave in a copy of `input` with its
+/// radius up to rounding, because the rows stay [`StackBlurrable`] values here
+/// instead of being converted back to the pixel format before the columns are
+/// blurred. The rows are blurred by all of the radii in a single scan of the
+/// input, with a [`MultiStackBlur`](iter::MultiStackBlur). This needs room for
+/// a copy of the image in the [`StackBlurrable`] type for every radius.
 ///
-/// Note that this function is *linear*. For sRGB, see [`par_simd_blur_srgb`].
-#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
-pub fn par_simd_blur_argb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
-	par_simd_blur(buffer, radius,
-		|i: [&u32; LANES]| Argb::from_u32xN(i.map(u32::clone)), Argb::to_u32xN,
-		|i| Argb::from_u32(*i), Argb::to_u32
-	);
+/// The provided closures are used to convert from the input's native pixel
+/// format to [`StackBlurrable`] values, and from those to the outputs' pixel
+/// format.
+pub fn blur_multi<T, S, B: StackBlurrable, const N: usize>(
+	input: ImgRef<T>,
+	outputs: [&mut ImgRefMut<S>; N],
+	radii: [usize; N],
+	to_blurrable: impl FnMut(&T) -> B,
+	to_output: impl FnMut(B) -> S
+) {
+	lines::blur_lines_multi(&input, outputs, radii, to_blurrable, to_output);
 }
 
-/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD in
-/// parallel.
+/// Like [`blur_multi`], but returns a [`BlurError`] if a parameter is not
+/// supported.
+pub fn try_blur_multi<T, S, B: StackBlurrable, const N: usize>(
+	input: ImgRef<T>,
+	outputs: [&mut ImgRefMut<S>; N],
+	radii: [usize; N],
+	to_blurrable: impl FnMut(&T) -> B,
+	to_output: impl FnMut(B) -> S
+) -> Result<(), BlurError> {
+	for output in &outputs {
+		check_output_size("outputs", &input, output)?;
+	}
+
+	blur_multi(input, outputs, radii, to_blurrable, to_output);
+	Ok(())
+}
+
+/// Blurs a buffer in parallel, assuming one element per pixel.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radius: usize,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) {
+	par_blur_xy(buffer, radius, radius, to_blurrable, to_pixel);
+}
+
+/// Like [`par_blur`], but returns a [`BlurError`] if a radius is too large for
+/// the weights of the kernel to add up in a `usize`.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radius: usize,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) -> Result<(), BlurError> {
+	check_weight(radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	par_blur(buffer, radius, to_blurrable, to_pixel);
+	Ok(())
+}
+
+/// Blurs a buffer in parallel with separate horizontal and vertical radii,
+/// assuming one element per pixel.
 ///
-/// This is a version of [`par_simd_blur`] with pre-filled conversion routines
-/// that provide good results for blur radii <= 1536. Larger radii may overflow.
+/// See [`blur_xy`] for how the radii are applied.
 ///
-/// Note that this function uses *sRGB*. For linear, see [`par_simd_blur_argb`].
-#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
-pub fn par_simd_blur_srgb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
-	par_simd_blur(buffer, radius,
-		|i: [&u32; LANES]| Argb::from_u32xN_srgb(i.map(u32::clone)), Argb::to_u32xN_srgb,
-		|i| Argb::from_u32_srgb(*i), Argb::to_u32_srgb
-	);
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_xy<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) {
+	par_blur_with_edge_mode(buffer, radius_x, radius_y, EdgeMode::Renormalize, to_blurrable, to_pixel);
+}
+
+/// Like [`par_blur_xy`], but returns a [`BlurError`] if a radius is too large
+/// for the weights of the kernel to add up in a `usize`.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_xy<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) -> Result<(), BlurError> {
+	check_weight(radius_x, |radius| Kernel::Tent.total_weight(radius))?;
+	check_weight(radius_y, |radius| Kernel::Tent.total_weight(radius))?;
+
+	par_blur_xy(buffer, radius_x, radius_y, to_blurrable, to_pixel);
+	Ok(())
+}
+
+/// Blurs a buffer in parallel with separate horizontal and vertical radii and a
+/// custom [`EdgeMode`], assuming one element per pixel.
+///
+/// See [`blur_with_edge_mode`] for how the parameters are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_with_edge_mode<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	edge_mode: EdgeMode,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) {
+	lines::par_blur_lines(buffer, &lines::Stack { radius: radius_x, edge_mode }, &lines::Stack { radius: radius_y, edge_mode }, to_blurrable, to_pixel);
+}
+
+/// Like [`par_blur_with_edge_mode`], but returns a [`BlurError`] if a radius is
+/// too large for the weights of the kernel to add up in a `usize`.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_with_edge_mode<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	edge_mode: EdgeMode,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) -> Result<(), BlurError> {
+	check_weight(radius_x, |radius| Kernel::Tent.total_weight(radius))?;
+	check_weight(radius_y, |radius| Kernel::Tent.total_weight(radius))?;
+
+	par_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, to_blurrable, to_pixel);
+	Ok(())
+}
+
+/// Blurs a buffer in parallel with fractional horizontal and vertical radii,
+/// assuming one element per pixel.
+///
+/// See [`blur_fractional`] for how the radii are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_fractional<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: f32,
+	radius_y: f32,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) {
+	let rows = lines::Fractional { radius: radius_x, edge_mode: EdgeMode::Renormalize };
+	let cols = lines::Fractional { radius: radius_y, edge_mode: EdgeMode::Renormalize };
+	lines::par_blur_lines(buffer, &rows, &cols, to_blurrable, to_pixel);
+}
+
+/// Like [`par_blur_fractional`], but returns a [`BlurError`] if a parameter is
+/// not supported.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_fractional<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: f32,
+	radius_y: f32,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) -> Result<(), BlurError> {
+	check_finite("radius_x", radius_x)?;
+	check_finite("radius_y", radius_y)?;
+
+	let rows = lines::Fractional { radius: radius_x, edge_mode: EdgeMode::Renormalize };
+	let cols = lines::Fractional { radius: radius_y, edge_mode: EdgeMode::Renormalize };
+	lines::par_blur_lines(buffer, &rows, &cols, to_blurrable, to_pixel);
+	Ok(())
+}
+
+/// Blurs a buffer in parallel with the given [`Kernel`] shape and horizontal
+/// and vertical radii, assuming one element per pixel.
+///
+/// See [`blur_kernel`] for how the parameters are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_kernel<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) {
+	let rows = lines::Shaped { radius: radius_x, kernel };
+	let cols = lines::Shaped { radius: radius_y, kernel };
+	lines::par_blur_lines(buffer, &rows, &cols, to_blurrable, to_pixel);
+}
+
+/// Like [`par_blur_kernel`], but returns a [`BlurError`] if a radius is too
+/// large for the weights of the kernel to add up in a `usize`.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_kernel<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) -> Result<(), BlurError> {
+	check_weight(radius_x, |radius| kernel.total_weight(radius))?;
+	check_weight(radius_y, |radius| kernel.total_weight(radius))?;
+
+	par_blur_kernel(buffer, radius_x, radius_y, kernel, to_blurrable, to_pixel);
+	Ok(())
+}
+
+/// Blurs a buffer in parallel with a separate radius for every pixel, assuming
+/// one element per pixel.
+///
+/// See [`blur_variable`] for how the radius map is applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_variable<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radii: ImgRef<u16>,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) {
+	lines::par_blur_lines_variable(buffer, &radii, to_blurrable, to_pixel);
+}
+
+/// Like [`par_blur_variable`], but returns a [`BlurError`] if a parameter is
+/// not supported.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_variable<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radii: ImgRef<u16>,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) -> Result<(), BlurError> {
+	check_size("radii", &radii, buffer)?;
+
+	lines::par_blur_lines_variable(buffer, &radii, to_blurrable, to_pixel);
+	Ok(())
+}
+
+/// Blurs a buffer in parallel with a separate weight for every pixel, assuming
+/// one element per pixel.
+///
+/// See [`blur_weighted`] for how the weights are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_weighted<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	weights: ImgRef<u8>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) {
+	lines::par_blur_lines_weighted(buffer, &weights, radius_x, radius_y, kernel, to_blurrable, to_pixel);
+}
+
+/// Like [`par_blur_weighted`], but returns a [`BlurError`] if a parameter is
+/// not supported.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_weighted<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	weights: ImgRef<u8>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) -> Result<(), BlurError> {
+	check_size("weights", &weights, buffer)?;
+
+	lines::par_blur_lines_weighted(buffer, &weights, radius_x, radius_y, kernel, to_blurrable, to_pixel);
+	Ok(())
+}
+
+/// Blurs a buffer in parallel with an approximation of a Gaussian blur with the
+/// standard deviations `sigma_x` and `sigma_y`, assuming one element per pixel.
+///
+/// See [`blur_gaussian`] for how the parameters are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_gaussian<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	sigma_x: f32,
+	sigma_y: f32,
+	passes: usize,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) {
+	let rows = lines::Gaussian { radius: sigma_to_radius(sigma_x, passes), passes };
+	let cols = lines::Gaussian { radius: sigma_to_radius(sigma_y, passes), passes };
+	lines::par_blur_lines(buffer, &rows, &cols, to_blurrable, to_pixel);
+}
+
+/// Like [`par_blur_gaussian`], but returns a [`BlurError`] if a parameter is
+/// not supported.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_gaussian<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	sigma_x: f32,
+	sigma_y: f32,
+	passes: usize,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) -> Result<(), BlurError> {
+	check_finite("sigma_x", sigma_x)?;
+	check_finite("sigma_y", sigma_y)?;
+
+	let rows = lines::Gaussian { radius: sigma_to_radius(sigma_x, passes), passes };
+	let cols = lines::Gaussian { radius: sigma_to_radius(sigma_y, passes), passes };
+	lines::par_blur_lines(buffer, &rows, &cols, to_blurrable, to_pixel);
+	Ok(())
+}
+
+/// Dilates a buffer in parallel, assuming one element per pixel.
+///
+/// See [`dilate`] for how the radius is applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to values that can be compared, and back.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_dilate<T: Send + Sync, V: PartialOrd + Clone + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radius: usize,
+	to_value: impl Fn(&T) -> V + Sync,
+	to_pixel: impl Fn(V) -> T + Sync
+) {
+	lines::par_morph_lines::<T, V, true>(buffer, radius, to_value, to_pixel);
+}
+
+/// Erodes a buffer in parallel, assuming one element per pixel.
+///
+/// See [`erode`] for how the radius is applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to values that can be compared, and back.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_erode<T: Send + Sync, V: PartialOrd + Clone + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radius: usize,
+	to_value: impl Fn(&T) -> V + Sync,
+	to_pixel: impl Fn(V) -> T + Sync
+) {
+	lines::par_morph_lines::<T, V, false>(buffer, radius, to_value, to_pixel);
+}
+
+/// Blurs an image by several radii at once in parallel, and writes the blur by
+/// each radius to the output at the same index, assuming one element per pixel.
+///
+/// See [`blur_multi`] for how the parameters are applied.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_multi<T: Sync, S: Send, B: StackBlurrable + Send + Sync, const N: usize>(
+	input: ImgRef<T>,
+	outputs: [&mut ImgRefMut<S>; N],
+	radii: [usize; N],
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_output: impl Fn(B) -> S + Sync
+) {
+	lines::par_blur_lines_multi(&input, outputs, radii, to_blurrable, to_output);
+}
+
+/// Like [`par_blur_multi`], but returns a [`BlurError`] if a parameter is not
+/// supported.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_multi<T: Sync, S: Send, B: StackBlurrable + Send + Sync, const N: usize>(
+	input: ImgRef<T>,
+	outputs: [&mut ImgRefMut<S>; N],
+	radii: [usize; N],
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_output: impl Fn(B) -> S + Sync
+) -> Result<(), BlurError> {
+	for output in &outputs {
+		check_output_size("outputs", &input, output)?;
+	}
+
+	lines::par_blur_lines_multi(&input, outputs, radii, to_blurrable, to_output);
+	Ok(())
+}
+
+/// Blurs a buffer with SIMD, assuming one element per pixel.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur<T, Bsimd: StackBlurrable, Bsingle: StackBlurrable, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius: usize,
+	to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
+	to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	to_pixel_single: impl FnMut(Bsingle) -> T
+) where LaneCount<LANES>: SupportedLaneCount {
+	simd_blur_xy(buffer, radius, radius, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+}
+
+/// Like [`simd_blur`], but returns a [`BlurError`] if a radius is too large for
+/// the weights of the kernel to add up in a `usize`.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur<T, Bsimd: StackBlurrable, Bsingle: StackBlurrable, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius: usize,
+	to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
+	to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	to_pixel_single: impl FnMut(Bsingle) -> T
+) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_weight(radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	simd_blur(buffer, radius, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+	Ok(())
+}
+
+/// Blurs a buffer with SIMD with separate horizontal and vertical radii,
+/// assuming one element per pixel.
+///
+/// See [`blur_xy`] for how the radii are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_xy<T, Bsimd: StackBlurrable, Bsingle: StackBlurrable, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
+	to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	to_pixel_single: impl FnMut(Bsingle) -> T
+) where LaneCount<LANES>: SupportedLaneCount {
+	simd_blur_with_edge_mode(buffer, radius_x, radius_y, EdgeMode::Renormalize, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+}
+
+/// Like [`simd_blur_xy`], but returns a [`BlurError`] if a radius is too large
+/// for the weights of the kernel to add up in a `usize`.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur_xy<T, Bsimd: StackBlurrable, Bsingle: StackBlurrable, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
+	to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	to_pixel_single: impl FnMut(Bsingle) -> T
+) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_weight(radius_x, |radius| Kernel::Tent.total_weight(radius))?;
+	check_weight(radius_y, |radius| Kernel::Tent.total_weight(radius))?;
+
+	simd_blur_xy(buffer, radius_x, radius_y, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+	Ok(())
+}
+
+/// Blurs a buffer with SIMD with separate horizontal and vertical radii and a
+/// custom [`EdgeMode`], assuming one element per pixel.
+///
+/// See [`blur_with_edge_mode`] for how the parameters are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "simd"))]
+#[allow(clippy::too_many_arguments)]
+pub fn simd_blur_with_edge_mode<T, Bsimd: StackBlurrable, Bsingle: StackBlurrable, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	edge_mode: EdgeMode,
+	to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
+	to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	to_pixel_single: impl FnMut(Bsingle) -> T
+) where LaneCount<LANES>: SupportedLaneCount {
+	lines::simd_blur_lines(buffer, &lines::Stack { radius: radius_x, edge_mode }, &lines::Stack { radius: radius_y, edge_mode }, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+}
+
+/// Like [`simd_blur_with_edge_mode`], but returns a [`BlurError`] if a radius
+/// is too large for the weights of the kernel to add up in a `usize`.
+#[cfg(any(doc, feature = "simd"))]
+#[allow(clippy::too_many_arguments)]
+pub fn try_simd_blur_with_edge_mode<T, Bsimd: StackBlurrable, Bsingle: StackBlurrable, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	edge_mode: EdgeMode,
+	to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
+	to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	to_pixel_single: impl FnMut(Bsingle) -> T
+) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_weight(radius_x, |radius| Kernel::Tent.total_weight(radius))?;
+	check_weight(radius_y, |radius| Kernel::Tent.total_weight(radius))?;
+
+	simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+	Ok(())
+}
+
+/// Blurs a buffer with SIMD with fractional horizontal and vertical radii,
+/// assuming one element per pixel.
+///
+/// See [`blur_fractional`] for how the radii are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_fractional<T, Bsimd: StackBlurrable, Bsingle: StackBlurrable, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: f32,
+	radius_y: f32,
+	to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
+	to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	to_pixel_single: impl FnMut(Bsingle) -> T
+) where LaneCount<LANES>: SupportedLaneCount {
+	let rows = lines::Fractional { radius: radius_x, edge_mode: EdgeMode::Renormalize };
+	let cols = lines::Fractional { radius: radius_y, edge_mode: EdgeMode::Renormalize };
+	lines::simd_blur_lines(buffer, &rows, &cols, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+}
+
+/// Like [`simd_blur_fractional`], but returns a [`BlurError`] if a parameter is
+/// not supported.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur_fractional<T, Bsimd: StackBlurrable, Bsingle: StackBlurrable, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: f32,
+	radius_y: f32,
+	to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
+	to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	to_pixel_single: impl FnMut(Bsingle) -> T
+) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_finite("radius_x", radius_x)?;
+	check_finite("radius_y", radius_y)?;
+
+	let rows = lines::Fractional { radius: radius_x, edge_mode: EdgeMode::Renormalize };
+	let cols = lines::Fractional { radius: radius_y, edge_mode: EdgeMode::Renormalize };
+	lines::simd_blur_lines(buffer, &rows, &cols, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+	Ok(())
+}
+
+/// Blurs a buffer with SIMD with the given [`Kernel`] shape and horizontal and
+/// vertical radii, assuming one element per pixel.
+///
+/// See [`blur_kernel`] for how the parameters are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "simd"))]
+#[allow(clippy::too_many_arguments)]
+pub fn simd_blur_kernel<T, Bsimd: StackBlurrable, Bsingle: StackBlurrable, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
+	to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	to_pixel_single: impl FnMut(Bsingle) -> T
+) where LaneCount<LANES>: SupportedLaneCount {
+	let rows = lines::Shaped { radius: radius_x, kernel };
+	let cols = lines::Shaped { radius: radius_y, kernel };
+	lines::simd_blur_lines(buffer, &rows, &cols, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+}
+
+/// Like [`simd_blur_kernel`], but returns a [`BlurError`] if a radius is too
+/// large for the weights of the kernel to add up in a `usize`.
+#[cfg(any(doc, feature = "simd"))]
+#[allow(clippy::too_many_arguments)]
+pub fn try_simd_blur_kernel<T, Bsimd: StackBlurrable, Bsingle: StackBlurrable, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
+	to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	to_pixel_single: impl FnMut(Bsingle) -> T
+) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_weight(radius_x, |radius| kernel.total_weight(radius))?;
+	check_weight(radius_y, |radius| kernel.total_weight(radius))?;
+
+	simd_blur_kernel(buffer, radius_x, radius_y, kernel, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+	Ok(())
+}
+
+/// Blurs a buffer with SIMD with an approximation of a Gaussian blur with the
+/// standard deviations `sigma_x` and `sigma_y`, assuming one element per pixel.
+///
+/// See [`blur_gaussian`] for how the parameters are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, feature = "simd"))]
+#[allow(clippy::too_many_arguments)]
+pub fn simd_blur_gaussian<T, Bsimd: StackBlurrable, Bsingle: StackBlurrable, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	sigma_x: f32,
+	sigma_y: f32,
+	passes: usize,
+	to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
+	to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	to_pixel_single: impl FnMut(Bsingle) -> T
+) where LaneCount<LANES>: SupportedLaneCount {
+	let rows = lines::Gaussian { radius: sigma_to_radius(sigma_x, passes), passes };
+	let cols = lines::Gaussian { radius: sigma_to_radius(sigma_y, passes), passes };
+	lines::simd_blur_lines(buffer, &rows, &cols, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+}
+
+/// Like [`simd_blur_gaussian`], but returns a [`BlurError`] if a parameter is
+/// not supported.
+#[cfg(any(doc, feature = "simd"))]
+#[allow(clippy::too_many_arguments)]
+pub fn try_simd_blur_gaussian<T, Bsimd: StackBlurrable, Bsingle: StackBlurrable, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	sigma_x: f32,
+	sigma_y: f32,
+	passes: usize,
+	to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
+	to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	to_pixel_single: impl FnMut(Bsingle) -> T
+) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_finite("sigma_x", sigma_x)?;
+	check_finite("sigma_y", sigma_y)?;
+
+	let rows = lines::Gaussian { radius: sigma_to_radius(sigma_x, passes), passes };
+	let cols = lines::Gaussian { radius: sigma_to_radius(sigma_y, passes), passes };
+	lines::simd_blur_lines(buffer, &rows, &cols, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+	Ok(())
+}
+
+/// Blurs a buffer with SIMD in parallel, assuming one element per pixel.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur<T: Send + Sync, Bsimd: StackBlurrable + Send + Sync, Bsingle: StackBlurrable + Send + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius: usize,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle) -> T + Sync
+) where LaneCount<LANES>: SupportedLaneCount {
+	par_simd_blur_xy(buffer, radius, radius, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+}
+
+/// Like [`par_simd_blur`], but returns a [`BlurError`] if a radius is too large
+/// for the weights of the kernel to add up in a `usize`.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur<T: Send + Sync, Bsimd: StackBlurrable + Send + Sync, Bsingle: StackBlurrable + Send + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius: usize,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle) -> T + Sync
+) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_weight(radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	par_simd_blur(buffer, radius, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+	Ok(())
+}
+
+/// Blurs a buffer with SIMD in parallel with separate horizontal and vertical
+/// radii, assuming one element per pixel.
+///
+/// See [`blur_xy`] for how the radii are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_xy<T: Send + Sync, Bsimd: StackBlurrable + Send + Sync, Bsingle: StackBlurrable + Send + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle) -> T + Sync
+) where LaneCount<LANES>: SupportedLaneCount {
+	par_simd_blur_with_edge_mode(buffer, radius_x, radius_y, EdgeMode::Renormalize, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+}
+
+/// Like [`par_simd_blur_xy`], but returns a [`BlurError`] if a radius is too
+/// large for the weights of the kernel to add up in a `usize`.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur_xy<T: Send + Sync, Bsimd: StackBlurrable + Send + Sync, Bsingle: StackBlurrable + Send + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle) -> T + Sync
+) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_weight(radius_x, |radius| Kernel::Tent.total_weight(radius))?;
+	check_weight(radius_y, |radius| Kernel::Tent.total_weight(radius))?;
+
+	par_simd_blur_xy(buffer, radius_x, radius_y, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+	Ok(())
+}
+
+/// Blurs a buffer with SIMD in parallel with separate horizontal and vertical
+/// radii and a custom [`EdgeMode`], assuming one element per pixel.
+///
+/// See [`blur_with_edge_mode`] for how the parameters are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+#[allow(clippy::too_many_arguments)]
+pub fn par_simd_blur_with_edge_mode<T: Send + Sync, Bsimd: StackBlurrable + Send + Sync, Bsingle: StackBlurrable + Send + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	edge_mode: EdgeMode,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle) -> T + Sync
+) where LaneCount<LANES>: SupportedLaneCount {
+	lines::par_simd_blur_lines(buffer, &lines::Stack { radius: radius_x, edge_mode }, &lines::Stack { radius: radius_y, edge_mode }, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+}
+
+/// Like [`par_simd_blur_with_edge_mode`], but returns a [`BlurError`] if a
+/// radius is too large for the weights of the kernel to add up in a `usize`.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+#[allow(clippy::too_many_arguments)]
+pub fn try_par_simd_blur_with_edge_mode<T: Send + Sync, Bsimd: StackBlurrable + Send + Sync, Bsingle: StackBlurrable + Send + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	edge_mode: EdgeMode,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle) -> T + Sync
+) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_weight(radius_x, |radius| Kernel::Tent.total_weight(radius))?;
+	check_weight(radius_y, |radius| Kernel::Tent.total_weight(radius))?;
+
+	par_simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+	Ok(())
+}
+
+/// Blurs a buffer with SIMD in parallel with fractional horizontal and vertical
+/// radii, assuming one element per pixel.
+///
+/// See [`blur_fractional`] for how the radii are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_fractional<T: Send + Sync, Bsimd: StackBlurrable + Send + Sync, Bsingle: StackBlurrable + Send + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: f32,
+	radius_y: f32,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle) -> T + Sync
+) where LaneCount<LANES>: SupportedLaneCount {
+	let rows = lines::Fractional { radius: radius_x, edge_mode: EdgeMode::Renormalize };
+	let cols = lines::Fractional { radius: radius_y, edge_mode: EdgeMode::Renormalize };
+	lines::par_simd_blur_lines(buffer, &rows, &cols, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+}
+
+/// Like [`par_simd_blur_fractional`], but returns a [`BlurError`] if a
+/// parameter is not supported.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur_fractional<T: Send + Sync, Bsimd: StackBlurrable + Send + Sync, Bsingle: StackBlurrable + Send + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: f32,
+	radius_y: f32,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle) -> T + Sync
+) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_finite("radius_x", radius_x)?;
+	check_finite("radius_y", radius_y)?;
+
+	let rows = lines::Fractional { radius: radius_x, edge_mode: EdgeMode::Renormalize };
+	let cols = lines::Fractional { radius: radius_y, edge_mode: EdgeMode::Renormalize };
+	lines::par_simd_blur_lines(buffer, &rows, &cols, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+	Ok(())
+}
+
+/// Blurs a buffer with SIMD in parallel with the given [`Kernel`] shape and
+/// horizontal and vertical radii, assuming one element per pixel.
+///
+/// See [`blur_kernel`] for how the parameters are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+#[allow(clippy::too_many_arguments)]
+pub fn par_simd_blur_kernel<T: Send + Sync, Bsimd: StackBlurrable + Send + Sync, Bsingle: StackBlurrable + Send + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle) -> T + Sync
+) where LaneCount<LANES>: SupportedLaneCount {
+	let rows = lines::Shaped { radius: radius_x, kernel };
+	let cols = lines::Shaped { radius: radius_y, kernel };
+	lines::par_simd_blur_lines(buffer, &rows, &cols, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+}
+
+/// Like [`par_simd_blur_kernel`], but returns a [`BlurError`] if a radius is
+/// too large for the weights of the kernel to add up in a `usize`.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+#[allow(clippy::too_many_arguments)]
+pub fn try_par_simd_blur_kernel<T: Send + Sync, Bsimd: StackBlurrable + Send + Sync, Bsingle: StackBlurrable + Send + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle) -> T + Sync
+) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_weight(radius_x, |radius| kernel.total_weight(radius))?;
+	check_weight(radius_y, |radius| kernel.total_weight(radius))?;
+
+	par_simd_blur_kernel(buffer, radius_x, radius_y, kernel, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+	Ok(())
+}
+
+/// Blurs a buffer with SIMD in parallel with an approximation of a Gaussian
+/// blur with the standard deviations `sigma_x` and `sigma_y`, assuming one
+/// element per pixel.
+///
+/// See [`blur_gaussian`] for how the parameters are applied.
+///
+/// The provided closures are used to convert from the buffer's native pixel
+/// format to [`StackBlurrable`] values that can be consumed by [`StackBlur`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+#[allow(clippy::too_many_arguments)]
+pub fn par_simd_blur_gaussian<T: Send + Sync, Bsimd: StackBlurrable + Send + Sync, Bsingle: StackBlurrable + Send + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	sigma_x: f32,
+	sigma_y: f32,
+	passes: usize,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle) -> T + Sync
+) where LaneCount<LANES>: SupportedLaneCount {
+	let rows = lines::Gaussian { radius: sigma_to_radius(sigma_x, passes), passes };
+	let cols = lines::Gaussian { radius: sigma_to_radius(sigma_y, passes), passes };
+	lines::par_simd_blur_lines(buffer, &rows, &cols, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+}
+
+/// Like [`par_simd_blur_gaussian`], but returns a [`BlurError`] if a parameter
+/// is not supported.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+#[allow(clippy::too_many_arguments)]
+pub fn try_par_simd_blur_gaussian<T: Send + Sync, Bsimd: StackBlurrable + Send + Sync, Bsingle: StackBlurrable + Send + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	sigma_x: f32,
+	sigma_y: f32,
+	passes: usize,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle) -> T + Sync
+) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_finite("sigma_x", sigma_x)?;
+	check_finite("sigma_y", sigma_y)?;
+
+	let rows = lines::Gaussian { radius: sigma_to_radius(sigma_x, passes), passes };
+	let cols = lines::Gaussian { radius: sigma_to_radius(sigma_y, passes), passes };
+	lines::par_simd_blur_lines(buffer, &rows, &cols, to_blurrable_simd, to_pixel_simd, to_blurrable_single, to_pixel_single);
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB).
+///
+/// This is a version of [`blur`] with pre-filled conversion routines. They use
+/// 32 bits per channel, or 64 bits when the radius is too large for that, which
+/// for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see [`blur_srgb`].
+pub fn blur_argb(buffer: &mut ImgRefMut<u32>, radius: usize) {
+	blur_argb_xy(buffer, radius, radius);
+}
+
+/// Like [`blur_argb`], but returns a [`BlurError`] if the radius is too large.
+pub fn try_blur_argb(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> {
+	try_blur_argb_xy(buffer, radius, radius)
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with premultiplied
+/// alpha.
+///
+/// This is a version of [`blur`] with pre-filled conversion routines. They use
+/// 32 bits per channel, or 64 bits when the radius is too large for that, which
+/// for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Premultiplied pixels can be blurred one channel at a time without any
+/// special treatment, so this does exactly the same thing as [`blur_argb`], and
+/// the output is premultiplied as well. For pixels with straight alpha, see
+/// [`blur_argb_straight`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`blur_srgb_premultiplied`].
+pub fn blur_argb_premultiplied(buffer: &mut ImgRefMut<u32>, radius: usize) {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		blur(buffer, radius, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		blur(buffer, radius, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`blur_argb_premultiplied`], but returns a [`BlurError`] if the radius
+/// is too large.
+pub fn try_blur_argb_premultiplied(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> {
+	check_radius(ARGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		blur(buffer, radius, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		blur(buffer, radius, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with straight (not
+/// premultiplied) alpha.
+///
+/// This is a version of [`blur`] with pre-filled conversion routines. They use
+/// 32 bits per channel, or 64 bits when the radius is too large for that, which
+/// for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Blurring straight alpha one channel at a time lets the color of transparent
+/// pixels, which is usually meaningless, bleed into the visible ones as dark or
+/// colored halos. This premultiplies every pixel before it is blurred and
+/// converts it back to straight alpha afterwards, which prevents that. Pixels
+/// that come out fully transparent are black.
+///
+/// Note that this function is *linear*. For sRGB, see [`blur_srgb_straight`].
+pub fn blur_argb_straight(buffer: &mut ImgRefMut<u32>, radius: usize) {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		blur(buffer, radius, |i| ArgbU32::from_u32_straight(*i), ArgbU32::to_u32_straight);
+	} else {
+		blur(buffer, radius, |i| ArgbU64::from_u32_straight(*i), ArgbU64::to_u32_straight);
+	}
+}
+
+/// Like [`blur_argb_straight`], but returns a [`BlurError`] if the radius is
+/// too large.
+pub fn try_blur_argb_straight(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> {
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		blur(buffer, radius, |i| ArgbU32::from_u32_straight(*i), ArgbU32::to_u32_straight);
+	} else {
+		blur(buffer, radius, |i| ArgbU64::from_u32_straight(*i), ArgbU64::to_u32_straight);
+	}
+
+	Ok(())
+}
+
+/// Blurs only the channels in `mask` of a buffer of 32-bit packed ARGB pixels
+/// (0xAARRGGBB), and leaves the others exactly as they were.
+///
+/// This is a version of [`blur_argb`] that only does the work for the channels
+/// that it blurs, so blurring just [`ChannelMask::ALPHA`], for example to make
+/// a shadow, takes about a quarter of the arithmetic. Like [`blur_argb`], it
+/// uses 32 bits per channel, or 64 bits when the radius is larger than
+/// [`ARGB_U32_MAX_RADIUS`]. An empty mask leaves the buffer untouched.
+///
+/// Note that this function is *linear*. For sRGB, see [`blur_srgb_masked`].
+pub fn blur_argb_masked(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) {
+	if mask.is_empty() {
+		return;
+	}
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, blur_masked::<StackBlurrableU32>(buffer, radius, mask, u32::from, |i| i as u8));
+	} else {
+		with_channel_count!(mask, blur_masked::<StackBlurrableU64>(buffer, radius, mask, u32::from, |i| i as u8));
+	}
+}
+
+/// Like [`blur_argb_masked`], but returns a [`BlurError`] if the radius is too
+/// large.
+pub fn try_blur_argb_masked(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) -> Result<(), BlurError> {
+	check_radius(ARGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if mask.is_empty() {
+		return Ok(());
+	}
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, blur_masked::<StackBlurrableU32>(buffer, radius, mask, u32::from, |i| i as u8));
+	} else {
+		with_channel_count!(mask, blur_masked::<StackBlurrableU64>(buffer, radius, mask, u32::from, |i| i as u8));
+	}
+
+	Ok(())
+}
+
+/// Sharpens a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with an unsharp
+/// mask.
+///
+/// Each channel in `mask` becomes `original + amount * (original - blurred)`,
+/// clamped to the range of the channel, where `blurred` is the result of
+/// [`blur_argb_masked`] with the given radius. Channels that differ from their
+/// blurred value by less than `threshold` are left alone, so that flat areas
+/// and noise are not sharpened. Pass [`ChannelMask::COLOR`] to keep alpha as it
+/// is.
+///
+/// The buffer is copied and the copy is blurred, so this needs as much memory
+/// again as the buffer takes up.
+///
+/// Note that this function is *linear*. For sRGB, see [`unsharp_mask_srgb`].
+pub fn unsharp_mask_argb(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) {
+	let mut blurred = copy_argb(buffer);
+	blur_argb_masked(&mut blurred.as_mut(), radius, mask);
+	unsharp(buffer, blurred.as_ref(), amount, threshold, mask, ARGB_CHANNEL_MAX, u32::from, |i| i as u8);
+}
+
+/// Like [`unsharp_mask_argb`], but returns a [`BlurError`] if the radius is too
+/// large or `amount` is not finite. The buffer is only changed if this succeeds.
+pub fn try_unsharp_mask_argb(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) -> Result<(), BlurError> {
+	check_amount(amount)?;
+
+	let mut blurred = copy_argb(buffer);
+	try_blur_argb_masked(&mut blurred.as_mut(), radius, mask)?;
+	unsharp(buffer, blurred.as_ref(), amount, threshold, mask, ARGB_CHANNEL_MAX, u32::from, |i| i as u8);
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with separate
+/// horizontal and vertical radii.
+///
+/// This is a version of [`blur_xy`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see [`blur_srgb_xy`].
+pub fn blur_argb_xy(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		blur_xy(buffer, radius_x, radius_y, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		blur_xy(buffer, radius_x, radius_y, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`blur_argb_xy`], but returns a [`BlurError`] if the radius is too
+/// large.
+pub fn try_blur_argb_xy(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) -> Result<(), BlurError> {
+	check_radius(ARGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		blur_xy(buffer, radius_x, radius_y, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		blur_xy(buffer, radius_x, radius_y, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with separate
+/// horizontal and vertical radii and a custom [`EdgeMode`].
+///
+/// This is a version of [`blur_with_edge_mode`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`blur_srgb_with_edge_mode`].
+pub fn blur_argb_with_edge_mode(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`blur_argb_with_edge_mode`], but returns a [`BlurError`] if the radius
+/// is too large.
+pub fn try_blur_argb_with_edge_mode(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) -> Result<(), BlurError> {
+	check_radius(ARGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with fractional
+/// horizontal and vertical radii.
+///
+/// This is a version of [`blur_fractional`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see [`blur_srgb_fractional`].
+pub fn blur_argb_fractional(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) {
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		blur_fractional(buffer, radius_x, radius_y, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		blur_fractional(buffer, radius_x, radius_y, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`blur_argb_fractional`], but returns a [`BlurError`] if the radius is
+/// too large or a parameter is not supported.
+pub fn try_blur_argb_fractional(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) -> Result<(), BlurError> {
+	check_finite("radius_x", radius_x)?;
+	check_finite("radius_y", radius_y)?;
+	check_radius(ARGB_CHANNEL_MAX, upper_radius(radius_x.max(radius_y)), fractional_weight)?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		try_blur_fractional(buffer, radius_x, radius_y, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32)
+	} else {
+		try_blur_fractional(buffer, radius_x, radius_y, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with the given
+/// [`Kernel`] shape and horizontal and vertical radii.
+///
+/// This is a version of [`blur_kernel`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see [`blur_srgb_kernel`].
+pub fn blur_argb_kernel(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) {
+	if fits_u32(ARGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`blur_argb_kernel`], but returns a [`BlurError`] if the radius is too
+/// large.
+pub fn try_blur_argb_kernel(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) -> Result<(), BlurError> {
+	check_radius(ARGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| kernel.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with a separate
+/// radius for every pixel.
+///
+/// This is a version of [`blur_variable`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see [`blur_srgb_variable`].
+pub fn blur_argb_variable(buffer: &mut ImgRefMut<u32>, radii: ImgRef<u16>) {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radii.pixels().max().unwrap_or(0) as usize)) {
+		blur_variable(buffer, radii, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		blur_variable(buffer, radii, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`blur_argb_variable`], but returns a [`BlurError`] if a parameter is
+/// not supported.
+pub fn try_blur_argb_variable(buffer: &mut ImgRefMut<u32>, radii: ImgRef<u16>) -> Result<(), BlurError> {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radii.pixels().max().unwrap_or(0) as usize)) {
+		try_blur_variable(buffer, radii, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32)
+	} else {
+		try_blur_variable(buffer, radii, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with an
+/// approximation of a Gaussian blur.
+///
+/// This is a version of [`blur_gaussian`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see [`blur_srgb_gaussian`].
+pub fn blur_argb_gaussian(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) {
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`blur_argb_gaussian`], but returns a [`BlurError`] if the radius is
+/// too large or a parameter is not supported.
+pub fn try_blur_argb_gaussian(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) -> Result<(), BlurError> {
+	check_finite("sigma_x", sigma_x)?;
+	check_finite("sigma_y", sigma_y)?;
+	check_radius(ARGB_CHANNEL_MAX, upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)), fractional_weight)?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		try_blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32)
+	} else {
+		try_blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32)
+	}
+}
+
+/// Blurs an image of 32-bit packed ARGB pixels (0xAARRGGBB) by several radii at
+/// once, and writes the blur by each radius to the output at the same index.
+///
+/// This is a version of [`blur_multi`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the largest radius is larger
+/// than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*.
+pub fn blur_argb_multi<const N: usize>(input: ImgRef<u32>, outputs: [&mut ImgRefMut<u32>; N], radii: [usize; N]) {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radii.into_iter().max().unwrap_or(0))) {
+		blur_multi(input, outputs, radii, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		blur_multi(input, outputs, radii, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`blur_argb_multi`], but returns a [`BlurError`] if a radius is too
+/// large or an output is not the same size as `input`.
+pub fn try_blur_argb_multi<const N: usize>(input: ImgRef<u32>, outputs: [&mut ImgRefMut<u32>; N], radii: [usize; N]) -> Result<(), BlurError> {
+	check_radius(ARGB_CHANNEL_MAX, radii.into_iter().max().unwrap_or(0), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radii.into_iter().max().unwrap_or(0))) {
+		try_blur_multi(input, outputs, radii, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32)
+	} else {
+		try_blur_multi(input, outputs, radii, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB).
+///
+/// This is a version of [`blur`] with pre-filled conversion routines. They use
+/// 32 bits per channel, or 64 bits when the radius is too large for that, which
+/// for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see [`blur_argb`].
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn blur_srgb(buffer: &mut ImgRefMut<u32>, radius: usize) {
+	blur_srgb_xy(buffer, radius, radius);
+}
+
+/// Like [`blur_srgb`], but returns a [`BlurError`] if the radius is too large.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn try_blur_srgb(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> {
+	try_blur_srgb_xy(buffer, radius, radius)
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with premultiplied
+/// alpha.
+///
+/// This is a version of [`blur`] with pre-filled conversion routines. They use
+/// 32 bits per channel, or 64 bits when the radius is too large for that, which
+/// for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Each pixel is converted to straight alpha, decoded to linear light, and
+/// premultiplied again before it is blurred, and the reverse happens
+/// afterwards, so the colors are blended in linear light without transparent
+/// pixels bleeding into them. Straightening the pixels loses some precision at
+/// low alpha, as described by
+/// [`Argb::from_u32_srgb_premultiplied`](color::Argb::from_u32_srgb_premultiplied).
+/// For pixels with straight alpha, see [`blur_srgb_straight`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`blur_argb_premultiplied`].
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn blur_srgb_premultiplied(buffer: &mut ImgRefMut<u32>, radius: usize) {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		blur(buffer, radius, |i| ArgbU32::from_u32_srgb_premultiplied(*i), ArgbU32::to_u32_srgb_premultiplied);
+	} else {
+		blur(buffer, radius, |i| ArgbU64::from_u32_srgb_premultiplied(*i), ArgbU64::to_u32_srgb_premultiplied);
+	}
+}
+
+/// Like [`blur_srgb_premultiplied`], but returns a [`BlurError`] if the radius
+/// is too large.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn try_blur_srgb_premultiplied(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> {
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		blur(buffer, radius, |i| ArgbU32::from_u32_srgb_premultiplied(*i), ArgbU32::to_u32_srgb_premultiplied);
+	} else {
+		blur(buffer, radius, |i| ArgbU64::from_u32_srgb_premultiplied(*i), ArgbU64::to_u32_srgb_premultiplied);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with straight (not
+/// premultiplied) alpha.
+///
+/// This is a version of [`blur`] with pre-filled conversion routines. They use
+/// 32 bits per channel, or 64 bits when the radius is too large for that, which
+/// for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Blurring straight alpha one channel at a time lets the color of transparent
+/// pixels, which is usually meaningless, bleed into the visible ones as dark or
+/// colored halos. This decodes every pixel to linear light and premultiplies it
+/// before it is blurred, and converts it back to straight alpha and sRGB
+/// afterwards, which prevents that. Pixels that come out fully transparent are
+/// black.
+///
+/// Note that this function uses *sRGB*. For linear, see [`blur_argb_straight`].
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn blur_srgb_straight(buffer: &mut ImgRefMut<u32>, radius: usize) {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		blur(buffer, radius, |i| ArgbU32::from_u32_srgb_straight(*i), ArgbU32::to_u32_srgb_straight);
+	} else {
+		blur(buffer, radius, |i| ArgbU64::from_u32_srgb_straight(*i), ArgbU64::to_u32_srgb_straight);
+	}
+}
+
+/// Like [`blur_srgb_straight`], but returns a [`BlurError`] if the radius is
+/// too large.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn try_blur_srgb_straight(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> {
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		blur(buffer, radius, |i| ArgbU32::from_u32_srgb_straight(*i), ArgbU32::to_u32_srgb_straight);
+	} else {
+		blur(buffer, radius, |i| ArgbU64::from_u32_srgb_straight(*i), ArgbU64::to_u32_srgb_straight);
+	}
+
+	Ok(())
+}
+
+/// Blurs only the channels in `mask` of a buffer of 32-bit packed sRGB pixels
+/// (0xAARRGGBB), and leaves the others exactly as they were.
+///
+/// This is a version of [`blur_srgb`] that only does the work for the channels
+/// that it blurs, so blurring just [`ChannelMask::ALPHA`], for example to make
+/// a shadow, takes about a quarter of the arithmetic. Like [`blur_srgb`], it
+/// uses 32 bits per channel, or 64 bits when the radius is larger than
+/// [`SRGB_U32_MAX_RADIUS`]. An empty mask leaves the buffer untouched.
+///
+/// Note that this function uses *sRGB*. For linear, see [`blur_argb_masked`].
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn blur_srgb_masked(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	if mask.is_empty() {
+		return;
+	}
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, blur_masked::<StackBlurrableU32>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	} else {
+		with_channel_count!(mask, blur_masked::<StackBlurrableU64>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	}
+}
+
+/// Like [`blur_srgb_masked`], but returns a [`BlurError`] if the radius is too
+/// large.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn try_blur_srgb_masked(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) -> Result<(), BlurError> {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if mask.is_empty() {
+		return Ok(());
+	}
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, blur_masked::<StackBlurrableU32>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	} else {
+		with_channel_count!(mask, blur_masked::<StackBlurrableU64>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	}
+
+	Ok(())
+}
+
+/// Sharpens a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with an unsharp
+/// mask.
+///
+/// Each channel in `mask` becomes `original + amount * (original - blurred)`,
+/// clamped to the range of the channel, where `blurred` is the result of
+/// [`blur_srgb_masked`] with the given radius. Channels that differ from their
+/// blurred value by less than `threshold` are left alone, so that flat areas
+/// and noise are not sharpened. Pass [`ChannelMask::COLOR`] to keep alpha as it
+/// is.
+///
+/// The difference is compared to `threshold` in sRGB, but the channels are
+/// sharpened in linear light.
+///
+/// The buffer is copied and the copy is blurred, so this needs as much memory
+/// again as the buffer takes up.
+///
+/// Note that this function uses *sRGB*. For linear, see [`unsharp_mask_argb`].
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn unsharp_mask_srgb(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	let mut blurred = copy_argb(buffer);
+	blur_srgb_masked(&mut blurred.as_mut(), radius, mask);
+	unsharp(buffer, blurred.as_ref(), amount, threshold, mask, SRGB_CHANNEL_MAX, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16));
+}
+
+/// Like [`unsharp_mask_srgb`], but returns a [`BlurError`] if the radius is too
+/// large or `amount` is not finite. The buffer is only changed if this succeeds.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn try_unsharp_mask_srgb(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) -> Result<(), BlurError> {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	check_amount(amount)?;
+
+	let mut blurred = copy_argb(buffer);
+	try_blur_srgb_masked(&mut blurred.as_mut(), radius, mask)?;
+	unsharp(buffer, blurred.as_ref(), amount, threshold, mask, SRGB_CHANNEL_MAX, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16));
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with separate
+/// horizontal and vertical radii.
+///
+/// This is a version of [`blur_xy`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see [`blur_argb_xy`].
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn blur_srgb_xy(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		blur_xy(buffer, radius_x, radius_y, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		blur_xy(buffer, radius_x, radius_y, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+}
+
+/// Like [`blur_srgb_xy`], but returns a [`BlurError`] if the radius is too
+/// large.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn try_blur_srgb_xy(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) -> Result<(), BlurError> {
+	check_radius(SRGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		blur_xy(buffer, radius_x, radius_y, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		blur_xy(buffer, radius_x, radius_y, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with separate
+/// horizontal and vertical radii and a custom [`EdgeMode`].
+///
+/// This is a version of [`blur_with_edge_mode`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`blur_argb_with_edge_mode`].
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn blur_srgb_with_edge_mode(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+}
+
+/// Like [`blur_srgb_with_edge_mode`], but returns a [`BlurError`] if the radius
+/// is too large.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn try_blur_srgb_with_edge_mode(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) -> Result<(), BlurError> {
+	check_radius(SRGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with fractional
+/// horizontal and vertical radii.
+///
+/// This is a version of [`blur_fractional`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`blur_argb_fractional`].
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn blur_srgb_fractional(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) {
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		blur_fractional(buffer, radius_x, radius_y, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		blur_fractional(buffer, radius_x, radius_y, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+}
+
+/// Like [`blur_srgb_fractional`], but returns a [`BlurError`] if the radius is
+/// too large or a parameter is not supported.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn try_blur_srgb_fractional(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) -> Result<(), BlurError> {
+	check_finite("radius_x", radius_x)?;
+	check_finite("radius_y", radius_y)?;
+	check_radius(SRGB_CHANNEL_MAX, upper_radius(radius_x.max(radius_y)), fractional_weight)?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		try_blur_fractional(buffer, radius_x, radius_y, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb)
+	} else {
+		try_blur_fractional(buffer, radius_x, radius_y, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with the given
+/// [`Kernel`] shape and horizontal and vertical radii.
+///
+/// This is a version of [`blur_kernel`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see [`blur_argb_kernel`].
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn blur_srgb_kernel(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) {
+	if fits_u32(SRGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+}
+
+/// Like [`blur_srgb_kernel`], but returns a [`BlurError`] if the radius is too
+/// large.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn try_blur_srgb_kernel(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) -> Result<(), BlurError> {
+	check_radius(SRGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| kernel.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with a separate
+/// radius for every pixel.
+///
+/// This is a version of [`blur_variable`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see [`blur_argb_variable`].
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn blur_srgb_variable(buffer: &mut ImgRefMut<u32>, radii: ImgRef<u16>) {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radii.pixels().max().unwrap_or(0) as usize)) {
+		blur_variable(buffer, radii, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		blur_variable(buffer, radii, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+}
+
+/// Like [`blur_srgb_variable`], but returns a [`BlurError`] if a parameter is
+/// not supported.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn try_blur_srgb_variable(buffer: &mut ImgRefMut<u32>, radii: ImgRef<u16>) -> Result<(), BlurError> {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radii.pixels().max().unwrap_or(0) as usize)) {
+		try_blur_variable(buffer, radii, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb)
+	} else {
+		try_blur_variable(buffer, radii, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with an
+/// approximation of a Gaussian blur.
+///
+/// This is a version of [`blur_gaussian`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see [`blur_argb_gaussian`].
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn blur_srgb_gaussian(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) {
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+}
+
+/// Like [`blur_srgb_gaussian`], but returns a [`BlurError`] if the radius is
+/// too large or a parameter is not supported.
+#[cfg(any(doc, feature = "blend-srgb"))]
+pub fn try_blur_srgb_gaussian(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) -> Result<(), BlurError> {
+	check_finite("sigma_x", sigma_x)?;
+	check_finite("sigma_y", sigma_y)?;
+	check_radius(SRGB_CHANNEL_MAX, upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)), fractional_weight)?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		try_blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb)
+	} else {
+		try_blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) in parallel.
+///
+/// This is a version of [`par_blur`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see [`par_blur_srgb`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_argb(buffer: &mut ImgRefMut<u32>, radius: usize) {
+	par_blur_argb_xy(buffer, radius, radius);
+}
+
+/// Like [`par_blur_argb`], but returns a [`BlurError`] if the radius is too
+/// large.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_argb(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> {
+	try_par_blur_argb_xy(buffer, radius, radius)
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with premultiplied
+/// alpha in parallel.
+///
+/// This is a version of [`par_blur`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Premultiplied pixels can be blurred one channel at a time without any
+/// special treatment, so this does exactly the same thing as [`par_blur_argb`],
+/// and the output is premultiplied as well. For pixels with straight alpha, see
+/// [`par_blur_argb_straight`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_blur_srgb_premultiplied`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_argb_premultiplied(buffer: &mut ImgRefMut<u32>, radius: usize) {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_blur(buffer, radius, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		par_blur(buffer, radius, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`par_blur_argb_premultiplied`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_argb_premultiplied(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> {
+	check_radius(ARGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_blur(buffer, radius, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		par_blur(buffer, radius, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with straight (not
+/// premultiplied) alpha in parallel.
+///
+/// This is a version of [`par_blur`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Blurring straight alpha one channel at a time lets the color of transparent
+/// pixels, which is usually meaningless, bleed into the visible ones as dark or
+/// colored halos. This premultiplies every pixel before it is blurred and
+/// converts it back to straight alpha afterwards, which prevents that. Pixels
+/// that come out fully transparent are black.
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_blur_srgb_straight`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_argb_straight(buffer: &mut ImgRefMut<u32>, radius: usize) {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_blur(buffer, radius, |i| ArgbU32::from_u32_straight(*i), ArgbU32::to_u32_straight);
+	} else {
+		par_blur(buffer, radius, |i| ArgbU64::from_u32_straight(*i), ArgbU64::to_u32_straight);
+	}
+}
+
+/// Like [`par_blur_argb_straight`], but returns a [`BlurError`] if the radius
+/// is too large.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_argb_straight(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> {
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_blur(buffer, radius, |i| ArgbU32::from_u32_straight(*i), ArgbU32::to_u32_straight);
+	} else {
+		par_blur(buffer, radius, |i| ArgbU64::from_u32_straight(*i), ArgbU64::to_u32_straight);
+	}
+
+	Ok(())
+}
+
+/// Blurs only the channels in `mask` of a buffer of 32-bit packed ARGB pixels
+/// (0xAARRGGBB) in parallel, and leaves the others exactly as they were.
+///
+/// This is a version of [`par_blur_argb`] that only does the work for the
+/// channels that it blurs, so blurring just [`ChannelMask::ALPHA`], for example
+/// to make a shadow, takes about a quarter of the arithmetic. Like
+/// [`par_blur_argb`], it uses 32 bits per channel, or 64 bits when the radius
+/// is larger than [`ARGB_U32_MAX_RADIUS`]. An empty mask leaves the buffer
+/// untouched.
+///
+/// Note that this function is *linear*. For sRGB, see [`par_blur_srgb_masked`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_argb_masked(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) {
+	if mask.is_empty() {
+		return;
+	}
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, par_blur_masked::<StackBlurrableU32>(buffer, radius, mask, u32::from, |i| i as u8));
+	} else {
+		with_channel_count!(mask, par_blur_masked::<StackBlurrableU64>(buffer, radius, mask, u32::from, |i| i as u8));
+	}
+}
+
+/// Like [`par_blur_argb_masked`], but returns a [`BlurError`] if the radius is
+/// too large.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_argb_masked(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) -> Result<(), BlurError> {
+	check_radius(ARGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if mask.is_empty() {
+		return Ok(());
+	}
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, par_blur_masked::<StackBlurrableU32>(buffer, radius, mask, u32::from, |i| i as u8));
+	} else {
+		with_channel_count!(mask, par_blur_masked::<StackBlurrableU64>(buffer, radius, mask, u32::from, |i| i as u8));
+	}
+
+	Ok(())
+}
+
+/// Sharpens a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) in parallel with
+/// an unsharp mask.
+///
+/// Each channel in `mask` becomes `original + amount * (original - blurred)`,
+/// clamped to the range of the channel, where `blurred` is the result of
+/// [`par_blur_argb_masked`] with the given radius. Channels that differ from
+/// their blurred value by less than `threshold` are left alone, so that flat
+/// areas and noise are not sharpened. Pass [`ChannelMask::COLOR`] to keep alpha
+/// as it is.
+///
+/// The buffer is copied and the copy is blurred, so this needs as much memory
+/// again as the buffer takes up.
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_unsharp_mask_srgb`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_unsharp_mask_argb(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) {
+	let mut blurred = copy_argb(buffer);
+	par_blur_argb_masked(&mut blurred.as_mut(), radius, mask);
+	par_unsharp(buffer, blurred.as_ref(), amount, threshold, mask, ARGB_CHANNEL_MAX, u32::from, |i| i as u8);
+}
+
+/// Like [`par_unsharp_mask_argb`], but returns a [`BlurError`] if the radius is
+/// too large or `amount` is not finite. The buffer is only changed if this
+/// succeeds.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_unsharp_mask_argb(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) -> Result<(), BlurError> {
+	check_amount(amount)?;
+
+	let mut blurred = copy_argb(buffer);
+	try_par_blur_argb_masked(&mut blurred.as_mut(), radius, mask)?;
+	par_unsharp(buffer, blurred.as_ref(), amount, threshold, mask, ARGB_CHANNEL_MAX, u32::from, |i| i as u8);
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) in parallel with
+/// separate horizontal and vertical radii.
+///
+/// This is a version of [`par_blur_xy`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see [`par_blur_srgb_xy`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_argb_xy(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_blur_xy(buffer, radius_x, radius_y, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		par_blur_xy(buffer, radius_x, radius_y, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`par_blur_argb_xy`], but returns a [`BlurError`] if the radius is too
+/// large.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_argb_xy(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) -> Result<(), BlurError> {
+	check_radius(ARGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_blur_xy(buffer, radius_x, radius_y, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		par_blur_xy(buffer, radius_x, radius_y, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) in parallel with
+/// separate horizontal and vertical radii and a custom [`EdgeMode`].
+///
+/// This is a version of [`par_blur_with_edge_mode`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_blur_srgb_with_edge_mode`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_argb_with_edge_mode(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		par_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`par_blur_argb_with_edge_mode`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_argb_with_edge_mode(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) -> Result<(), BlurError> {
+	check_radius(ARGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		par_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) in parallel with
+/// fractional horizontal and vertical radii.
+///
+/// This is a version of [`par_blur_fractional`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_blur_srgb_fractional`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_argb_fractional(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) {
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		par_blur_fractional(buffer, radius_x, radius_y, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		par_blur_fractional(buffer, radius_x, radius_y, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`par_blur_argb_fractional`], but returns a [`BlurError`] if the radius
+/// is too large or a parameter is not supported.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_argb_fractional(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) -> Result<(), BlurError> {
+	check_finite("radius_x", radius_x)?;
+	check_finite("radius_y", radius_y)?;
+	check_radius(ARGB_CHANNEL_MAX, upper_radius(radius_x.max(radius_y)), fractional_weight)?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		try_par_blur_fractional(buffer, radius_x, radius_y, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32)
+	} else {
+		try_par_blur_fractional(buffer, radius_x, radius_y, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) in parallel with
+/// the given [`Kernel`] shape and horizontal and vertical radii.
+///
+/// This is a version of [`par_blur_kernel`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see [`par_blur_srgb_kernel`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_argb_kernel(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) {
+	if fits_u32(ARGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		par_blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		par_blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`par_blur_argb_kernel`], but returns a [`BlurError`] if the radius is
+/// too large.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_argb_kernel(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) -> Result<(), BlurError> {
+	check_radius(ARGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| kernel.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		par_blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		par_blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) in parallel with a
+/// separate radius for every pixel.
+///
+/// This is a version of [`par_blur_variable`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_blur_srgb_variable`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_argb_variable(buffer: &mut ImgRefMut<u32>, radii: ImgRef<u16>) {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radii.pixels().max().unwrap_or(0) as usize)) {
+		par_blur_variable(buffer, radii, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		par_blur_variable(buffer, radii, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`par_blur_argb_variable`], but returns a [`BlurError`] if a parameter
+/// is not supported.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_argb_variable(buffer: &mut ImgRefMut<u32>, radii: ImgRef<u16>) -> Result<(), BlurError> {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radii.pixels().max().unwrap_or(0) as usize)) {
+		try_par_blur_variable(buffer, radii, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32)
+	} else {
+		try_par_blur_variable(buffer, radii, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) in parallel with an
+/// approximation of a Gaussian blur.
+///
+/// This is a version of [`par_blur_gaussian`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_blur_srgb_gaussian`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_argb_gaussian(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) {
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		par_blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		par_blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`par_blur_argb_gaussian`], but returns a [`BlurError`] if the radius
+/// is too large or a parameter is not supported.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_argb_gaussian(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) -> Result<(), BlurError> {
+	check_finite("sigma_x", sigma_x)?;
+	check_finite("sigma_y", sigma_y)?;
+	check_radius(ARGB_CHANNEL_MAX, upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)), fractional_weight)?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		try_par_blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32)
+	} else {
+		try_par_blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32)
+	}
+}
+
+/// Blurs an image of 32-bit packed ARGB pixels (0xAARRGGBB) by several radii at
+/// once in parallel, and writes the blur by each radius to the output at the
+/// same index.
+///
+/// This is a version of [`par_blur_multi`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the largest radius
+/// is larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_argb_multi<const N: usize>(input: ImgRef<u32>, outputs: [&mut ImgRefMut<u32>; N], radii: [usize; N]) {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radii.into_iter().max().unwrap_or(0))) {
+		par_blur_multi(input, outputs, radii, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32);
+	} else {
+		par_blur_multi(input, outputs, radii, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32);
+	}
+}
+
+/// Like [`par_blur_argb_multi`], but returns a [`BlurError`] if a radius is too
+/// large or an output is not the same size as `input`.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn try_par_blur_argb_multi<const N: usize>(input: ImgRef<u32>, outputs: [&mut ImgRefMut<u32>; N], radii: [usize; N]) -> Result<(), BlurError> {
+	check_radius(ARGB_CHANNEL_MAX, radii.into_iter().max().unwrap_or(0), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radii.into_iter().max().unwrap_or(0))) {
+		try_par_blur_multi(input, outputs, radii, |i| ArgbU32::from_u32(*i), ArgbU32::to_u32)
+	} else {
+		try_par_blur_multi(input, outputs, radii, |i| ArgbU64::from_u32(*i), ArgbU64::to_u32)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) in parallel.
+///
+/// This is a version of [`par_blur`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see [`par_blur_argb`].
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn par_blur_srgb(buffer: &mut ImgRefMut<u32>, radius: usize) {
+	par_blur_srgb_xy(buffer, radius, radius);
+}
+
+/// Like [`par_blur_srgb`], but returns a [`BlurError`] if the radius is too
+/// large.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn try_par_blur_srgb(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> {
+	try_par_blur_srgb_xy(buffer, radius, radius)
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with premultiplied
+/// alpha in parallel.
+///
+/// This is a version of [`par_blur`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Each pixel is converted to straight alpha, decoded to linear light, and
+/// premultiplied again before it is blurred, and the reverse happens
+/// afterwards, so the colors are blended in linear light without transparent
+/// pixels bleeding into them. Straightening the pixels loses some precision at
+/// low alpha, as described by
+/// [`Argb::from_u32_srgb_premultiplied`](color::Argb::from_u32_srgb_premultiplied).
+/// For pixels with straight alpha, see [`par_blur_srgb_straight`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_blur_argb_premultiplied`].
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn par_blur_srgb_premultiplied(buffer: &mut ImgRefMut<u32>, radius: usize) {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_blur(buffer, radius, |i| ArgbU32::from_u32_srgb_premultiplied(*i), ArgbU32::to_u32_srgb_premultiplied);
+	} else {
+		par_blur(buffer, radius, |i| ArgbU64::from_u32_srgb_premultiplied(*i), ArgbU64::to_u32_srgb_premultiplied);
+	}
+}
+
+/// Like [`par_blur_srgb_premultiplied`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn try_par_blur_srgb_premultiplied(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> {
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_blur(buffer, radius, |i| ArgbU32::from_u32_srgb_premultiplied(*i), ArgbU32::to_u32_srgb_premultiplied);
+	} else {
+		par_blur(buffer, radius, |i| ArgbU64::from_u32_srgb_premultiplied(*i), ArgbU64::to_u32_srgb_premultiplied);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with straight (not
+/// premultiplied) alpha in parallel.
+///
+/// This is a version of [`par_blur`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Blurring straight alpha one channel at a time lets the color of transparent
+/// pixels, which is usually meaningless, bleed into the visible ones as dark or
+/// colored halos. This decodes every pixel to linear light and premultiplies it
+/// before it is blurred, and converts it back to straight alpha and sRGB
+/// afterwards, which prevents that. Pixels that come out fully transparent are
+/// black.
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_blur_argb_straight`].
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn par_blur_srgb_straight(buffer: &mut ImgRefMut<u32>, radius: usize) {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_blur(buffer, radius, |i| ArgbU32::from_u32_srgb_straight(*i), ArgbU32::to_u32_srgb_straight);
+	} else {
+		par_blur(buffer, radius, |i| ArgbU64::from_u32_srgb_straight(*i), ArgbU64::to_u32_srgb_straight);
+	}
+}
+
+/// Like [`par_blur_srgb_straight`], but returns a [`BlurError`] if the radius
+/// is too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn try_par_blur_srgb_straight(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> {
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_blur(buffer, radius, |i| ArgbU32::from_u32_srgb_straight(*i), ArgbU32::to_u32_srgb_straight);
+	} else {
+		par_blur(buffer, radius, |i| ArgbU64::from_u32_srgb_straight(*i), ArgbU64::to_u32_srgb_straight);
+	}
+
+	Ok(())
+}
+
+/// Blurs only the channels in `mask` of a buffer of 32-bit packed sRGB pixels
+/// (0xAARRGGBB) in parallel, and leaves the others exactly as they were.
+///
+/// This is a version of [`par_blur_srgb`] that only does the work for the
+/// channels that it blurs, so blurring just [`ChannelMask::ALPHA`], for example
+/// to make a shadow, takes about a quarter of the arithmetic. Like
+/// [`par_blur_srgb`], it uses 32 bits per channel, or 64 bits when the radius
+/// is larger than [`SRGB_U32_MAX_RADIUS`]. An empty mask leaves the buffer
+/// untouched.
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_blur_argb_masked`].
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn par_blur_srgb_masked(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	if mask.is_empty() {
+		return;
+	}
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, par_blur_masked::<StackBlurrableU32>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	} else {
+		with_channel_count!(mask, par_blur_masked::<StackBlurrableU64>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	}
+}
+
+/// Like [`par_blur_srgb_masked`], but returns a [`BlurError`] if the radius is
+/// too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn try_par_blur_srgb_masked(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) -> Result<(), BlurError> {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if mask.is_empty() {
+		return Ok(());
+	}
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, par_blur_masked::<StackBlurrableU32>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	} else {
+		with_channel_count!(mask, par_blur_masked::<StackBlurrableU64>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	}
+
+	Ok(())
+}
+
+/// Sharpens a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) in parallel with
+/// an unsharp mask.
+///
+/// Each channel in `mask` becomes `original + amount * (original - blurred)`,
+/// clamped to the range of the channel, where `blurred` is the result of
+/// [`par_blur_srgb_masked`] with the given radius. Channels that differ from
+/// their blurred value by less than `threshold` are left alone, so that flat
+/// areas and noise are not sharpened. Pass [`ChannelMask::COLOR`] to keep alpha
+/// as it is.
+///
+/// The difference is compared to `threshold` in sRGB, but the channels are
+/// sharpened in linear light.
+///
+/// The buffer is copied and the copy is blurred, so this needs as much memory
+/// again as the buffer takes up.
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_unsharp_mask_argb`].
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn par_unsharp_mask_srgb(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	let mut blurred = copy_argb(buffer);
+	par_blur_srgb_masked(&mut blurred.as_mut(), radius, mask);
+	par_unsharp(buffer, blurred.as_ref(), amount, threshold, mask, SRGB_CHANNEL_MAX, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16));
+}
+
+/// Like [`par_unsharp_mask_srgb`], but returns a [`BlurError`] if the radius is
+/// too large or `amount` is not finite. The buffer is only changed if this
+/// succeeds.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn try_par_unsharp_mask_srgb(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) -> Result<(), BlurError> {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	check_amount(amount)?;
+
+	let mut blurred = copy_argb(buffer);
+	try_par_blur_srgb_masked(&mut blurred.as_mut(), radius, mask)?;
+	par_unsharp(buffer, blurred.as_ref(), amount, threshold, mask, SRGB_CHANNEL_MAX, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16));
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) in parallel with
+/// separate horizontal and vertical radii.
+///
+/// This is a version of [`par_blur_xy`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see [`par_blur_argb_xy`].
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn par_blur_srgb_xy(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_blur_xy(buffer, radius_x, radius_y, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		par_blur_xy(buffer, radius_x, radius_y, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+}
+
+/// Like [`par_blur_srgb_xy`], but returns a [`BlurError`] if the radius is too
+/// large.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn try_par_blur_srgb_xy(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) -> Result<(), BlurError> {
+	check_radius(SRGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_blur_xy(buffer, radius_x, radius_y, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		par_blur_xy(buffer, radius_x, radius_y, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) in parallel with
+/// separate horizontal and vertical radii and a custom [`EdgeMode`].
+///
+/// This is a version of [`par_blur_with_edge_mode`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_blur_argb_with_edge_mode`].
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn par_blur_srgb_with_edge_mode(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		par_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+}
+
+/// Like [`par_blur_srgb_with_edge_mode`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn try_par_blur_srgb_with_edge_mode(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) -> Result<(), BlurError> {
+	check_radius(SRGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		par_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) in parallel with
+/// fractional horizontal and vertical radii.
+///
+/// This is a version of [`par_blur_fractional`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_blur_argb_fractional`].
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn par_blur_srgb_fractional(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) {
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		par_blur_fractional(buffer, radius_x, radius_y, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		par_blur_fractional(buffer, radius_x, radius_y, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+}
+
+/// Like [`par_blur_srgb_fractional`], but returns a [`BlurError`] if the radius
+/// is too large or a parameter is not supported.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn try_par_blur_srgb_fractional(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) -> Result<(), BlurError> {
+	check_finite("radius_x", radius_x)?;
+	check_finite("radius_y", radius_y)?;
+	check_radius(SRGB_CHANNEL_MAX, upper_radius(radius_x.max(radius_y)), fractional_weight)?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		try_par_blur_fractional(buffer, radius_x, radius_y, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb)
+	} else {
+		try_par_blur_fractional(buffer, radius_x, radius_y, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) in parallel with
+/// the given [`Kernel`] shape and horizontal and vertical radii.
+///
+/// This is a version of [`par_blur_kernel`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_blur_argb_kernel`].
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn par_blur_srgb_kernel(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) {
+	if fits_u32(SRGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		par_blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		par_blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+}
+
+/// Like [`par_blur_srgb_kernel`], but returns a [`BlurError`] if the radius is
+/// too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn try_par_blur_srgb_kernel(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) -> Result<(), BlurError> {
+	check_radius(SRGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| kernel.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		par_blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		par_blur_kernel(buffer, radius_x, radius_y, kernel, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) in parallel with a
+/// separate radius for every pixel.
+///
+/// This is a version of [`par_blur_variable`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_blur_argb_variable`].
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn par_blur_srgb_variable(buffer: &mut ImgRefMut<u32>, radii: ImgRef<u16>) {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radii.pixels().max().unwrap_or(0) as usize)) {
+		par_blur_variable(buffer, radii, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		par_blur_variable(buffer, radii, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+}
+
+/// Like [`par_blur_srgb_variable`], but returns a [`BlurError`] if a parameter
+/// is not supported.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn try_par_blur_srgb_variable(buffer: &mut ImgRefMut<u32>, radii: ImgRef<u16>) -> Result<(), BlurError> {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radii.pixels().max().unwrap_or(0) as usize)) {
+		try_par_blur_variable(buffer, radii, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb)
+	} else {
+		try_par_blur_variable(buffer, radii, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) in parallel with an
+/// approximation of a Gaussian blur.
+///
+/// This is a version of [`par_blur_gaussian`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_blur_argb_gaussian`].
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn par_blur_srgb_gaussian(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) {
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		par_blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb);
+	} else {
+		par_blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb);
+	}
+}
+
+/// Like [`par_blur_srgb_gaussian`], but returns a [`BlurError`] if the radius
+/// is too large or a parameter is not supported.
+#[cfg(any(doc, all(feature = "rayon", feature = "blend-srgb")))]
+pub fn try_par_blur_srgb_gaussian(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) -> Result<(), BlurError> {
+	check_finite("sigma_x", sigma_x)?;
+	check_finite("sigma_y", sigma_y)?;
+	check_radius(SRGB_CHANNEL_MAX, upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)), fractional_weight)?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		try_par_blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb)
+	} else {
+		try_par_blur_gaussian(buffer, sigma_x, sigma_y, passes, |i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD.
+///
+/// This is a version of [`simd_blur`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see [`simd_blur_srgb`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_argb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	simd_blur_argb_xy::<LANES>(buffer, radius, radius);
+}
+
+/// Like [`simd_blur_argb`], but returns a [`BlurError`] if the radius is too
+/// large.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur_argb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	try_simd_blur_argb_xy::<LANES>(buffer, radius, radius)
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with premultiplied
+/// alpha with SIMD.
+///
+/// This is a version of [`simd_blur`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Premultiplied pixels can be blurred one channel at a time without any
+/// special treatment, so this does exactly the same thing as
+/// [`simd_blur_argb`], and the output is premultiplied as well. For pixels with
+/// straight alpha, see [`simd_blur_argb_straight`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`simd_blur_srgb_premultiplied`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_argb_premultiplied<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+}
+
+/// Like [`simd_blur_argb_premultiplied`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur_argb_premultiplied<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(ARGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with straight (not
+/// premultiplied) alpha with SIMD.
+///
+/// This is a version of [`simd_blur`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Blurring straight alpha one channel at a time lets the color of transparent
+/// pixels, which is usually meaningless, bleed into the visible ones as dark or
+/// colored halos. This premultiplies every pixel before it is blurred and
+/// converts it back to straight alpha afterwards, which prevents that. Pixels
+/// that come out fully transparent are black.
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`simd_blur_srgb_straight`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_argb_straight<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_straight(i.map(u32::clone)), ArgbU32xN::to_u32xN_straight,
+			|i| ArgbU32::from_u32_straight(*i), ArgbU32::to_u32_straight
+		);
+	} else {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_straight(i.map(u32::clone)), ArgbU64xN::to_u32xN_straight,
+			|i| ArgbU64::from_u32_straight(*i), ArgbU64::to_u32_straight
+		);
+	}
+}
+
+/// Like [`simd_blur_argb_straight`], but returns a [`BlurError`] if the radius
+/// is too large.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur_argb_straight<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_straight(i.map(u32::clone)), ArgbU32xN::to_u32xN_straight,
+			|i| ArgbU32::from_u32_straight(*i), ArgbU32::to_u32_straight
+		);
+	} else {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_straight(i.map(u32::clone)), ArgbU64xN::to_u32xN_straight,
+			|i| ArgbU64::from_u32_straight(*i), ArgbU64::to_u32_straight
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs only the channels in `mask` of a buffer of 32-bit packed ARGB pixels
+/// (0xAARRGGBB) with SIMD, and leaves the others exactly as they were.
+///
+/// This is a version of [`simd_blur_argb`] that only does the work for the
+/// channels that it blurs, so blurring just [`ChannelMask::ALPHA`], for example
+/// to make a shadow, takes about a quarter of the arithmetic. Like
+/// [`simd_blur_argb`], it uses 32 bits per channel, or 64 bits when the radius
+/// is larger than [`ARGB_U32_MAX_RADIUS`]. An empty mask leaves the buffer
+/// untouched.
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`simd_blur_srgb_masked`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_argb_masked<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) where LaneCount<LANES>: SupportedLaneCount {
+	if mask.is_empty() {
+		return;
+	}
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, simd_blur_masked::<StackBlurrableU32, StackBlurrableU32xN<LANES>, LANES>(buffer, radius, mask, u32::from, |i| i as u8));
+	} else {
+		with_channel_count!(mask, simd_blur_masked::<StackBlurrableU64, StackBlurrableU64xN<LANES>, LANES>(buffer, radius, mask, u32::from, |i| i as u8));
+	}
+}
+
+/// Like [`simd_blur_argb_masked`], but returns a [`BlurError`] if the radius is
+/// too large.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur_argb_masked<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(ARGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if mask.is_empty() {
+		return Ok(());
+	}
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, simd_blur_masked::<StackBlurrableU32, StackBlurrableU32xN<LANES>, LANES>(buffer, radius, mask, u32::from, |i| i as u8));
+	} else {
+		with_channel_count!(mask, simd_blur_masked::<StackBlurrableU64, StackBlurrableU64xN<LANES>, LANES>(buffer, radius, mask, u32::from, |i| i as u8));
+	}
+
+	Ok(())
+}
+
+/// Sharpens a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD with
+/// an unsharp mask.
+///
+/// Each channel in `mask` becomes `original + amount * (original - blurred)`,
+/// clamped to the range of the channel, where `blurred` is the result of
+/// [`simd_blur_argb_masked`] with the given radius. Channels that differ from
+/// their blurred value by less than `threshold` are left alone, so that flat
+/// areas and noise are not sharpened. Pass [`ChannelMask::COLOR`] to keep alpha
+/// as it is.
+///
+/// The buffer is copied and the copy is blurred, so this needs as much memory
+/// again as the buffer takes up.
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`simd_unsharp_mask_srgb`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_unsharp_mask_argb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) where LaneCount<LANES>: SupportedLaneCount {
+	let mut blurred = copy_argb(buffer);
+	simd_blur_argb_masked::<LANES>(&mut blurred.as_mut(), radius, mask);
+	unsharp(buffer, blurred.as_ref(), amount, threshold, mask, ARGB_CHANNEL_MAX, u32::from, |i| i as u8);
+}
+
+/// Like [`simd_unsharp_mask_argb`], but returns a [`BlurError`] if the radius
+/// is too large or `amount` is not finite. The buffer is only changed if this
+/// succeeds.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_unsharp_mask_argb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_amount(amount)?;
+
+	let mut blurred = copy_argb(buffer);
+	try_simd_blur_argb_masked::<LANES>(&mut blurred.as_mut(), radius, mask)?;
+	unsharp(buffer, blurred.as_ref(), amount, threshold, mask, ARGB_CHANNEL_MAX, u32::from, |i| i as u8);
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD with
+/// separate horizontal and vertical radii.
+///
+/// This is a version of [`simd_blur_xy`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see [`simd_blur_srgb_xy`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_argb_xy<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+}
+
+/// Like [`simd_blur_argb_xy`], but returns a [`BlurError`] if the radius is too
+/// large.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur_argb_xy<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(ARGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD with
+/// separate horizontal and vertical radii and a custom [`EdgeMode`].
+///
+/// This is a version of [`simd_blur_with_edge_mode`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`simd_blur_srgb_with_edge_mode`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_argb_with_edge_mode<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+}
+
+/// Like [`simd_blur_argb_with_edge_mode`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur_argb_with_edge_mode<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(ARGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD with
+/// fractional horizontal and vertical radii.
+///
+/// This is a version of [`simd_blur_fractional`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`simd_blur_srgb_fractional`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_argb_fractional<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+}
+
+/// Like [`simd_blur_argb_fractional`], but returns a [`BlurError`] if the
+/// radius is too large or a parameter is not supported.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur_argb_fractional<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_finite("radius_x", radius_x)?;
+	check_finite("radius_y", radius_y)?;
+	check_radius(ARGB_CHANNEL_MAX, upper_radius(radius_x.max(radius_y)), fractional_weight)?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		try_simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		)
+	} else {
+		try_simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD with the
+/// given [`Kernel`] shape and horizontal and vertical radii.
+///
+/// This is a version of [`simd_blur_kernel`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`simd_blur_srgb_kernel`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_argb_kernel<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(ARGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+}
+
+/// Like [`simd_blur_argb_kernel`], but returns a [`BlurError`] if the radius is
+/// too large.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur_argb_kernel<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(ARGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| kernel.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD with an
+/// approximation of a Gaussian blur.
+///
+/// This is a version of [`simd_blur_gaussian`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`simd_blur_srgb_gaussian`].
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_argb_gaussian<const LANES: usize>(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+}
+
+/// Like [`simd_blur_argb_gaussian`], but returns a [`BlurError`] if the radius
+/// is too large or a parameter is not supported.
+#[cfg(any(doc, feature = "simd"))]
+pub fn try_simd_blur_argb_gaussian<const LANES: usize>(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_finite("sigma_x", sigma_x)?;
+	check_finite("sigma_y", sigma_y)?;
+	check_radius(ARGB_CHANNEL_MAX, upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)), fractional_weight)?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		try_simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		)
+	} else {
+		try_simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD.
+///
+/// This is a version of [`simd_blur`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see [`simd_blur_argb`].
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn simd_blur_srgb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	simd_blur_srgb_xy::<LANES>(buffer, radius, radius);
+}
+
+/// Like [`simd_blur_srgb`], but returns a [`BlurError`] if the radius is too
+/// large.
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn try_simd_blur_srgb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	try_simd_blur_srgb_xy::<LANES>(buffer, radius, radius)
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with premultiplied
+/// alpha with SIMD.
+///
+/// This is a version of [`simd_blur`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Each pixel is converted to straight alpha, decoded to linear light, and
+/// premultiplied again before it is blurred, and the reverse happens
+/// afterwards, so the colors are blended in linear light without transparent
+/// pixels bleeding into them. Straightening the pixels loses some precision at
+/// low alpha, as described by
+/// [`Argb::from_u32_srgb_premultiplied`](color::Argb::from_u32_srgb_premultiplied).
+/// For pixels with straight alpha, see [`simd_blur_srgb_straight`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`simd_blur_argb_premultiplied`].
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn simd_blur_srgb_premultiplied<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb_premultiplied(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb_premultiplied,
+			|i| ArgbU32::from_u32_srgb_premultiplied(*i), ArgbU32::to_u32_srgb_premultiplied
+		);
+	} else {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb_premultiplied(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb_premultiplied,
+			|i| ArgbU64::from_u32_srgb_premultiplied(*i), ArgbU64::to_u32_srgb_premultiplied
+		);
+	}
+}
+
+/// Like [`simd_blur_srgb_premultiplied`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn try_simd_blur_srgb_premultiplied<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb_premultiplied(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb_premultiplied,
+			|i| ArgbU32::from_u32_srgb_premultiplied(*i), ArgbU32::to_u32_srgb_premultiplied
+		);
+	} else {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb_premultiplied(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb_premultiplied,
+			|i| ArgbU64::from_u32_srgb_premultiplied(*i), ArgbU64::to_u32_srgb_premultiplied
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with straight (not
+/// premultiplied) alpha with SIMD.
+///
+/// This is a version of [`simd_blur`] with pre-filled conversion routines. They
+/// use 32 bits per channel, or 64 bits when the radius is too large for that,
+/// which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Blurring straight alpha one channel at a time lets the color of transparent
+/// pixels, which is usually meaningless, bleed into the visible ones as dark or
+/// colored halos. This decodes every pixel to linear light and premultiplies it
+/// before it is blurred, and converts it back to straight alpha and sRGB
+/// afterwards, which prevents that. Pixels that come out fully transparent are
+/// black.
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`simd_blur_argb_straight`].
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn simd_blur_srgb_straight<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb_straight(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb_straight,
+			|i| ArgbU32::from_u32_srgb_straight(*i), ArgbU32::to_u32_srgb_straight
+		);
+	} else {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb_straight(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb_straight,
+			|i| ArgbU64::from_u32_srgb_straight(*i), ArgbU64::to_u32_srgb_straight
+		);
+	}
+}
+
+/// Like [`simd_blur_srgb_straight`], but returns a [`BlurError`] if the radius
+/// is too large.
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn try_simd_blur_srgb_straight<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb_straight(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb_straight,
+			|i| ArgbU32::from_u32_srgb_straight(*i), ArgbU32::to_u32_srgb_straight
+		);
+	} else {
+		simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb_straight(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb_straight,
+			|i| ArgbU64::from_u32_srgb_straight(*i), ArgbU64::to_u32_srgb_straight
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs only the channels in `mask` of a buffer of 32-bit packed sRGB pixels
+/// (0xAARRGGBB) with SIMD, and leaves the others exactly as they were.
+///
+/// This is a version of [`simd_blur_srgb`] that only does the work for the
+/// channels that it blurs, so blurring just [`ChannelMask::ALPHA`], for example
+/// to make a shadow, takes about a quarter of the arithmetic. Like
+/// [`simd_blur_srgb`], it uses 32 bits per channel, or 64 bits when the radius
+/// is larger than [`SRGB_U32_MAX_RADIUS`]. An empty mask leaves the buffer
+/// untouched.
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`simd_blur_argb_masked`].
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn simd_blur_srgb_masked<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) where LaneCount<LANES>: SupportedLaneCount {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	if mask.is_empty() {
+		return;
+	}
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, simd_blur_masked::<StackBlurrableU32, StackBlurrableU32xN<LANES>, LANES>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	} else {
+		with_channel_count!(mask, simd_blur_masked::<StackBlurrableU64, StackBlurrableU64xN<LANES>, LANES>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	}
+}
+
+/// Like [`simd_blur_srgb_masked`], but returns a [`BlurError`] if the radius is
+/// too large.
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn try_simd_blur_srgb_masked<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if mask.is_empty() {
+		return Ok(());
+	}
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, simd_blur_masked::<StackBlurrableU32, StackBlurrableU32xN<LANES>, LANES>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	} else {
+		with_channel_count!(mask, simd_blur_masked::<StackBlurrableU64, StackBlurrableU64xN<LANES>, LANES>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	}
+
+	Ok(())
+}
+
+/// Sharpens a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD with
+/// an unsharp mask.
+///
+/// Each channel in `mask` becomes `original + amount * (original - blurred)`,
+/// clamped to the range of the channel, where `blurred` is the result of
+/// [`simd_blur_srgb_masked`] with the given radius. Channels that differ from
+/// their blurred value by less than `threshold` are left alone, so that flat
+/// areas and noise are not sharpened. Pass [`ChannelMask::COLOR`] to keep alpha
+/// as it is.
+///
+/// The difference is compared to `threshold` in sRGB, but the channels are
+/// sharpened in linear light.
+///
+/// The buffer is copied and the copy is blurred, so this needs as much memory
+/// again as the buffer takes up.
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`simd_unsharp_mask_argb`].
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn simd_unsharp_mask_srgb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) where LaneCount<LANES>: SupportedLaneCount {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	let mut blurred = copy_argb(buffer);
+	simd_blur_srgb_masked::<LANES>(&mut blurred.as_mut(), radius, mask);
+	unsharp(buffer, blurred.as_ref(), amount, threshold, mask, SRGB_CHANNEL_MAX, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16));
+}
+
+/// Like [`simd_unsharp_mask_srgb`], but returns a [`BlurError`] if the radius
+/// is too large or `amount` is not finite. The buffer is only changed if this
+/// succeeds.
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn try_simd_unsharp_mask_srgb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	check_amount(amount)?;
+
+	let mut blurred = copy_argb(buffer);
+	try_simd_blur_srgb_masked::<LANES>(&mut blurred.as_mut(), radius, mask)?;
+	unsharp(buffer, blurred.as_ref(), amount, threshold, mask, SRGB_CHANNEL_MAX, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16));
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD with
+/// separate horizontal and vertical radii.
+///
+/// This is a version of [`simd_blur_xy`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see [`simd_blur_argb_xy`].
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn simd_blur_srgb_xy<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+}
+
+/// Like [`simd_blur_srgb_xy`], but returns a [`BlurError`] if the radius is too
+/// large.
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn try_simd_blur_srgb_xy<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(SRGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD with
+/// separate horizontal and vertical radii and a custom [`EdgeMode`].
+///
+/// This is a version of [`simd_blur_with_edge_mode`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`simd_blur_argb_with_edge_mode`].
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn simd_blur_srgb_with_edge_mode<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+}
+
+/// Like [`simd_blur_srgb_with_edge_mode`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn try_simd_blur_srgb_with_edge_mode<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(SRGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD with
+/// fractional horizontal and vertical radii.
+///
+/// This is a version of [`simd_blur_fractional`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`simd_blur_argb_fractional`].
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn simd_blur_srgb_fractional<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+}
+
+/// Like [`simd_blur_srgb_fractional`], but returns a [`BlurError`] if the
+/// radius is too large or a parameter is not supported.
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn try_simd_blur_srgb_fractional<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_finite("radius_x", radius_x)?;
+	check_finite("radius_y", radius_y)?;
+	check_radius(SRGB_CHANNEL_MAX, upper_radius(radius_x.max(radius_y)), fractional_weight)?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		try_simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		)
+	} else {
+		try_simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD with the
+/// given [`Kernel`] shape and horizontal and vertical radii.
+///
+/// This is a version of [`simd_blur_kernel`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`simd_blur_argb_kernel`].
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn simd_blur_srgb_kernel<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+}
+
+/// Like [`simd_blur_srgb_kernel`], but returns a [`BlurError`] if the radius is
+/// too large.
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn try_simd_blur_srgb_kernel<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(SRGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| kernel.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD with an
+/// approximation of a Gaussian blur.
+///
+/// This is a version of [`simd_blur_gaussian`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`simd_blur_argb_gaussian`].
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn simd_blur_srgb_gaussian<const LANES: usize>(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+}
+
+/// Like [`simd_blur_srgb_gaussian`], but returns a [`BlurError`] if the radius
+/// is too large or a parameter is not supported.
+#[cfg(any(doc, all(feature = "simd", feature = "blend-srgb")))]
+pub fn try_simd_blur_srgb_gaussian<const LANES: usize>(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_finite("sigma_x", sigma_x)?;
+	check_finite("sigma_y", sigma_y)?;
+	check_radius(SRGB_CHANNEL_MAX, upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)), fractional_weight)?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		try_simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		)
+	} else {
+		try_simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD in
+/// parallel.
+///
+/// This is a version of [`par_simd_blur`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see [`par_simd_blur_srgb`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_argb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	par_simd_blur_argb_xy::<LANES>(buffer, radius, radius);
+}
+
+/// Like [`par_simd_blur_argb`], but returns a [`BlurError`] if the radius is
+/// too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur_argb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	try_par_simd_blur_argb_xy::<LANES>(buffer, radius, radius)
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with premultiplied
+/// alpha with SIMD in parallel.
+///
+/// This is a version of [`par_simd_blur`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`ARGB_U32_MAX_RADIUS`].
+///
+/// Premultiplied pixels can be blurred one channel at a time without any
+/// special treatment, so this does exactly the same thing as
+/// [`par_simd_blur_argb`], and the output is premultiplied as well. For pixels
+/// with straight alpha, see [`par_simd_blur_argb_straight`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_simd_blur_srgb_premultiplied`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_argb_premultiplied<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+}
+
+/// Like [`par_simd_blur_argb_premultiplied`], but returns a [`BlurError`] if
+/// the radius is too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur_argb_premultiplied<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(ARGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with straight (not
+/// premultiplied) alpha with SIMD in parallel.
+///
+/// This is a version of [`par_simd_blur`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Blurring straight alpha one channel at a time lets the color of transparent
+/// pixels, which is usually meaningless, bleed into the visible ones as dark or
+/// colored halos. This premultiplies every pixel before it is blurred and
+/// converts it back to straight alpha afterwards, which prevents that. Pixels
+/// that come out fully transparent are black.
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_simd_blur_srgb_straight`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_argb_straight<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_straight(i.map(u32::clone)), ArgbU32xN::to_u32xN_straight,
+			|i| ArgbU32::from_u32_straight(*i), ArgbU32::to_u32_straight
+		);
+	} else {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_straight(i.map(u32::clone)), ArgbU64xN::to_u32xN_straight,
+			|i| ArgbU64::from_u32_straight(*i), ArgbU64::to_u32_straight
+		);
+	}
+}
+
+/// Like [`par_simd_blur_argb_straight`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur_argb_straight<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_straight(i.map(u32::clone)), ArgbU32xN::to_u32xN_straight,
+			|i| ArgbU32::from_u32_straight(*i), ArgbU32::to_u32_straight
+		);
+	} else {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_straight(i.map(u32::clone)), ArgbU64xN::to_u32xN_straight,
+			|i| ArgbU64::from_u32_straight(*i), ArgbU64::to_u32_straight
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs only the channels in `mask` of a buffer of 32-bit packed ARGB pixels
+/// (0xAARRGGBB) with SIMD in parallel, and leaves the others exactly as they
+/// were.
+///
+/// This is a version of [`par_simd_blur_argb`] that only does the work for the
+/// channels that it blurs, so blurring just [`ChannelMask::ALPHA`], for example
+/// to make a shadow, takes about a quarter of the arithmetic. Like
+/// [`par_simd_blur_argb`], it uses 32 bits per channel, or 64 bits when the
+/// radius is larger than [`ARGB_U32_MAX_RADIUS`]. An empty mask leaves the
+/// buffer untouched.
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_simd_blur_srgb_masked`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_argb_masked<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) where LaneCount<LANES>: SupportedLaneCount {
+	if mask.is_empty() {
+		return;
+	}
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, par_simd_blur_masked::<StackBlurrableU32, StackBlurrableU32xN<LANES>, LANES>(buffer, radius, mask, u32::from, |i| i as u8));
+	} else {
+		with_channel_count!(mask, par_simd_blur_masked::<StackBlurrableU64, StackBlurrableU64xN<LANES>, LANES>(buffer, radius, mask, u32::from, |i| i as u8));
+	}
+}
+
+/// Like [`par_simd_blur_argb_masked`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur_argb_masked<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(ARGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if mask.is_empty() {
+		return Ok(());
+	}
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, par_simd_blur_masked::<StackBlurrableU32, StackBlurrableU32xN<LANES>, LANES>(buffer, radius, mask, u32::from, |i| i as u8));
+	} else {
+		with_channel_count!(mask, par_simd_blur_masked::<StackBlurrableU64, StackBlurrableU64xN<LANES>, LANES>(buffer, radius, mask, u32::from, |i| i as u8));
+	}
+
+	Ok(())
+}
+
+/// Sharpens a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD in
+/// parallel with an unsharp mask.
+///
+/// Each channel in `mask` becomes `original + amount * (original - blurred)`,
+/// clamped to the range of the channel, where `blurred` is the result of
+/// [`par_simd_blur_argb_masked`] with the given radius. Channels that differ
+/// from their blurred value by less than `threshold` are left alone, so that
+/// flat areas and noise are not sharpened. Pass [`ChannelMask::COLOR`] to keep
+/// alpha as it is.
+///
+/// The buffer is copied and the copy is blurred, so this needs as much memory
+/// again as the buffer takes up.
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_simd_unsharp_mask_srgb`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_unsharp_mask_argb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) where LaneCount<LANES>: SupportedLaneCount {
+	let mut blurred = copy_argb(buffer);
+	par_simd_blur_argb_masked::<LANES>(&mut blurred.as_mut(), radius, mask);
+	par_unsharp(buffer, blurred.as_ref(), amount, threshold, mask, ARGB_CHANNEL_MAX, u32::from, |i| i as u8);
+}
+
+/// Like [`par_simd_unsharp_mask_argb`], but returns a [`BlurError`] if the
+/// radius is too large or `amount` is not finite. The buffer is only changed if
+/// this succeeds.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_unsharp_mask_argb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_amount(amount)?;
+
+	let mut blurred = copy_argb(buffer);
+	try_par_simd_blur_argb_masked::<LANES>(&mut blurred.as_mut(), radius, mask)?;
+	par_unsharp(buffer, blurred.as_ref(), amount, threshold, mask, ARGB_CHANNEL_MAX, u32::from, |i| i as u8);
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD in
+/// parallel with separate horizontal and vertical radii.
+///
+/// This is a version of [`par_simd_blur_xy`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_simd_blur_srgb_xy`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_argb_xy<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		par_simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+}
+
+/// Like [`par_simd_blur_argb_xy`], but returns a [`BlurError`] if the radius is
+/// too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur_argb_xy<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(ARGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		par_simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD in
+/// parallel with separate horizontal and vertical radii and a custom
+/// [`EdgeMode`].
+///
+/// This is a version of [`par_simd_blur_with_edge_mode`] with pre-filled
+/// conversion routines. They use 32 bits per channel, or 64 bits when the
+/// radius is too large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_simd_blur_srgb_with_edge_mode`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_argb_with_edge_mode<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		par_simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+}
+
+/// Like [`par_simd_blur_argb_with_edge_mode`], but returns a [`BlurError`] if
+/// the radius is too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur_argb_with_edge_mode<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(ARGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		par_simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD in
+/// parallel with fractional horizontal and vertical radii.
+///
+/// This is a version of [`par_simd_blur_fractional`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_simd_blur_srgb_fractional`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_argb_fractional<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		par_simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		par_simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+}
+
+/// Like [`par_simd_blur_argb_fractional`], but returns a [`BlurError`] if the
+/// radius is too large or a parameter is not supported.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur_argb_fractional<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_finite("radius_x", radius_x)?;
+	check_finite("radius_y", radius_y)?;
+	check_radius(ARGB_CHANNEL_MAX, upper_radius(radius_x.max(radius_y)), fractional_weight)?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		try_par_simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		)
+	} else {
+		try_par_simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD in
+/// parallel with the given [`Kernel`] shape and horizontal and vertical radii.
+///
+/// This is a version of [`par_simd_blur_kernel`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_simd_blur_srgb_kernel`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_argb_kernel<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(ARGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		par_simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		par_simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+}
+
+/// Like [`par_simd_blur_argb_kernel`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur_argb_kernel<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(ARGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| kernel.total_weight(radius))?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		par_simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		par_simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed ARGB pixels (0xAARRGGBB) with SIMD in
+/// parallel with an approximation of a Gaussian blur.
+///
+/// This is a version of [`par_simd_blur_gaussian`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`ARGB_U32_MAX_RADIUS`].
+///
+/// Note that this function is *linear*. For sRGB, see
+/// [`par_simd_blur_srgb_gaussian`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_argb_gaussian<const LANES: usize>(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		par_simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		);
+	} else {
+		par_simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		);
+	}
+}
+
+/// Like [`par_simd_blur_argb_gaussian`], but returns a [`BlurError`] if the
+/// radius is too large or a parameter is not supported.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn try_par_simd_blur_argb_gaussian<const LANES: usize>(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_finite("sigma_x", sigma_x)?;
+	check_finite("sigma_y", sigma_y)?;
+	check_radius(ARGB_CHANNEL_MAX, upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)), fractional_weight)?;
+
+	if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		try_par_simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN(i.map(u32::clone)), ArgbU32xN::to_u32xN,
+			|i| ArgbU32::from_u32(*i), ArgbU32::to_u32
+		)
+	} else {
+		try_par_simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN(i.map(u32::clone)), ArgbU64xN::to_u32xN,
+			|i| ArgbU64::from_u32(*i), ArgbU64::to_u32
+		)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD in
+/// parallel.
+///
+/// This is a version of [`par_simd_blur`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see [`par_simd_blur_argb`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn par_simd_blur_srgb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	par_simd_blur_srgb_xy::<LANES>(buffer, radius, radius);
+}
+
+/// Like [`par_simd_blur_srgb`], but returns a [`BlurError`] if the radius is
+/// too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn try_par_simd_blur_srgb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	try_par_simd_blur_srgb_xy::<LANES>(buffer, radius, radius)
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with premultiplied
+/// alpha with SIMD in parallel.
+///
+/// This is a version of [`par_simd_blur`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Each pixel is converted to straight alpha, decoded to linear light, and
+/// premultiplied again before it is blurred, and the reverse happens
+/// afterwards, so the colors are blended in linear light without transparent
+/// pixels bleeding into them. Straightening the pixels loses some precision at
+/// low alpha, as described by
+/// [`Argb::from_u32_srgb_premultiplied`](color::Argb::from_u32_srgb_premultiplied).
+/// For pixels with straight alpha, see [`par_simd_blur_srgb_straight`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_simd_blur_argb_premultiplied`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn par_simd_blur_srgb_premultiplied<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb_premultiplied(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb_premultiplied,
+			|i| ArgbU32::from_u32_srgb_premultiplied(*i), ArgbU32::to_u32_srgb_premultiplied
+		);
+	} else {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb_premultiplied(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb_premultiplied,
+			|i| ArgbU64::from_u32_srgb_premultiplied(*i), ArgbU64::to_u32_srgb_premultiplied
+		);
+	}
+}
+
+/// Like [`par_simd_blur_srgb_premultiplied`], but returns a [`BlurError`] if
+/// the radius is too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn try_par_simd_blur_srgb_premultiplied<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb_premultiplied(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb_premultiplied,
+			|i| ArgbU32::from_u32_srgb_premultiplied(*i), ArgbU32::to_u32_srgb_premultiplied
+		);
+	} else {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb_premultiplied(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb_premultiplied,
+			|i| ArgbU64::from_u32_srgb_premultiplied(*i), ArgbU64::to_u32_srgb_premultiplied
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with straight (not
+/// premultiplied) alpha with SIMD in parallel.
+///
+/// This is a version of [`par_simd_blur`] with pre-filled conversion routines.
+/// They use 32 bits per channel, or 64 bits when the radius is too large for
+/// that, which for a plain Stackblur means larger than [`SRGB_U32_MAX_RADIUS`].
+///
+/// Blurring straight alpha one channel at a time lets the color of transparent
+/// pixels, which is usually meaningless, bleed into the visible ones as dark or
+/// colored halos. This decodes every pixel to linear light and premultiplies it
+/// before it is blurred, and converts it back to straight alpha and sRGB
+/// afterwards, which prevents that. Pixels that come out fully transparent are
+/// black.
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_simd_blur_argb_straight`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn par_simd_blur_srgb_straight<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb_straight(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb_straight,
+			|i| ArgbU32::from_u32_srgb_straight(*i), ArgbU32::to_u32_srgb_straight
+		);
+	} else {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb_straight(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb_straight,
+			|i| ArgbU64::from_u32_srgb_straight(*i), ArgbU64::to_u32_srgb_straight
+		);
+	}
+}
+
+/// Like [`par_simd_blur_srgb_straight`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn try_par_simd_blur_srgb_straight<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb_straight(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb_straight,
+			|i| ArgbU32::from_u32_srgb_straight(*i), ArgbU32::to_u32_srgb_straight
+		);
+	} else {
+		par_simd_blur(buffer, radius,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb_straight(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb_straight,
+			|i| ArgbU64::from_u32_srgb_straight(*i), ArgbU64::to_u32_srgb_straight
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs only the channels in `mask` of a buffer of 32-bit packed sRGB pixels
+/// (0xAARRGGBB) with SIMD in parallel, and leaves the others exactly as they
+/// were.
+///
+/// This is a version of [`par_simd_blur_srgb`] that only does the work for the
+/// channels that it blurs, so blurring just [`ChannelMask::ALPHA`], for example
+/// to make a shadow, takes about a quarter of the arithmetic. Like
+/// [`par_simd_blur_srgb`], it uses 32 bits per channel, or 64 bits when the
+/// radius is larger than [`SRGB_U32_MAX_RADIUS`]. An empty mask leaves the
+/// buffer untouched.
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_simd_blur_argb_masked`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn par_simd_blur_srgb_masked<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) where LaneCount<LANES>: SupportedLaneCount {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	if mask.is_empty() {
+		return;
+	}
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, par_simd_blur_masked::<StackBlurrableU32, StackBlurrableU32xN<LANES>, LANES>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	} else {
+		with_channel_count!(mask, par_simd_blur_masked::<StackBlurrableU64, StackBlurrableU64xN<LANES>, LANES>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	}
+}
+
+/// Like [`par_simd_blur_srgb_masked`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn try_par_simd_blur_srgb_masked<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, mask: ChannelMask) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	check_radius(SRGB_CHANNEL_MAX, radius, |radius| Kernel::Tent.total_weight(radius))?;
+
+	if mask.is_empty() {
+		return Ok(());
+	}
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+		with_channel_count!(mask, par_simd_blur_masked::<StackBlurrableU32, StackBlurrableU32xN<LANES>, LANES>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	} else {
+		with_channel_count!(mask, par_simd_blur_masked::<StackBlurrableU64, StackBlurrableU64xN<LANES>, LANES>(buffer, radius, mask, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16)));
+	}
+
+	Ok(())
+}
+
+/// Sharpens a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD in
+/// parallel with an unsharp mask.
+///
+/// Each channel in `mask` becomes `original + amount * (original - blurred)`,
+/// clamped to the range of the channel, where `blurred` is the result of
+/// [`par_simd_blur_srgb_masked`] with the given radius. Channels that differ
+/// from their blurred value by less than `threshold` are left alone, so that
+/// flat areas and noise are not sharpened. Pass [`ChannelMask::COLOR`] to keep
+/// alpha as it is.
+///
+/// The difference is compared to `threshold` in sRGB, but the channels are
+/// sharpened in linear light.
+///
+/// The buffer is copied and the copy is blurred, so this needs as much memory
+/// again as the buffer takes up.
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_simd_unsharp_mask_argb`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn par_simd_unsharp_mask_srgb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) where LaneCount<LANES>: SupportedLaneCount {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	let mut blurred = copy_argb(buffer);
+	par_simd_blur_srgb_masked::<LANES>(&mut blurred.as_mut(), radius, mask);
+	par_unsharp(buffer, blurred.as_ref(), amount, threshold, mask, SRGB_CHANNEL_MAX, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16));
+}
+
+/// Like [`par_simd_unsharp_mask_srgb`], but returns a [`BlurError`] if the
+/// radius is too large or `amount` is not finite. The buffer is only changed if
+/// this succeeds.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn try_par_simd_unsharp_mask_srgb<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius: usize, amount: f32, threshold: u8, mask: ChannelMask) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	use blend_srgb::convert::{rgb12_to_srgb8, srgb8_to_rgb12};
+
+	check_amount(amount)?;
+
+	let mut blurred = copy_argb(buffer);
+	try_par_simd_blur_srgb_masked::<LANES>(&mut blurred.as_mut(), radius, mask)?;
+	par_unsharp(buffer, blurred.as_ref(), amount, threshold, mask, SRGB_CHANNEL_MAX, |i| srgb8_to_rgb12(i) as u32, |i| rgb12_to_srgb8(i as u16));
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD in
+/// parallel with separate horizontal and vertical radii.
+///
+/// This is a version of [`par_simd_blur_xy`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_simd_blur_argb_xy`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn par_simd_blur_srgb_xy<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		par_simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+}
+
+/// Like [`par_simd_blur_srgb_xy`], but returns a [`BlurError`] if the radius is
+/// too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn try_par_simd_blur_srgb_xy<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(SRGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		par_simd_blur_xy(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD in
+/// parallel with separate horizontal and vertical radii and a custom
+/// [`EdgeMode`].
+///
+/// This is a version of [`par_simd_blur_with_edge_mode`] with pre-filled
+/// conversion routines. They use 32 bits per channel, or 64 bits when the
+/// radius is too large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_simd_blur_argb_with_edge_mode`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn par_simd_blur_srgb_with_edge_mode<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		par_simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+}
+
+/// Like [`par_simd_blur_srgb_with_edge_mode`], but returns a [`BlurError`] if
+/// the radius is too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn try_par_simd_blur_srgb_with_edge_mode<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, edge_mode: EdgeMode) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(SRGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| Kernel::Tent.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius_x.max(radius_y))) {
+		par_simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		par_simd_blur_with_edge_mode(buffer, radius_x, radius_y, edge_mode,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD in
+/// parallel with fractional horizontal and vertical radii.
+///
+/// This is a version of [`par_simd_blur_fractional`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_simd_blur_argb_fractional`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn par_simd_blur_srgb_fractional<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		par_simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		par_simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+}
+
+/// Like [`par_simd_blur_srgb_fractional`], but returns a [`BlurError`] if the
+/// radius is too large or a parameter is not supported.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn try_par_simd_blur_srgb_fractional<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: f32, radius_y: f32) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_finite("radius_x", radius_x)?;
+	check_finite("radius_y", radius_y)?;
+	check_radius(SRGB_CHANNEL_MAX, upper_radius(radius_x.max(radius_y)), fractional_weight)?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(radius_x.max(radius_y)))) {
+		try_par_simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		)
+	} else {
+		try_par_simd_blur_fractional(buffer, radius_x, radius_y,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		)
+	}
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD in
+/// parallel with the given [`Kernel`] shape and horizontal and vertical radii.
+///
+/// This is a version of [`par_simd_blur_kernel`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_simd_blur_argb_kernel`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn par_simd_blur_srgb_kernel<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		par_simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		par_simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+}
+
+/// Like [`par_simd_blur_srgb_kernel`], but returns a [`BlurError`] if the
+/// radius is too large.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn try_par_simd_blur_srgb_kernel<const LANES: usize>(buffer: &mut ImgRefMut<u32>, radius_x: usize, radius_y: usize, kernel: Kernel) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_radius(SRGB_CHANNEL_MAX, radius_x.max(radius_y), |radius| kernel.total_weight(radius))?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, kernel.total_weight(radius_x.max(radius_y))) {
+		par_simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		par_simd_blur_kernel(buffer, radius_x, radius_y, kernel,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+
+	Ok(())
+}
+
+/// Blurs a buffer of 32-bit packed sRGB pixels (0xAARRGGBB) with SIMD in
+/// parallel with an approximation of a Gaussian blur.
+///
+/// This is a version of [`par_simd_blur_gaussian`] with pre-filled conversion
+/// routines. They use 32 bits per channel, or 64 bits when the radius is too
+/// large for that, which for a plain Stackblur means larger than
+/// [`SRGB_U32_MAX_RADIUS`].
+///
+/// Note that this function uses *sRGB*. For linear, see
+/// [`par_simd_blur_argb_gaussian`].
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn par_simd_blur_srgb_gaussian<const LANES: usize>(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) where LaneCount<LANES>: SupportedLaneCount {
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		par_simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		);
+	} else {
+		par_simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		);
+	}
+}
+
+/// Like [`par_simd_blur_srgb_gaussian`], but returns a [`BlurError`] if the
+/// radius is too large or a parameter is not supported.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd", feature = "blend-srgb")))]
+pub fn try_par_simd_blur_srgb_gaussian<const LANES: usize>(buffer: &mut ImgRefMut<u32>, sigma_x: f32, sigma_y: f32, passes: usize) -> Result<(), BlurError> where LaneCount<LANES>: SupportedLaneCount {
+	check_finite("sigma_x", sigma_x)?;
+	check_finite("sigma_y", sigma_y)?;
+	check_radius(SRGB_CHANNEL_MAX, upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)), fractional_weight)?;
+
+	if fits_u32(SRGB_CHANNEL_MAX, fractional_weight(upper_radius(sigma_to_radius(sigma_x.max(sigma_y), passes)))) {
+		try_par_simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU32xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU32xN::to_u32xN_srgb,
+			|i| ArgbU32::from_u32_srgb(*i), ArgbU32::to_u32_srgb
+		)
+	} else {
+		try_par_simd_blur_gaussian(buffer, sigma_x, sigma_y, passes,
+			|i: [&u32; LANES]| ArgbU64xN::from_u32xN_srgb(i.map(u32::clone)), ArgbU64xN::to_u32xN_srgb,
+			|i| ArgbU64::from_u32_srgb(*i), ArgbU64::to_u32_srgb
+		)
+	}
 }
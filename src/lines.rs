@@ -0,0 +1,1063 @@
+//! The machinery shared by the 2D helpers in the crate root.
+//!
+//! Every 2D helper blurs all the rows of a buffer and then all of its columns,
+//! one line at a time. The only thing that differs between them is what they
+//! run over each line, which is described by a [`LineBlur`].
+
+use std::collections::VecDeque;
+use std::ops::{Add, AddAssign, Div, Mul, SubAssign};
+#[cfg(any(doc, feature = "simd"))]
+use std::simd::{LaneCount, SupportedLaneCount};
+
+use imgref::{ImgRef, ImgRefMut, ImgVec};
+
+use crate::traits::StackBlurrable;
+use crate::iter::{BlurPair, EdgeMode, FractionalStackBlur, Kernel, KernelBlur, Morphology, MultiStackBlur, StackBlur, VariableStackBlur, WeightedBlur};
+
+/// Something that can blur a single line of a buffer.
+pub trait LineBlur<B> {
+	/// Scratch space that is carried from one line to the next.
+	type Ops: Default;
+
+	/// Returns whether blurring a line would leave it untouched, in which case
+	/// the whole pass is skipped.
+	fn is_identity(&self) -> bool;
+
+	/// Blurs the items of `read`, handing each result to `write` in order.
+	fn blur_line(&self, read: impl Iterator<Item = B>, write: impl FnMut(B), ops: &mut Self::Ops);
+}
+
+/// A plain [`StackBlur`].
+pub struct Stack {
+	pub radius: usize,
+	pub edge_mode: EdgeMode
+}
+
+impl<B: StackBlurrable> LineBlur<B> for Stack {
+	type Ops = VecDeque<B>;
+
+	fn is_identity(&self) -> bool {
+		self.radius == 0
+	}
+
+	fn blur_line(&self, read: impl Iterator<Item = B>, write: impl FnMut(B), ops: &mut Self::Ops) {
+		let mut blur = StackBlur::with_edge_mode(read, self.radius, self.edge_mode, std::mem::take(ops));
+		blur.by_ref().for_each(write);
+		*ops = blur.into_ops();
+	}
+}
+
+/// A [`FractionalStackBlur`].
+pub struct Fractional {
+	pub radius: f32,
+	pub edge_mode: EdgeMode
+}
+
+impl<B: StackBlurrable> LineBlur<B> for Fractional {
+	type Ops = [VecDeque<B>; 2];
+
+	fn is_identity(&self) -> bool {
+		crate::iter::split_radius(self.radius) == (0, 0)
+	}
+
+	fn blur_line(&self, read: impl Iterator<Item = B>, write: impl FnMut(B), ops: &mut Self::Ops) {
+		let mut blur = FractionalStackBlur::with_edge_mode(read, self.radius, self.edge_mode, std::mem::take(ops));
+		blur.by_ref().for_each(write);
+		*ops = blur.into_ops();
+	}
+}
+
+/// A [`KernelBlur`].
+pub struct Shaped {
+	pub radius: usize,
+	pub kernel: Kernel
+}
+
+impl<B: StackBlurrable> LineBlur<B> for Shaped {
+	type Ops = VecDeque<(B, usize)>;
+
+	fn is_identity(&self) -> bool {
+		self.radius == 0
+	}
+
+	fn blur_line(&self, read: impl Iterator<Item = B>, write: impl FnMut(B), ops: &mut Self::Ops) {
+		let mut blur = KernelBlur::new(read, self.radius, self.kernel, std::mem::take(ops));
+		blur.by_ref().for_each(write);
+		*ops = blur.into_ops();
+	}
+}
+
+/// Several rounds of [`FractionalStackBlur`], approximating a Gaussian blur.
+pub struct Gaussian {
+	pub radius: f32,
+	pub passes: usize
+}
+
+impl<B: StackBlurrable> LineBlur<B> for Gaussian {
+	type Ops = ([VecDeque<B>; 2], Vec<B>, Vec<B>);
+
+	fn is_identity(&self) -> bool {
+		self.passes == 0 || crate::iter::split_radius(self.radius) == (0, 0)
+	}
+
+	fn blur_line(&self, read: impl Iterator<Item = B>, write: impl FnMut(B), ops: &mut Self::Ops) {
+		let (ops, line, next) = ops;
+
+		// Every pass but the last goes through a scratch buffer, so that the
+		// line is only converted from and to pixels once.
+		line.clear();
+		line.extend(read);
+
+		for _ in 1..self.passes {
+			let mut blur = FractionalStackBlur::new(line.drain(..), self.radius, std::mem::take(ops));
+			next.extend(&mut blur);
+			*ops = blur.into_ops();
+			std::mem::swap(line, next);
+		}
+
+		let mut blur = FractionalStackBlur::new(line.drain(..), self.radius, std::mem::take(ops));
+		blur.by_ref().for_each(write);
+		*ops = blur.into_ops();
+	}
+}
+
+/// The number of adjacent columns that the column passes of [`blur_lines`] and
+/// [`par_blur_lines`] blur together. With 4-byte pixels, this is a cache line.
+const COLUMN_BLOCK: usize = 16;
+
+/// The largest [`ColumnBlock`], in bytes, that the column passes use. A blur
+/// keeps about `radius * 2` of them around, so with [`StackBlurrable`] types
+/// larger than 16 bytes, the blocks take more room in the cache than they
+/// save, and the columns are blurred one at a time instead.
+const MAX_COLUMN_BLOCK_SIZE: usize = 256;
+
+/// Returns whether the column passes blur columns of `B` in [`ColumnBlock`]s.
+fn use_column_blocks<B>() -> bool {
+	std::mem::size_of::<ColumnBlock<B>>() <= MAX_COLUMN_BLOCK_SIZE
+}
+
+/// The pixels of a row within a block of [`COLUMN_BLOCK`] adjacent columns.
+///
+/// This is [`StackBlurrable`] one lane at a time, so blurring a line of these
+/// does exactly the same thing to each lane as blurring its column by itself
+/// would, but walks down all of the columns in the block together.
+#[derive(Clone)]
+pub struct ColumnBlock<B>([B; COLUMN_BLOCK]);
+
+impl<B: Default> Default for ColumnBlock<B> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<B: StackBlurrable> Add for ColumnBlock<B> {
+	type Output = Self;
+
+	fn add(mut self, rhs: Self) -> Self {
+		self += rhs;
+		self
+	}
+}
+
+impl<B: StackBlurrable> AddAssign for ColumnBlock<B> {
+	fn add_assign(&mut self, rhs: Self) {
+		self.0.iter_mut().zip(rhs.0).for_each(|(lane, rhs)| *lane += rhs);
+	}
+}
+
+impl<B: StackBlurrable> SubAssign for ColumnBlock<B> {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.0.iter_mut().zip(rhs.0).for_each(|(lane, rhs)| *lane -= rhs);
+	}
+}
+
+impl<B: StackBlurrable> Mul<usize> for ColumnBlock<B> {
+	type Output = Self;
+
+	fn mul(self, rhs: usize) -> Self {
+		Self(self.0.map(|lane| lane * rhs))
+	}
+}
+
+impl<B: StackBlurrable> Div<usize> for ColumnBlock<B> {
+	type Output = Self;
+
+	fn div(self, rhs: usize) -> Self {
+		Self(self.0.map(|lane| lane / rhs))
+	}
+}
+
+/// Blurs the `lanes` columns of `buffer` starting at `x`, which must be at most
+/// [`COLUMN_BLOCK`] of them, as a single line of [`ColumnBlock`]s.
+///
+/// Walking down a single column touches a different cache line for every
+/// pixel, while the pixels of a block share them.
+///
+/// # Safety
+///
+/// `buffer` must point to the first pixel of an image with the given `height`
+/// and `stride` that is at least `x + lanes` pixels wide, and nothing else may
+/// access those columns at the same time.
+#[allow(clippy::too_many_arguments)]
+unsafe fn blur_column_block<T, B: StackBlurrable, L: LineBlur<ColumnBlock<B>>>(
+	buffer: *mut T,
+	stride: usize,
+	height: usize,
+	x: usize,
+	lanes: usize,
+	line: &L,
+	mut to_blurrable: impl FnMut(&T) -> B,
+	mut to_pixel: impl FnMut(B, &T) -> T,
+	ops: &mut L::Ops
+) {
+	let row = move |y: usize| buffer.add(y * stride + x);
+
+	// No blur yields an item before reading the one at the same position, so
+	// every row of the block is read before it is written.
+	let read = (0..height).map(|y| {
+		let row = row(y);
+		ColumnBlock(std::array::from_fn(|lane| if lane < lanes { to_blurrable(&*row.add(lane)) } else { B::default() }))
+	});
+
+	let mut y = 0;
+	line.blur_line(read, |ColumnBlock(pixels)| {
+		let row = row(y);
+		pixels.into_iter().take(lanes).enumerate().for_each(|(lane, pixel)| {
+			let place = row.add(lane);
+			*place = to_pixel(pixel, &*place);
+		});
+		y += 1;
+	}, ops);
+}
+
+/// Blurs column `x` of `buffer` by itself, for [`StackBlurrable`] types that
+/// are too large for [`ColumnBlock`]s.
+///
+/// # Safety
+///
+/// The same as for [`blur_column_block`], with `lanes` being 1.
+#[allow(clippy::too_many_arguments)]
+pub unsafe fn blur_column<T, B: StackBlurrable, L: LineBlur<B>>(
+	buffer: *mut T,
+	stride: usize,
+	height: usize,
+	x: usize,
+	line: &L,
+	mut to_blurrable: impl FnMut(&T) -> B,
+	mut to_pixel: impl FnMut(B, &T) -> T,
+	ops: &mut L::Ops
+) {
+	let pixel = move |y: usize| buffer.add(y * stride + x);
+
+	let mut y = 0;
+	line.blur_line((0..height).map(|y| to_blurrable(&*pixel(y))), |blurred| {
+		let place = pixel(y);
+		*place = to_pixel(blurred, &*place);
+		y += 1;
+	}, ops);
+}
+
+pub fn blur_lines<T, B: StackBlurrable, L: LineBlur<B> + LineBlur<ColumnBlock<B>>>(
+	buffer: &mut ImgRefMut<T>,
+	rows: &L,
+	cols: &L,
+	to_blurrable: impl FnMut(&T) -> B,
+	mut to_pixel: impl FnMut(B) -> T
+) {
+	blur_lines_onto(buffer, rows, cols, to_blurrable, |pixel, _| to_pixel(pixel));
+}
+
+/// Like [`blur_lines`], but `to_pixel` also gets the pixel that it replaces, so
+/// that it can keep parts of it.
+pub fn blur_lines_onto<T, B: StackBlurrable, L: LineBlur<B> + LineBlur<ColumnBlock<B>>>(
+	buffer: &mut ImgRefMut<T>,
+	rows: &L,
+	cols: &L,
+	mut to_blurrable: impl FnMut(&T) -> B,
+	mut to_pixel: impl FnMut(B, &T) -> T
+) {
+	use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut};
+
+	if !LineBlur::<B>::is_identity(rows) {
+		let mut ops = <L as LineBlur<B>>::Ops::default();
+		let buffer_ptr = buffer.as_mut_ptr();
+
+		for (mut write, read) in unsafe { buffer_ptr.iter_rows_ptr_mut() }.zip(buffer.iter_rows()) {
+			rows.blur_line(read.map(&mut to_blurrable), |pixel| unsafe {
+				let place = write.next().unwrap();
+				*place = to_pixel(pixel, &*place);
+			}, &mut ops);
+		}
+	}
+
+	// The columns are blurred through a pointer taken only after the rows have
+	// been written, so that those writes cannot invalidate it.
+	if !LineBlur::<B>::is_identity(cols) {
+		let (width, height, stride) = (buffer.width(), buffer.height(), buffer.stride());
+		let buffer_ptr = buffer.buf_mut().as_mut_ptr();
+
+		if use_column_blocks::<B>() {
+			let mut ops = <L as LineBlur<ColumnBlock<B>>>::Ops::default();
+
+			for x in (0..width).step_by(COLUMN_BLOCK) {
+				unsafe { blur_column_block(buffer_ptr, stride, height, x, COLUMN_BLOCK.min(width - x), cols, &mut to_blurrable, &mut to_pixel, &mut ops) };
+			}
+		} else {
+			let mut ops = <L as LineBlur<B>>::Ops::default();
+
+			for x in 0..width {
+				unsafe { blur_column(buffer_ptr, stride, height, x, cols, &mut to_blurrable, &mut to_pixel, &mut ops) };
+			}
+		}
+	}
+}
+
+/// Returns the scratch space that `opses` holds for the current thread, or
+/// `fallback` filled with fresh scratch space if the current thread has none.
+///
+/// `opses` has room for every thread of the current Rayon thread pool, plus one
+/// more at the end for the thread that called into it, since Rayon runs jobs
+/// that are too small to split up on that thread directly. A line that somehow
+/// ends up on any other thread still gets blurred, just without reusing the
+/// allocations of the lines before it.
+///
+/// # Safety
+///
+/// `opses` must point to `len` elements that outlive the returned reference,
+/// and no other thread may access the element for this thread at the same time.
+#[cfg(any(doc, feature = "rayon"))]
+unsafe fn worker_scratch<O: Default>(opses: *mut Option<O>, len: usize, fallback: &mut Option<O>) -> &mut Option<O> {
+	match rayon::current_thread_index() {
+		Some(index) if index < len - 1 => &mut *opses.add(index),
+		Some(_) => {
+			*fallback = Some(O::default());
+			fallback
+		}
+		None => &mut *opses.add(len - 1)
+	}
+}
+
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_lines<T: Send + Sync, B: StackBlurrable + Send + Sync, L: LineBlur<B> + LineBlur<ColumnBlock<B>> + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	rows: &L,
+	cols: &L,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) where <L as LineBlur<B>>::Ops: Send + Sync, <L as LineBlur<ColumnBlock<B>>>::Ops: Send + Sync {
+	par_blur_lines_onto(buffer, rows, cols, to_blurrable, |pixel, _| to_pixel(pixel))
+}
+
+/// Like [`par_blur_lines`], but `to_pixel` also gets the pixel that it
+/// replaces, so that it can keep parts of it.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_lines_onto<T: Send + Sync, B: StackBlurrable + Send + Sync, L: LineBlur<B> + LineBlur<ColumnBlock<B>> + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	rows: &L,
+	cols: &L,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B, &T) -> T + Sync
+) where <L as LineBlur<B>>::Ops: Send + Sync, <L as LineBlur<ColumnBlock<B>>>::Ops: Send + Sync {
+	use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut};
+	#[cfg(not(doc))]
+	use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelBridge, ParallelIterator};
+
+	// See `blur_lines` for why the columns are blurred through their own pointer.
+	if !LineBlur::<B>::is_identity(rows) {
+		let mut opses: Vec<Option<<L as LineBlur<B>>::Ops>> = std::iter::repeat_with(|| Some(Default::default())).take(rayon::current_num_threads() + 1).collect();
+		let threads = opses.len();
+		let opses_ptr = unsafe { unique::Unique::new_unchecked(opses.as_mut_ptr()) };
+		let buffer_ptr = buffer.as_mut_ptr();
+
+		unsafe { buffer_ptr.iter_rows_ptr_mut() }.zip(buffer.iter_rows()).par_bridge().for_each(|(mut write, read)| {
+			let mut fallback = None;
+			let ops_ref = unsafe { worker_scratch(opses_ptr.as_ptr(), threads, &mut fallback) };
+
+			let mut ops = ops_ref.take().unwrap();
+			rows.blur_line(read.map(&to_blurrable), |pixel| unsafe {
+				let place = write.next().unwrap();
+				*place = to_pixel(pixel, &*place);
+			}, &mut ops);
+			ops_ref.replace(ops);
+		});
+	}
+
+	if !LineBlur::<B>::is_identity(cols) {
+		let (width, height, stride) = (buffer.width(), buffer.height(), buffer.stride());
+		let buffer_ptr = unsafe { unique::Unique::new_unchecked(buffer.buf_mut().as_mut_ptr()) };
+
+		if use_column_blocks::<B>() {
+			let mut opses: Vec<Option<<L as LineBlur<ColumnBlock<B>>>::Ops>> = std::iter::repeat_with(|| Some(Default::default())).take(rayon::current_num_threads() + 1).collect();
+			let threads = opses.len();
+			let opses_ptr = unsafe { unique::Unique::new_unchecked(opses.as_mut_ptr()) };
+
+			(0..width).into_par_iter().step_by(COLUMN_BLOCK).for_each(|x| {
+				let mut fallback = None;
+				let ops_ref = unsafe { worker_scratch(opses_ptr.as_ptr(), threads, &mut fallback) };
+
+				let mut ops = ops_ref.take().unwrap();
+				unsafe { blur_column_block(buffer_ptr.as_ptr(), stride, height, x, COLUMN_BLOCK.min(width - x), cols, &to_blurrable, &to_pixel, &mut ops) };
+				ops_ref.replace(ops);
+			});
+		} else {
+			let mut opses: Vec<Option<<L as LineBlur<B>>::Ops>> = std::iter::repeat_with(|| Some(Default::default())).take(rayon::current_num_threads() + 1).collect();
+			let threads = opses.len();
+			let opses_ptr = unsafe { unique::Unique::new_unchecked(opses.as_mut_ptr()) };
+
+			(0..width).into_par_iter().for_each(|x| {
+				let mut fallback = None;
+				let ops_ref = unsafe { worker_scratch(opses_ptr.as_ptr(), threads, &mut fallback) };
+
+				let mut ops = ops_ref.take().unwrap();
+				unsafe { blur_column(buffer_ptr.as_ptr(), stride, height, x, cols, &to_blurrable, &to_pixel, &mut ops) };
+				ops_ref.replace(ops);
+			});
+		}
+	}
+}
+
+/// Blurs `input` by two radii, and writes the difference between the narrow
+/// and the wide blur of every pixel to `output`. `to_output` also gets the
+/// pixel of `input` at the same position.
+///
+/// The rows are blurred by both radii in a single scan of the input, through a
+/// [`BlurPair`], into two planes whose columns are then blurred separately.
+pub fn blur_lines_difference<T, S, B: StackBlurrable>(
+	input: &ImgRef<T>,
+	output: &mut ImgRefMut<S>,
+	radius_narrow: usize,
+	radius_wide: usize,
+	mut to_blurrable: impl FnMut(&T) -> B,
+	mut to_output: impl FnMut(B, &T) -> S
+) {
+	assert_eq!((input.width(), input.height()), (output.width(), output.height()), "the input must be the same size as the output");
+
+	let (width, height) = (input.width(), input.height());
+	let (mut narrow, mut wide) = (Vec::with_capacity(width * height), Vec::with_capacity(width * height));
+	let mut ops = Default::default();
+
+	for read in input.rows() {
+		let mut blur = BlurPair::with_edge_mode(read.iter().map(&mut to_blurrable), [radius_narrow, radius_wide], EdgeMode::Renormalize, std::mem::take(&mut ops));
+		blur.by_ref().for_each(|(pixel_narrow, pixel_wide)| {
+			narrow.push(pixel_narrow);
+			wide.push(pixel_wide);
+		});
+		ops = blur.into_ops();
+	}
+
+	let (mut narrow, mut wide) = (ImgVec::new(narrow, width, height), ImgVec::new(wide, width, height));
+	let rows = Stack { radius: 0, edge_mode: EdgeMode::Renormalize };
+	blur_lines(&mut narrow.as_mut(), &rows, &Stack { radius: radius_narrow, edge_mode: EdgeMode::Renormalize }, B::clone, |pixel| pixel);
+	blur_lines(&mut wide.as_mut(), &rows, &Stack { radius: radius_wide, edge_mode: EdgeMode::Renormalize }, B::clone, |pixel| pixel);
+
+	for (((write, read), narrow), wide) in output.rows_mut().zip(input.rows()).zip(narrow.rows()).zip(wide.rows()) {
+		for (((place, pixel), narrow), wide) in write.iter_mut().zip(read).zip(narrow).zip(wide) {
+			let mut difference = narrow.clone();
+			difference -= wide.clone();
+			*place = to_output(difference, pixel);
+		}
+	}
+}
+
+/// Like [`blur_lines_difference`], but in parallel.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_lines_difference<T: Sync, S: Send, B: StackBlurrable + Send + Sync>(
+	input: &ImgRef<T>,
+	output: &mut ImgRefMut<S>,
+	radius_narrow: usize,
+	radius_wide: usize,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_output: impl Fn(B, &T) -> S + Sync
+) {
+	#[cfg(not(doc))]
+	use rayon::iter::{ParallelBridge, ParallelIterator};
+
+	assert_eq!((input.width(), input.height()), (output.width(), output.height()), "the input must be the same size as the output");
+
+	let (width, height) = (input.width(), input.height());
+	let mut narrow = ImgVec::new(vec![B::default(); width * height], width, height);
+	let mut wide = ImgVec::new(vec![B::default(); width * height], width, height);
+
+	let mut opses: Vec<Option<[VecDeque<B>; 2]>> = std::iter::repeat_with(|| Some(Default::default())).take(rayon::current_num_threads() + 1).collect();
+	let threads = opses.len();
+	let opses_ptr = unsafe { unique::Unique::new_unchecked(opses.as_mut_ptr()) };
+
+	narrow.rows_mut().zip(wide.rows_mut()).zip(input.rows()).par_bridge().for_each(|((write_narrow, write_wide), read)| {
+		let mut fallback = None;
+		let ops_ref = unsafe { worker_scratch(opses_ptr.as_ptr(), threads, &mut fallback) };
+
+		let mut blur = BlurPair::with_edge_mode(read.iter().map(&to_blurrable), [radius_narrow, radius_wide], EdgeMode::Renormalize, ops_ref.take().unwrap());
+		write_narrow.iter_mut().zip(write_wide.iter_mut()).zip(blur.by_ref()).for_each(|((place_narrow, place_wide), (pixel_narrow, pixel_wide))| {
+			*place_narrow = pixel_narrow;
+			*place_wide = pixel_wide;
+		});
+		ops_ref.replace(blur.into_ops());
+	});
+
+	let rows = Stack { radius: 0, edge_mode: EdgeMode::Renormalize };
+	par_blur_lines(&mut narrow.as_mut(), &rows, &Stack { radius: radius_narrow, edge_mode: EdgeMode::Renormalize }, B::clone, |pixel| pixel);
+	par_blur_lines(&mut wide.as_mut(), &rows, &Stack { radius: radius_wide, edge_mode: EdgeMode::Renormalize }, B::clone, |pixel| pixel);
+
+	output.rows_mut().zip(input.rows()).zip(narrow.rows()).zip(wide.rows()).par_bridge().for_each(|(((write, read), narrow), wide)| {
+		for (((place, pixel), narrow), wide) in write.iter_mut().zip(read).zip(narrow).zip(wide) {
+			let mut difference = narrow.clone();
+			difference -= wide.clone();
+			*place = to_output(difference, pixel);
+		}
+	});
+}
+
+/// Blurs `input` by every radius in `radii`, and writes the blur by each one
+/// to the output at the same index.
+///
+/// The rows are blurred by all of the radii in a single scan of the input,
+/// through a [`MultiStackBlur`], into a plane for every radius, whose columns
+/// are then blurred separately. The planes share a single buffer, in which
+/// every row of the input is followed by that row of each plane in turn.
+pub fn blur_lines_multi<T, S, B: StackBlurrable, const N: usize>(
+	input: &ImgRef<T>,
+	outputs: [&mut ImgRefMut<S>; N],
+	radii: [usize; N],
+	mut to_blurrable: impl FnMut(&T) -> B,
+	mut to_output: impl FnMut(B) -> S
+) {
+	let (width, height) = (input.width(), input.height());
+
+	for output in &outputs {
+		assert_eq!((width, height), (output.width(), output.height()), "the input must be the same size as every output");
+	}
+
+	if width == 0 || height == 0 || N == 0 {
+		return;
+	}
+
+	let mut planes = vec![B::default(); width * height * N];
+	let mut ops = VecDeque::new();
+
+	for (write, read) in planes.chunks_mut(width * N).zip(input.rows()) {
+		let mut blur = MultiStackBlur::new(read.iter().map(&mut to_blurrable), radii, std::mem::take(&mut ops));
+		blur.by_ref().enumerate().for_each(|(x, pixels)| pixels.into_iter().enumerate().for_each(|(n, pixel)| write[n * width + x] = pixel));
+		ops = blur.into_ops();
+	}
+
+	let rows = Stack { radius: 0, edge_mode: EdgeMode::Renormalize };
+
+	for (n, (output, radius)) in outputs.into_iter().zip(radii).enumerate() {
+		let mut plane = ImgRefMut::new_stride(&mut planes[n * width..], width, height, width * N);
+		blur_lines(&mut plane, &rows, &Stack { radius, edge_mode: EdgeMode::Renormalize }, B::clone, |pixel| pixel);
+
+		for (write, read) in output.rows_mut().zip(plane.rows()) {
+			write.iter_mut().zip(read).for_each(|(place, pixel)| *place = to_output(pixel.clone()));
+		}
+	}
+}
+
+/// Like [`blur_lines_multi`], but in parallel.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_lines_multi<T: Sync, S: Send, B: StackBlurrable + Send + Sync, const N: usize>(
+	input: &ImgRef<T>,
+	outputs: [&mut ImgRefMut<S>; N],
+	radii: [usize; N],
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_output: impl Fn(B) -> S + Sync
+) {
+	#[cfg(not(doc))]
+	use rayon::iter::{ParallelBridge, ParallelIterator};
+
+	let (width, height) = (input.width(), input.height());
+
+	for output in &outputs {
+		assert_eq!((width, height), (output.width(), output.height()), "the input must be the same size as every output");
+	}
+
+	if width == 0 || height == 0 || N == 0 {
+		return;
+	}
+
+	let mut planes = vec![B::default(); width * height * N];
+
+	let mut opses: Vec<Option<VecDeque<B>>> = std::iter::repeat_with(|| Some(VecDeque::new())).take(rayon::current_num_threads() + 1).collect();
+	let threads = opses.len();
+	let opses_ptr = unsafe { unique::Unique::new_unchecked(opses.as_mut_ptr()) };
+
+	planes.chunks_mut(width * N).zip(input.rows()).par_bridge().for_each(|(write, read)| {
+		let mut fallback = None;
+		let ops_ref = unsafe { worker_scratch(opses_ptr.as_ptr(), threads, &mut fallback) };
+
+		let mut blur = MultiStackBlur::new(read.iter().map(&to_blurrable), radii, ops_ref.take().unwrap());
+		blur.by_ref().enumerate().for_each(|(x, pixels)| pixels.into_iter().enumerate().for_each(|(n, pixel)| write[n * width + x] = pixel));
+		ops_ref.replace(blur.into_ops());
+	});
+
+	let rows = Stack { radius: 0, edge_mode: EdgeMode::Renormalize };
+
+	for (n, (output, radius)) in outputs.into_iter().zip(radii).enumerate() {
+		let mut plane = ImgRefMut::new_stride(&mut planes[n * width..], width, height, width * N);
+		par_blur_lines(&mut plane, &rows, &Stack { radius, edge_mode: EdgeMode::Renormalize }, B::clone, |pixel| pixel);
+
+		output.rows_mut().zip(plane.rows()).par_bridge().for_each(|(write, read)| {
+			write.iter_mut().zip(read).for_each(|(place, pixel)| *place = to_output(pixel.clone()));
+		});
+	}
+}
+
+/// Like [`blur_lines`], but every pixel is blurred by a [`VariableStackBlur`]
+/// with the radius at the same position in `radii`.
+pub fn blur_lines_variable<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	radii: &ImgRef<u16>,
+	mut to_blurrable: impl FnMut(&T) -> B,
+	mut to_pixel: impl FnMut(B) -> T
+) {
+	use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut};
+	use imgref_iter::iter::{IterWindows, IterWindowsPtrMut};
+
+	assert_eq!((radii.width(), radii.height()), (buffer.width(), buffer.height()), "the radius map must be the same size as the buffer");
+
+	let mut ops = Vec::new();
+
+	// This is needed to avoid Undefined Behavior. Writing to the rows of the
+	// must be done before constructing the columns iterators, because otherwise
+	// the writes would invalidate their borrows. However I don't want to
+	// duplicate this loop, so make it a closure.
+	let mut blur_windows = |writer: IterWindowsPtrMut<T>, reader: IterWindows<T>, radii: IterWindows<u16>, ops: &mut Vec<(B, usize)>| {
+		for ((mut write, read), radii) in writer.zip(reader).zip(radii) {
+			let mut blur = VariableStackBlur::new(read.map(&mut to_blurrable).zip(radii.map(|&radius| radius as usize)), std::mem::take(ops));
+			blur.by_ref().for_each(|pixel| unsafe { *write.next().unwrap() = to_pixel(pixel) });
+			*ops = blur.into_ops();
+		}
+	};
+
+	let buffer_ptr = buffer.as_mut_ptr();
+	blur_windows(unsafe { buffer_ptr.iter_rows_ptr_mut() }, buffer.iter_rows(), radii.iter_rows(), &mut ops);
+	blur_windows(unsafe { buffer_ptr.iter_cols_ptr_mut() }, buffer.iter_cols(), radii.iter_cols(), &mut ops);
+}
+
+/// Like [`par_blur_lines`], but every pixel is blurred by a
+/// [`VariableStackBlur`] with the radius at the same position in `radii`.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_lines_variable<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	radii: &ImgRef<u16>,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) {
+	use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut};
+	use imgref_iter::iter::{IterWindows, IterWindowsPtrMut};
+	#[cfg(not(doc))]
+	use rayon::iter::{ParallelBridge, ParallelIterator};
+
+	assert_eq!((radii.width(), radii.height()), (buffer.width(), buffer.height()), "the radius map must be the same size as the buffer");
+
+	let mut opses: Vec<Option<Vec<(B, usize)>>> = std::iter::repeat_with(|| Some(Vec::new())).take(rayon::current_num_threads() + 1).collect();
+	let threads = opses.len();
+	let opses_ptr = unsafe { unique::Unique::new_unchecked(opses.as_mut_ptr()) };
+
+	let par_blur_windows = |writer: IterWindowsPtrMut<T>, reader: IterWindows<T>, radii: IterWindows<u16>| {
+		writer.zip(reader).zip(radii).par_bridge().for_each(|((mut write, read), radii)| {
+			let mut fallback = None;
+			let ops_ref = unsafe { worker_scratch(opses_ptr.as_ptr(), threads, &mut fallback) };
+
+			let mut blur = VariableStackBlur::new(read.map(&to_blurrable).zip(radii.map(|&radius| radius as usize)), ops_ref.take().unwrap());
+			blur.by_ref().for_each(|pixel| unsafe { *write.next().unwrap() = to_pixel(pixel) });
+			ops_ref.replace(blur.into_ops());
+		});
+	};
+
+	let buffer_ptr = buffer.as_mut_ptr();
+	par_blur_windows(unsafe { buffer_ptr.iter_rows_ptr_mut() }, buffer.iter_rows(), radii.iter_rows());
+	par_blur_windows(unsafe { buffer_ptr.iter_cols_ptr_mut() }, buffer.iter_cols(), radii.iter_cols());
+}
+
+/// Writes the average of every pair of adjacent items of `read` to `write`,
+/// and the last item by itself if there is an odd number of them.
+///
+/// A [`StackBlur`] is not fused, so `read` isn't polled again once it has
+/// returned `None`.
+fn halve<B: StackBlurrable>(mut read: impl Iterator<Item = B>, mut write: impl FnMut(B)) {
+	while let Some(first) = read.next() {
+		match read.next() {
+			Some(second) => write((first + second) / 2),
+			None => return write(first)
+		}
+	}
+}
+
+/// Blurs `input` with a [`StackBlur`] of `radius` and writes it to `output` at
+/// half its size, rounded up, so that every pixel of `output` is the average of
+/// a 2×2 block of blurred pixels.
+///
+/// The rows are blurred and halved into `scratch` before the columns are, so
+/// the columns only have half as many pixels to go through.
+pub fn blur_halve<B: StackBlurrable>(input: &ImgRef<B>, output: &mut ImgRefMut<B>, radius: usize, scratch: &mut Vec<B>, ops: &mut VecDeque<B>) {
+	use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut};
+
+	let (width, height) = (output.width(), input.height());
+	scratch.clear();
+	scratch.resize(width * height, B::default());
+
+	for (write, read) in ImgRefMut::new(&mut scratch[..], width, height).rows_mut().zip(input.rows()) {
+		let mut write = write.iter_mut();
+		let mut blur = StackBlur::new(read.iter().cloned(), radius, std::mem::take(ops));
+		halve(&mut blur, |pixel| *write.next().unwrap() = pixel);
+		*ops = blur.into_ops();
+	}
+
+	let halved = ImgRef::new(&scratch[..], width, height);
+	let output_ptr = output.as_mut_ptr();
+
+	for (mut write, read) in unsafe { output_ptr.iter_cols_ptr_mut() }.zip(halved.iter_cols()) {
+		let mut blur = StackBlur::new(read.cloned(), radius, std::mem::take(ops));
+		halve(&mut blur, |pixel| unsafe { *write.next().unwrap() = pixel });
+		*ops = blur.into_ops();
+	}
+}
+
+/// Like [`blur_halve`], but in parallel, with the scratch space for every
+/// thread in `opses`, which is grown to fit the current Rayon thread pool.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_halve<B: StackBlurrable + Send + Sync>(
+	input: &ImgRef<B>,
+	output: &mut ImgRefMut<B>,
+	radius: usize,
+	scratch: &mut Vec<B>,
+	opses: &mut Vec<Option<VecDeque<B>>>
+) {
+	use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut};
+	#[cfg(not(doc))]
+	use rayon::iter::{ParallelBridge, ParallelIterator};
+
+	let (width, height) = (output.width(), input.height());
+	scratch.clear();
+	scratch.resize(width * height, B::default());
+
+	let threads = opses.len().max(rayon::current_num_threads() + 1);
+	opses.resize_with(threads, || Some(VecDeque::new()));
+	let opses_ptr = unsafe { unique::Unique::new_unchecked(opses.as_mut_ptr()) };
+
+	ImgRefMut::new(&mut scratch[..], width, height).rows_mut().zip(input.rows()).par_bridge().for_each(|(write, read)| {
+		let mut fallback = None;
+		let ops_ref = unsafe { worker_scratch(opses_ptr.as_ptr(), threads, &mut fallback) };
+
+		let mut write = write.iter_mut();
+		let mut blur = StackBlur::new(read.iter().cloned(), radius, ops_ref.take().unwrap());
+		halve(&mut blur, |pixel| *write.next().unwrap() = pixel);
+		ops_ref.replace(blur.into_ops());
+	});
+
+	let halved = ImgRef::new(&scratch[..], width, height);
+	let output_ptr = output.as_mut_ptr();
+
+	unsafe { output_ptr.iter_cols_ptr_mut() }.zip(halved.iter_cols()).par_bridge().for_each(|(mut write, read)| {
+		let mut fallback = None;
+		let ops_ref = unsafe { worker_scratch(opses_ptr.as_ptr(), threads, &mut fallback) };
+
+		let mut blur = StackBlur::new(read.cloned(), radius, ops_ref.take().unwrap());
+		halve(&mut blur, |pixel| unsafe { *write.next().unwrap() = pixel });
+		ops_ref.replace(blur.into_ops());
+	});
+}
+
+/// Like [`blur_lines`], but every pixel becomes the largest (if `GROW`) or the
+/// smallest value within `radius` of it, using a [`Morphology`].
+///
+/// Comparing values doesn't work across the lanes of a [`ColumnBlock`], so the
+/// columns are processed one at a time.
+pub fn morph_lines<T, V: PartialOrd + Clone, const GROW: bool>(
+	buffer: &mut ImgRefMut<T>,
+	radius: usize,
+	mut to_value: impl FnMut(&T) -> V,
+	mut to_pixel: impl FnMut(V) -> T
+) {
+	use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut};
+	use imgref_iter::iter::{IterWindows, IterWindowsPtrMut};
+
+	if radius == 0 {
+		return;
+	}
+
+	let mut ops = VecDeque::new();
+
+	// See `blur_lines_variable` for why this is a closure.
+	let mut morph_windows = |writer: IterWindowsPtrMut<T>, reader: IterWindows<T>, ops: &mut VecDeque<(V, usize)>| {
+		for (mut write, read) in writer.zip(reader) {
+			let mut morph = Morphology::<V, _, GROW>::new(read.map(&mut to_value), radius, std::mem::take(ops));
+			morph.by_ref().for_each(|pixel| unsafe { *write.next().unwrap() = to_pixel(pixel) });
+			*ops = morph.into_ops();
+		}
+	};
+
+	let buffer_ptr = buffer.as_mut_ptr();
+	morph_windows(unsafe { buffer_ptr.iter_rows_ptr_mut() }, buffer.iter_rows(), &mut ops);
+	morph_windows(unsafe { buffer_ptr.iter_cols_ptr_mut() }, buffer.iter_cols(), &mut ops);
+}
+
+/// Like [`par_blur_lines`], but every pixel becomes the largest (if `GROW`) or
+/// the smallest value within `radius` of it, using a [`Morphology`].
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_morph_lines<T: Send + Sync, V: PartialOrd + Clone + Send + Sync, const GROW: bool>(
+	buffer: &mut ImgRefMut<T>,
+	radius: usize,
+	to_value: impl Fn(&T) -> V + Sync,
+	to_pixel: impl Fn(V) -> T + Sync
+) {
+	use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut};
+	use imgref_iter::iter::{IterWindows, IterWindowsPtrMut};
+	#[cfg(not(doc))]
+	use rayon::iter::{ParallelBridge, ParallelIterator};
+
+	if radius == 0 {
+		return;
+	}
+
+	let mut opses: Vec<Option<VecDeque<(V, usize)>>> = std::iter::repeat_with(|| Some(VecDeque::new())).take(rayon::current_num_threads() + 1).collect();
+	let threads = opses.len();
+	let opses_ptr = unsafe { unique::Unique::new_unchecked(opses.as_mut_ptr()) };
+
+	let par_morph_windows = |writer: IterWindowsPtrMut<T>, reader: IterWindows<T>| {
+		writer.zip(reader).par_bridge().for_each(|(mut write, read)| {
+			let mut fallback = None;
+			let ops_ref = unsafe { worker_scratch(opses_ptr.as_ptr(), threads, &mut fallback) };
+
+			let mut morph = Morphology::<V, _, GROW>::new(read.map(&to_value), radius, ops_ref.take().unwrap());
+			morph.by_ref().for_each(|pixel| unsafe { *write.next().unwrap() = to_pixel(pixel) });
+			ops_ref.replace(morph.into_ops());
+		});
+	};
+
+	let buffer_ptr = buffer.as_mut_ptr();
+	par_morph_windows(unsafe { buffer_ptr.iter_rows_ptr_mut() }, buffer.iter_rows());
+	par_morph_windows(unsafe { buffer_ptr.iter_cols_ptr_mut() }, buffer.iter_cols());
+}
+
+/// Blurs every row and then every column of `buffer` with a [`WeightedBlur`] of
+/// `kernel`, taking the weights of the rows from `weights`, and the weights of
+/// the columns from the sums of weights that come out of the rows.
+pub fn blur_lines_weighted<T, B: StackBlurrable>(
+	buffer: &mut ImgRefMut<T>,
+	weights: &ImgRef<u8>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	mut to_blurrable: impl FnMut(&T) -> B,
+	mut to_pixel: impl FnMut(B) -> T
+) {
+	use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut};
+
+	assert_eq!((weights.width(), weights.height()), (buffer.width(), buffer.height()), "the weight map must be the same size as the buffer");
+
+	let mut ops = VecDeque::new();
+	let mut sums = ImgVec::new(vec![0usize; buffer.width() * buffer.height()], buffer.width(), buffer.height());
+
+	// The rows must be written before the column iterators are constructed,
+	// for the same reason as in `blur_lines`.
+	let buffer_ptr = buffer.as_mut_ptr();
+	let sums_ptr = sums.as_mut().as_mut_ptr();
+
+	for (((mut write, read), weights), mut write_sums) in unsafe { buffer_ptr.iter_rows_ptr_mut() }.zip(buffer.iter_rows()).zip(weights.iter_rows()).zip(unsafe { sums_ptr.iter_rows_ptr_mut() }) {
+		let mut blur = WeightedBlur::new(read.map(&mut to_blurrable).zip(weights.map(|&weight| weight as usize)), radius_x, kernel, std::mem::take(&mut ops));
+		blur.by_ref().for_each(|(pixel, weight)| unsafe {
+			*write.next().unwrap() = to_pixel(pixel);
+			*write_sums.next().unwrap() = weight;
+		});
+		ops = blur.into_ops();
+	}
+
+	for ((mut write, read), weights) in unsafe { buffer_ptr.iter_cols_ptr_mut() }.zip(buffer.iter_cols()).zip(sums.iter_cols()) {
+		let mut blur = WeightedBlur::new(read.map(&mut to_blurrable).zip(weights.copied()), radius_y, kernel, std::mem::take(&mut ops));
+		blur.by_ref().for_each(|(pixel, _)| unsafe { *write.next().unwrap() = to_pixel(pixel) });
+		ops = blur.into_ops();
+	}
+}
+
+/// Like [`blur_lines_weighted`], but in parallel.
+#[cfg(any(doc, feature = "rayon"))]
+pub fn par_blur_lines_weighted<T: Send + Sync, B: StackBlurrable + Send + Sync>(
+	buffer: &mut ImgRefMut<T>,
+	weights: &ImgRef<u8>,
+	radius_x: usize,
+	radius_y: usize,
+	kernel: Kernel,
+	to_blurrable: impl Fn(&T) -> B + Sync,
+	to_pixel: impl Fn(B) -> T + Sync
+) {
+	use imgref_iter::traits::{ImgIter, ImgIterMut, ImgIterPtrMut};
+	#[cfg(not(doc))]
+	use rayon::iter::{ParallelBridge, ParallelIterator};
+
+	assert_eq!((weights.width(), weights.height()), (buffer.width(), buffer.height()), "the weight map must be the same size as the buffer");
+
+	let mut opses: Vec<Option<VecDeque<(B, usize)>>> = std::iter::repeat_with(|| Some(VecDeque::new())).take(rayon::current_num_threads() + 1).collect();
+	let threads = opses.len();
+	let opses_ptr = unsafe { unique::Unique::new_unchecked(opses.as_mut_ptr()) };
+	let mut sums = ImgVec::new(vec![0usize; buffer.width() * buffer.height()], buffer.width(), buffer.height());
+
+	// The rows must be written before the column iterators are constructed,
+	// for the same reason as in `blur_lines`.
+	let buffer_ptr = buffer.as_mut_ptr();
+	let sums_ptr = sums.as_mut().as_mut_ptr();
+
+	unsafe { buffer_ptr.iter_rows_ptr_mut() }.zip(buffer.iter_rows()).zip(weights.iter_rows()).zip(unsafe { sums_ptr.iter_rows_ptr_mut() }).par_bridge().for_each(|(((mut write, read), weights), mut write_sums)| {
+		let mut fallback = None;
+		let ops_ref = unsafe { worker_scratch(opses_ptr.as_ptr(), threads, &mut fallback) };
+
+		let mut blur = WeightedBlur::new(read.map(&to_blurrable).zip(weights.map(|&weight| weight as usize)), radius_x, kernel, ops_ref.take().unwrap());
+		blur.by_ref().for_each(|(pixel, weight)| unsafe {
+			*write.next().unwrap() = to_pixel(pixel);
+			*write_sums.next().unwrap() = weight;
+		});
+		ops_ref.replace(blur.into_ops());
+	});
+
+	unsafe { buffer_ptr.iter_cols_ptr_mut() }.zip(buffer.iter_cols()).zip(sums.iter_cols()).par_bridge().for_each(|((mut write, read), weights)| {
+		let mut fallback = None;
+		let ops_ref = unsafe { worker_scratch(opses_ptr.as_ptr(), threads, &mut fallback) };
+
+		let mut blur = WeightedBlur::new(read.map(&to_blurrable).zip(weights.copied()), radius_y, kernel, ops_ref.take().unwrap());
+		blur.by_ref().for_each(|(pixel, _)| unsafe { *write.next().unwrap() = to_pixel(pixel) });
+		ops_ref.replace(blur.into_ops());
+	});
+}
+
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_lines<T, Bsimd, Bsingle, L: LineBlur<Bsimd> + LineBlur<Bsingle>, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	rows: &L,
+	cols: &L,
+	to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	mut to_pixel_simd: impl FnMut(Bsimd) -> [T; LANES],
+	to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	mut to_pixel_single: impl FnMut(Bsingle) -> T
+) where LaneCount<LANES>: SupportedLaneCount {
+	simd_blur_lines_onto(buffer, rows, cols, to_blurrable_simd, |pixels, _| to_pixel_simd(pixels), to_blurrable_single, |pixel, _| to_pixel_single(pixel));
+}
+
+/// Like [`simd_blur_lines`], but `to_pixel_simd` and `to_pixel_single` also get
+/// the pixels that they replace, so that they can keep parts of them.
+#[cfg(any(doc, feature = "simd"))]
+pub fn simd_blur_lines_onto<T, Bsimd, Bsingle, L: LineBlur<Bsimd> + LineBlur<Bsingle>, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	rows: &L,
+	cols: &L,
+	mut to_blurrable_simd: impl FnMut([&T; LANES]) -> Bsimd,
+	mut to_pixel_simd: impl FnMut(Bsimd, [&T; LANES]) -> [T; LANES],
+	mut to_blurrable_single: impl FnMut(&T) -> Bsingle,
+	mut to_pixel_single: impl FnMut(Bsingle, &T) -> T
+) where LaneCount<LANES>: SupportedLaneCount {
+	#[cfg(not(doc))]
+	use imgref_iter::traits::{ImgIterMut, ImgSimdIter, ImgSimdIterPtrMut};
+	#[cfg(not(doc))]
+	use imgref_iter::iter::{SimdIterWindow, SimdIterWindowPtrMut, SimdIterWindows, SimdIterWindowsPtrMut};
+
+	let mut ops_simd = <L as LineBlur<Bsimd>>::Ops::default();
+	let mut ops_single = <L as LineBlur<Bsingle>>::Ops::default();
+
+	let mut simd_blur_windows = |writer: SimdIterWindowsPtrMut<T, LANES>, reader: SimdIterWindows<T, LANES>, line: &L, ops_simd: &mut <L as LineBlur<Bsimd>>::Ops, ops_single: &mut <L as LineBlur<Bsingle>>::Ops| {
+		if LineBlur::<Bsimd>::is_identity(line) {
+			return;
+		}
+
+		for (write, read) in writer.zip(reader) {
+			match (write, read) {
+				(SimdIterWindowPtrMut::Simd(mut write), SimdIterWindow::Simd(read)) => {
+					LineBlur::<Bsimd>::blur_line(line, read.map(&mut to_blurrable_simd), |pixels| {
+						let places = write.next().unwrap();
+						places.into_iter().zip(to_pixel_simd(pixels, places.map(|place| unsafe { &*place }))).for_each(|(place, pixel)| unsafe { *place = pixel });
+					}, ops_simd);
+				}
+
+				(SimdIterWindowPtrMut::Single(mut write), SimdIterWindow::Single(read)) => {
+					LineBlur::<Bsingle>::blur_line(line, read.map(&mut to_blurrable_single), |pixel| unsafe {
+						let place = write.next().unwrap();
+						*place = to_pixel_single(pixel, &*place);
+					}, ops_single);
+				}
+
+				_ => unreachable!()
+			}
+		}
+	};
+
+	let buffer_ptr = buffer.as_mut_ptr();
+	simd_blur_windows(unsafe { buffer_ptr.simd_iter_rows_ptr_mut::<LANES>() }, buffer.simd_iter_rows::<LANES>(), rows, &mut ops_simd, &mut ops_single);
+	simd_blur_windows(unsafe { buffer_ptr.simd_iter_cols_ptr_mut::<LANES>() }, buffer.simd_iter_cols::<LANES>(), cols, &mut ops_simd, &mut ops_single);
+}
+
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_lines<T: Send + Sync, Bsimd: Send + Sync, Bsingle: Send + Sync, L: LineBlur<Bsimd> + LineBlur<Bsingle> + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	rows: &L,
+	cols: &L,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle) -> T + Sync
+) where LaneCount<LANES>: SupportedLaneCount, <L as LineBlur<Bsimd>>::Ops: Send + Sync, <L as LineBlur<Bsingle>>::Ops: Send + Sync {
+	par_simd_blur_lines_onto(buffer, rows, cols, to_blurrable_simd, |pixels, _| to_pixel_simd(pixels), to_blurrable_single, |pixel, _| to_pixel_single(pixel))
+}
+
+/// Like [`par_simd_blur_lines`], but `to_pixel_simd` and `to_pixel_single` also
+/// get the pixels that they replace, so that they can keep parts of them.
+#[cfg(any(doc, all(feature = "rayon", feature = "simd")))]
+pub fn par_simd_blur_lines_onto<T: Send + Sync, Bsimd: Send + Sync, Bsingle: Send + Sync, L: LineBlur<Bsimd> + LineBlur<Bsingle> + Sync, const LANES: usize>(
+	buffer: &mut ImgRefMut<T>,
+	rows: &L,
+	cols: &L,
+	to_blurrable_simd: impl Fn([&T; LANES]) -> Bsimd + Sync,
+	to_pixel_simd: impl Fn(Bsimd, [&T; LANES]) -> [T; LANES] + Sync,
+	to_blurrable_single: impl Fn(&T) -> Bsingle + Sync,
+	to_pixel_single: impl Fn(Bsingle, &T) -> T + Sync
+) where LaneCount<LANES>: SupportedLaneCount, <L as LineBlur<Bsimd>>::Ops: Send + Sync, <L as LineBlur<Bsingle>>::Ops: Send + Sync {
+	#[cfg(not(doc))]
+	use imgref_iter::traits::{ImgIterMut, ImgSimdIter, ImgSimdIterPtrMut};
+	#[cfg(not(doc))]
+	use imgref_iter::iter::{SimdIterWindow, SimdIterWindowPtrMut, SimdIterWindows, SimdIterWindowsPtrMut};
+	#[cfg(not(doc))]
+	use rayon::iter::{ParallelBridge, ParallelIterator};
+
+	let mut opses_simd: Vec<Option<<L as LineBlur<Bsimd>>::Ops>> = std::iter::repeat_with(|| Some(Default::default())).take(rayon::current_num_threads() + 1).collect();
+	let threads_simd = opses_simd.len();
+	let opses_simd_ptr = unsafe { unique::Unique::new_unchecked(opses_simd.as_mut_ptr()) };
+
+	let mut opses_single: Vec<Option<<L as LineBlur<Bsingle>>::Ops>> = std::iter::repeat_with(|| Some(Default::default())).take(rayon::current_num_threads() + 1).collect();
+	let threads_single = opses_single.len();
+	let opses_single_ptr = unsafe { unique::Unique::new_unchecked(opses_single.as_mut_ptr()) };
+
+	let par_simd_blur_windows = |writer: SimdIterWindowsPtrMut<T, LANES>, reader: SimdIterWindows<T, LANES>, line: &L| {
+		if LineBlur::<Bsimd>::is_identity(line) {
+			return;
+		}
+
+		writer.zip(reader).par_bridge().for_each(|(write, read)| match (write, read) {
+			(SimdIterWindowPtrMut::Simd(mut write), SimdIterWindow::Simd(read)) => {
+				let mut fallback = None;
+				let ops_ref = unsafe { worker_scratch(opses_simd_ptr.as_ptr(), threads_simd, &mut fallback) };
+
+				let mut ops = ops_ref.take().unwrap();
+				LineBlur::<Bsimd>::blur_line(line, read.map(&to_blurrable_simd), |pixels| {
+					let places = write.next().unwrap();
+					places.into_iter().zip(to_pixel_simd(pixels, places.map(|place| unsafe { &*place }))).for_each(|(place, pixel)| unsafe { *place = pixel });
+				}, &mut ops);
+				ops_ref.replace(ops);
+			}
+
+			(SimdIterWindowPtrMut::Single(mut write), SimdIterWindow::Single(read)) => {
+				let mut fallback = None;
+				let ops_ref = unsafe { worker_scratch(opses_single_ptr.as_ptr(), threads_single, &mut fallback) };
+
+				let mut ops = ops_ref.take().unwrap();
+				LineBlur::<Bsingle>::blur_line(line, read.map(&to_blurrable_single), |pixel| unsafe {
+					let place = write.next().unwrap();
+					*place = to_pixel_single(pixel, &*place);
+				}, &mut ops);
+				ops_ref.replace(ops);
+			}
+
+			_ => unreachable!()
+		});
+	};
+
+	let buffer_ptr = buffer.as_mut_ptr();
+	par_simd_blur_windows(unsafe { buffer_ptr.simd_iter_rows_ptr_mut::<LANES>() }, buffer.simd_iter_rows::<LANES>(), rows);
+	par_simd_blur_windows(unsafe { buffer_ptr.simd_iter_cols_ptr_mut::<LANES>() }, buffer.simd_iter_cols::<LANES>(), cols);
+}
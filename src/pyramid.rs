@@ -0,0 +1,308 @@
+//! Gaussian and Laplacian image pyramids, for multi-scale work like blending
+//! images together, bloom, and enhancing details.
+//!
+//! A Gaussian pyramid is a stack of ever smaller copies of an image. Every
+//! level is blurred by a [`StackBlur`] and then shrunk to half the width and
+//! height of the one below it, rounded up, by averaging 2×2 blocks of pixels,
+//! down to a level that is only a few pixels across.
+//!
+//! A Laplacian pyramid keeps only the details that each level of a Gaussian
+//! pyramid has over the next. Every level but the last becomes the difference
+//! between itself and the next level expanded back to its size, and the last
+//! level is kept as it is. Expanding and adding the levels back up from the
+//! top, which is called collapsing the pyramid, gives back the original image.
+//! To blend two images, build a Laplacian pyramid of each with a signed type,
+//! mix every level by a Gaussian pyramid of the mask, and collapse the result.
+//!
+//! ## Signed levels
+//!
+//! The levels of a Laplacian pyramid are negative wherever the next level is
+//! brighter, so the [`StackBlurrable`] type has to be able to hold negative
+//! values, the same way as for a
+//! [`DifferenceBlur`](crate::iter::DifferenceBlur#negative-differences).
+//! Expanding a level rounds the same way whether it is building the pyramid or
+//! collapsing it, so with the wrapping integer types of the [`color`] module,
+//! collapsing a Laplacian pyramid gives back exactly the image that it was
+//! built from. Their levels can't be mixed or scaled in between, though,
+//! because they divide as unsigned, which turns negative values into huge
+//! positive ones. To blend images, or to change the levels in any other way
+//! before collapsing them, use a signed type like [`StackBlurrableF32`], or
+//! [`Channels`] of them for more than one channel.
+//!
+//! ## Reusing buffers
+//!
+//! A [`Pyramid`] keeps the buffers of its levels, along with its scratch
+//! space, from one build to the next. Once it has been built from an image,
+//! building it again from images of the same size or smaller, with the same
+//! number of levels or fewer, does not allocate.
+//!
+//! [`StackBlur`]: crate::iter::StackBlur
+//! [`color`]: crate::color
+//! [`StackBlurrableF32`]: crate::float::StackBlurrableF32
+//! [`Channels`]: crate::color::Channels
+
+use std::collections::VecDeque;
+
+use imgref::{ImgRef, ImgRefMut};
+
+use crate::traits::StackBlurrable;
+use crate::lines;
+
+/// An image pyramid. See the [module documentation](self) for an overview.
+pub struct Pyramid<B: StackBlurrable> {
+	radius: usize,
+	/// The buffers of every level that has ever been built, even the ones past
+	/// `len`, which are kept so that they don't have to be allocated again.
+	levels: Vec<Vec<B>>,
+	len: usize,
+	width: usize,
+	height: usize,
+	/// The rows of a level after blurring and halving them, before its columns
+	/// are.
+	scratch: Vec<B>,
+	ops: VecDeque<B>,
+	#[cfg(any(doc, feature = "rayon"))]
+	opses: Vec<Option<VecDeque<B>>>
+}
+
+impl<B: StackBlurrable> Pyramid<B> {
+	/// Creates an empty [`Pyramid`] that blurs every level by `radius` before
+	/// halving it.
+	///
+	/// A radius of 2 is about as much blur as halving an image needs to avoid
+	/// aliasing. Larger radii blur each level more than the levels above it can
+	/// show, which smooths the pyramid out at the cost of some detail.
+	pub fn new(radius: usize) -> Self {
+		Self {
+			radius,
+			levels: Vec::new(),
+			len: 0,
+			width: 0,
+			height: 0,
+			scratch: Vec::new(),
+			ops: VecDeque::new(),
+			#[cfg(any(doc, feature = "rayon"))]
+			opses: Vec::new()
+		}
+	}
+
+	/// Returns the radius that every level is blurred by.
+	pub fn radius(&self) -> usize {
+		self.radius
+	}
+
+	/// Returns the number of levels, including the first one, which is as large
+	/// as the image.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Returns whether the pyramid has no levels, because it hasn't been built
+	/// yet or was built from an empty image.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns the width and height of the level at `index`.
+	pub fn size(&self, index: usize) -> (usize, usize) {
+		let (mut width, mut height) = (self.width, self.height);
+
+		for _ in 0..index {
+			width = (width + 1) / 2;
+			height = (height + 1) / 2;
+		}
+
+		(width, height)
+	}
+
+	/// Returns the level at `index`.
+	///
+	/// Panics if there is no level at `index`.
+	pub fn level(&self, index: usize) -> ImgRef<'_, B> {
+		assert!(index < self.len, "there is no level {index} in a pyramid of {} levels", self.len);
+
+		let (width, height) = self.size(index);
+		ImgRef::new(&self.levels[index][..], width, height)
+	}
+
+	/// Returns the level at `index` for modifying, like mixing it with another
+	/// pyramid before collapsing it.
+	///
+	/// Panics if there is no level at `index`.
+	pub fn level_mut(&mut self, index: usize) -> ImgRefMut<'_, B> {
+		assert!(index < self.len, "there is no level {index} in a pyramid of {} levels", self.len);
+
+		let (width, height) = self.size(index);
+		ImgRefMut::new(&mut self.levels[index][..], width, height)
+	}
+
+	/// Fills the first level from `input`, and makes room for `levels` levels
+	/// of the right sizes in total.
+	fn start<T>(&mut self, input: &ImgRef<T>, levels: usize, to_blurrable: impl FnMut(&T) -> B) {
+		self.width = input.width();
+		self.height = input.height();
+		self.len = if self.width == 0 || self.height == 0 { 0 } else { levels };
+
+		if self.levels.len() < self.len {
+			self.levels.resize_with(self.len, Vec::new);
+		}
+
+		if self.len > 0 {
+			self.levels[0].clear();
+			self.levels[0].extend(input.rows().flatten().map(to_blurrable));
+		}
+
+		for index in 1..self.len {
+			let (width, height) = self.size(index);
+			self.levels[index].clear();
+			self.levels[index].resize(width * height, B::default());
+		}
+	}
+
+	/// Returns the level at `index` and the one after it.
+	fn pair_mut(&mut self, index: usize) -> (ImgRefMut<'_, B>, ImgRefMut<'_, B>) {
+		let (width, height) = self.size(index);
+		let (next_width, next_height) = self.size(index + 1);
+		let (levels, next) = self.levels.split_at_mut(index + 1);
+		(ImgRefMut::new(&mut levels[index][..], width, height), ImgRefMut::new(&mut next[0][..], next_width, next_height))
+	}
+
+	/// Builds a Gaussian pyramid of `levels` levels from `input`, replacing any
+	/// levels that were there before.
+	///
+	/// The provided closure is used to convert from the input's native pixel
+	/// format to [`StackBlurrable`] values.
+	pub fn build_gaussian<T>(&mut self, input: ImgRef<T>, levels: usize, to_blurrable: impl FnMut(&T) -> B) {
+		self.start(&input, levels, to_blurrable);
+
+		for index in 1..self.len {
+			let radius = self.radius;
+			let mut scratch = std::mem::take(&mut self.scratch);
+			let mut ops = std::mem::take(&mut self.ops);
+			let (level, mut next) = self.pair_mut(index - 1);
+			lines::blur_halve(&level.as_ref(), &mut next, radius, &mut scratch, &mut ops);
+			self.scratch = scratch;
+			self.ops = ops;
+		}
+	}
+
+	/// Builds a Laplacian pyramid of `levels` levels from `input`, replacing any
+	/// levels that were there before.
+	///
+	/// The provided closure is used to convert from the input's native pixel
+	/// format to [`StackBlurrable`] values. See the
+	/// [module documentation](self#signed-levels) for which types can hold
+	/// the levels.
+	pub fn build_laplacian<T>(&mut self, input: ImgRef<T>, levels: usize, to_blurrable: impl FnMut(&T) -> B) {
+		self.build_gaussian(input, levels, to_blurrable);
+
+		// Each level is taken apart before the one above it, which is still
+		// needed as it is.
+		for index in 0..self.len.saturating_sub(1) {
+			let (fine, coarse) = self.pair_mut(index);
+			expand(coarse.as_ref(), fine, |pixel, expanded| *pixel -= expanded);
+		}
+	}
+
+	/// Collapses a Laplacian pyramid back into the Gaussian pyramid that it was
+	/// built from, so that the first level is the reconstructed image.
+	///
+	/// Collapsing a pyramid that is already Gaussian blurs its first level
+	/// even more.
+	pub fn collapse(&mut self) {
+		for index in (0..self.len.saturating_sub(1)).rev() {
+			let (fine, coarse) = self.pair_mut(index);
+			expand(coarse.as_ref(), fine, |pixel, expanded| *pixel += expanded);
+		}
+	}
+}
+
+#[cfg(any(doc, feature = "rayon"))]
+impl<B: StackBlurrable + Send + Sync> Pyramid<B> {
+	/// Builds a Gaussian pyramid of `levels` levels from `input` in parallel,
+	/// replacing any levels that were there before.
+	///
+	/// See [`build_gaussian`](Self::build_gaussian) for how the parameters are
+	/// applied.
+	pub fn par_build_gaussian<T>(&mut self, input: ImgRef<T>, levels: usize, to_blurrable: impl FnMut(&T) -> B) {
+		self.start(&input, levels, to_blurrable);
+
+		for index in 1..self.len {
+			let radius = self.radius;
+			let mut scratch = std::mem::take(&mut self.scratch);
+			let mut opses = std::mem::take(&mut self.opses);
+			let (level, mut next) = self.pair_mut(index - 1);
+			lines::par_blur_halve(&level.as_ref(), &mut next, radius, &mut scratch, &mut opses);
+			self.scratch = scratch;
+			self.opses = opses;
+		}
+	}
+
+	/// Builds a Laplacian pyramid of `levels` levels from `input` in parallel,
+	/// replacing any levels that were there before.
+	///
+	/// See [`build_laplacian`](Self::build_laplacian) for how the parameters
+	/// are applied.
+	pub fn par_build_laplacian<T>(&mut self, input: ImgRef<T>, levels: usize, to_blurrable: impl FnMut(&T) -> B) {
+		self.par_build_gaussian(input, levels, to_blurrable);
+
+		for index in 0..self.len.saturating_sub(1) {
+			let (fine, coarse) = self.pair_mut(index);
+			par_expand(coarse.as_ref(), fine, |pixel, expanded| *pixel -= expanded);
+		}
+	}
+
+	/// Collapses a Laplacian pyramid back into the Gaussian pyramid that it was
+	/// built from in parallel.
+	///
+	/// See [`collapse`](Self::collapse) for more.
+	pub fn par_collapse(&mut self) {
+		for index in (0..self.len.saturating_sub(1)).rev() {
+			let (fine, coarse) = self.pair_mut(index);
+			par_expand(coarse.as_ref(), fine, |pixel, expanded| *pixel += expanded);
+		}
+	}
+}
+
+/// Returns the two pixels of a line of `len` that a pixel at `position` on a
+/// line twice as long lies between, nearest first.
+fn neighbors(position: usize, len: usize) -> (usize, usize) {
+	let near = position / 2;
+	let far = if position % 2 == 0 { near.saturating_sub(1) } else { (near + 1).min(len - 1) };
+	(near, far)
+}
+
+/// Expands row `y` of `coarse` to the width of `row`, and hands every pixel of
+/// `row` to `combine` along with the expanded pixel at its position.
+fn expand_row<B: StackBlurrable>(coarse: &ImgRef<B>, y: usize, row: &mut [B], combine: &impl Fn(&mut B, B)) {
+	// Every pixel of `coarse` covers a 2×2 block of the larger level, so the
+	// pixel centers lie a quarter of the way between those of `coarse`.
+	let coarse_row = |y: usize| &coarse.buf()[y * coarse.stride()..][..coarse.width()];
+	let (near_y, far_y) = neighbors(y, coarse.height());
+	let (near_row, far_row) = (coarse_row(near_y), coarse_row(far_y));
+
+	for (x, pixel) in row.iter_mut().enumerate() {
+		let (near_x, far_x) = neighbors(x, coarse.width());
+		let near = near_row[near_x].clone() * 3 + near_row[far_x].clone();
+		let far = far_row[near_x].clone() * 3 + far_row[far_x].clone();
+		combine(pixel, (near * 3 + far) / 16);
+	}
+}
+
+/// Expands `coarse` to the size of `fine` with bilinear interpolation, and
+/// hands every pixel of `fine` to `combine` along with the expanded pixel at
+/// its position.
+fn expand<B: StackBlurrable>(coarse: ImgRef<B>, mut fine: ImgRefMut<B>, combine: impl Fn(&mut B, B)) {
+	for (y, row) in fine.rows_mut().enumerate() {
+		expand_row(&coarse, y, row, &combine);
+	}
+}
+
+/// Like [`expand`], but in parallel.
+#[cfg(any(doc, feature = "rayon"))]
+fn par_expand<B: StackBlurrable + Send + Sync>(coarse: ImgRef<B>, mut fine: ImgRefMut<B>, combine: impl Fn(&mut B, B) + Sync) {
+	#[cfg(not(doc))]
+	use rayon::iter::{ParallelBridge, ParallelIterator};
+
+	fine.rows_mut().enumerate().par_bridge().for_each(|(y, row)| expand_row(&coarse, y, row, &combine));
+}
@@ -0,0 +1,238 @@
+//! Drop shadows and outer glows for layers of packed ARGB pixels.
+//!
+//! A [`Shadow`] takes the alpha channel of a layer, moves it by an offset,
+//! grows or shrinks it by a spread, blurs it, and tints it with a color. It can
+//! either [`render`](Shadow::render) the result into an image of its own that
+//! is large enough to hold everything the blur spreads out, or
+//! [`composite`](Shadow::composite) it underneath the layer in place.
+//!
+//! An outer glow is just a shadow without an offset, usually with a positive
+//! spread and a bright color. See [`Shadow::outer_glow`].
+//!
+//! Everything outside the layer counts as fully transparent. The shape is
+//! blurred with the same [`FractionalStackBlur`] as
+//! [`blur_fractional`](crate::blur_fractional), inside a margin of transparent
+//! pixels that is wide enough that the renormalization at its edges never
+//! touches the shadow, so the shadow fades out past the edges of the layer the
+//! same way it would on an infinite canvas.
+//!
+//! [`FractionalStackBlur`]: crate::iter::FractionalStackBlur
+
+use imgref::{ImgRef, ImgRefMut, ImgVec};
+
+use crate::color::{Channel, StackBlurrableU32, StackBlurrableU64};
+use crate::error::BlurError;
+use crate::lines;
+use crate::{check_finite, check_radius, fits_u32, fractional_weight, sigma_to_radius, upper_radius, ARGB_CHANNEL_MAX};
+
+/// How much a [`Shadow`] is blurred.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Softness {
+	/// A single Stackblur with this radius, which may be fractional, like
+	/// [`blur_fractional`](crate::blur_fractional).
+	Radius(f32),
+	/// An approximate Gaussian blur with this standard deviation, made of three
+	/// Stackblurs, like [`blur_gaussian`](crate::blur_gaussian) with three
+	/// passes.
+	Sigma(f32)
+}
+
+impl Softness {
+	/// Returns the radius and number of passes of the blur.
+	fn passes(self) -> (f32, usize) {
+		match self {
+			Self::Radius(radius) => (radius, 1),
+			Self::Sigma(sigma) => (sigma_to_radius(sigma, 3), 3)
+		}
+	}
+}
+
+/// A drop shadow or outer glow. See the [module documentation](self) for an
+/// overview.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Shadow {
+	/// How far the shadow is moved from the layer, in pixels, to the right and
+	/// down.
+	pub offset: (isize, isize),
+	/// How many pixels the shape of the layer is grown by before it is blurred,
+	/// or shrunk by if this is negative. The shape grows and shrinks by the
+	/// same amount in every direction, including diagonally, so corners stay
+	/// square.
+	pub spread: isize,
+	/// How much the shadow is blurred.
+	pub softness: Softness,
+	/// The color of the shadow, as a packed ARGB pixel (0xAARRGGBB) with
+	/// straight alpha. Its alpha is multiplied into that of the shadow.
+	pub color: u32,
+	/// How opaque the shadow is, from 0 to 1, on top of the alpha of `color`.
+	pub opacity: f32
+}
+
+/// A shadow rendered into an image of its own by [`Shadow::render`].
+#[derive(Clone, Debug)]
+pub struct ShadowImage {
+	/// The shadow, as packed ARGB pixels (0xAARRGGBB) with straight alpha. Every
+	/// pixel has the color of the shadow; only the alpha changes.
+	pub image: ImgVec<u32>,
+	/// Where the top left corner of `image` is, relative to the top left corner
+	/// of the layer. This includes the offset of the shadow, and is negative
+	/// when the shadow reaches past the top or left of the layer.
+	pub origin: (isize, isize)
+}
+
+impl Shadow {
+	/// Creates a drop shadow with the given offset, softness and color, no
+	/// spread, and full opacity.
+	pub fn drop_shadow(offset_x: isize, offset_y: isize, softness: Softness, color: u32) -> Self {
+		Self { offset: (offset_x, offset_y), spread: 0, softness, color, opacity: 1.0 }
+	}
+
+	/// Creates an outer glow with the given spread, softness and color, no
+	/// offset, and full opacity.
+	pub fn outer_glow(spread: isize, softness: Softness, color: u32) -> Self {
+		Self { offset: (0, 0), spread, softness, color, opacity: 1.0 }
+	}
+
+	/// Returns how many pixels the shadow reaches past the edges of the layer,
+	/// before it is offset.
+	pub fn extent(&self) -> usize {
+		let (radius, passes) = self.softness.passes();
+		self.spread.max(0) as usize + upper_radius(radius) * passes
+	}
+
+	/// Renders the shadow of a layer of packed ARGB pixels (0xAARRGGBB) into a
+	/// new image, which is [`extent`](Self::extent) pixels larger than the
+	/// layer on every side.
+	///
+	/// Only the alpha of the layer is used. The blur uses 32 bits per channel,
+	/// or 64 bits when the radius is too large for that.
+	pub fn render(&self, layer: ImgRef<u32>) -> ShadowImage {
+		let (radius, passes) = self.softness.passes();
+		let extent = self.extent();
+
+		// One more radius than the blur reaches, so that every pixel that the
+		// blur renormalizes at the edges has nothing but transparency around it,
+		// and the spread on top when shrinking, so that the shape is eroded from
+		// the edges of the layer as well.
+		let margin = extent + upper_radius(radius) + self.spread.min(0).unsigned_abs();
+		let (width, height) = (layer.width() + margin * 2, layer.height() + margin * 2);
+
+		let mut plane = ImgVec::new(vec![0u8; width * height], width, height);
+		for (y, row) in layer.rows().enumerate() {
+			for (x, pixel) in row.iter().enumerate() {
+				plane[(x + margin, y + margin)] = (pixel >> 24) as u8;
+			}
+		}
+
+		spread(&mut plane, self.spread);
+
+		let line = lines::Gaussian { radius, passes };
+		if fits_u32(ARGB_CHANNEL_MAX, fractional_weight(upper_radius(radius))) {
+			lines::blur_lines(&mut plane.as_mut(), &line, &line, |&i| StackBlurrableU32::from_u32(i.into()), |i| i.to_u32() as u8);
+		} else {
+			lines::blur_lines(&mut plane.as_mut(), &line, &line, |&i| StackBlurrableU64::from_u32(i.into()), |i| i.to_u32() as u8);
+		}
+
+		// NaN is clamped to NaN, which casts to 0.
+		let strength = f32::from((self.color >> 24) as u8) / 255.0 * self.opacity.clamp(0.0, 1.0);
+		let color = self.color & 0x00FFFFFF;
+
+		let crop = margin - extent;
+		let (width, height) = (layer.width() + extent * 2, layer.height() + extent * 2);
+		let pixels = plane.sub_image(crop, crop, width, height).pixels().map(|alpha| ((f32::from(alpha) * strength).round() as u32) << 24 | color).collect();
+
+		ShadowImage {
+			image: ImgVec::new(pixels, width, height),
+			origin: (self.offset.0 - extent as isize, self.offset.1 - extent as isize)
+		}
+	}
+
+	/// Like [`render`](Self::render), but returns a [`BlurError`] if the
+	/// softness is too large or a parameter is not supported.
+	pub fn try_render(&self, layer: ImgRef<u32>) -> Result<ShadowImage, BlurError> {
+		self.check()?;
+		Ok(self.render(layer))
+	}
+
+	/// Renders the shadow of a layer of packed ARGB pixels (0xAARRGGBB) with
+	/// straight alpha, and composites the layer over it in place.
+	///
+	/// The parts of the shadow that fall outside the layer are cut off. To keep
+	/// them, pad the layer with transparent pixels first, or composite the
+	/// result of [`render`](Self::render) yourself. Pixels of the layer that are
+	/// fully opaque, or that the shadow does not reach, are left exactly as they
+	/// were.
+	pub fn composite(&self, layer: &mut ImgRefMut<u32>) {
+		let shadow = self.render(layer.as_ref());
+		let (left, top) = shadow.origin;
+		let (width, height) = (shadow.image.width(), shadow.image.height());
+
+		for (y, row) in layer.rows_mut().enumerate() {
+			let shadow_y = y as isize - top;
+			if shadow_y < 0 || shadow_y as usize >= height {
+				continue;
+			}
+
+			for (x, pixel) in row.iter_mut().enumerate() {
+				let shadow_x = x as isize - left;
+				if shadow_x >= 0 && (shadow_x as usize) < width {
+					*pixel = over(*pixel, shadow.image[(shadow_x as usize, shadow_y as usize)]);
+				}
+			}
+		}
+	}
+
+	/// Like [`composite`](Self::composite), but returns a [`BlurError`] if the
+	/// softness is too large or a parameter is not supported.
+	pub fn try_composite(&self, layer: &mut ImgRefMut<u32>) -> Result<(), BlurError> {
+		self.check()?;
+		self.composite(layer);
+		Ok(())
+	}
+
+	/// Returns an error if the shadow cannot be rendered.
+	fn check(&self) -> Result<(), BlurError> {
+		match self.softness {
+			Softness::Radius(radius) => check_finite("softness", radius)?,
+			Softness::Sigma(sigma) => check_finite("softness", sigma)?
+		}
+
+		check_finite("opacity", self.opacity)?;
+
+		let (radius, _) = self.softness.passes();
+		check_radius(ARGB_CHANNEL_MAX, upper_radius(radius), fractional_weight)
+	}
+}
+
+/// Grows the shape in `plane` by `spread` pixels in every direction, or shrinks
+/// it if `spread` is negative, by taking the largest or smallest alpha in the
+/// square around each pixel. Pixels off the edge of `plane` are left out, so
+/// it needs a margin of transparent pixels at least as wide as the spread for
+/// them to count as transparent when shrinking.
+fn spread(plane: &mut ImgVec<u8>, spread: isize) {
+	let radius = spread.unsigned_abs();
+
+	if spread > 0 {
+		lines::morph_lines::<u8, u8, true>(&mut plane.as_mut(), radius, |&alpha| alpha, |alpha| alpha);
+	} else {
+		lines::morph_lines::<u8, u8, false>(&mut plane.as_mut(), radius, |&alpha| alpha, |alpha| alpha);
+	}
+}
+
+/// Composites `top` over `bottom`, both with straight alpha.
+fn over(top: u32, bottom: u32) -> u32 {
+	let [top_alpha, top_red, top_green, top_blue] = top.to_be_bytes().map(u32::from);
+	let [bottom_alpha, bottom_red, bottom_green, bottom_blue] = bottom.to_be_bytes().map(u32::from);
+
+	if top_alpha == 255 || bottom_alpha == 0 {
+		return top;
+	}
+
+	// Both weights are out of 255 * 255.
+	let top_weight = top_alpha * 255;
+	let bottom_weight = bottom_alpha * (255 - top_alpha);
+	let total = top_weight + bottom_weight;
+
+	let mix = |top, bottom| ((top * top_weight + bottom * bottom_weight + total / 2) / total) as u8;
+	u32::from_be_bytes([((total + 127) / 255) as u8, mix(top_red, bottom_red), mix(top_green, bottom_green), mix(top_blue, bottom_blue)])
+}
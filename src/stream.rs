@@ -0,0 +1,347 @@
+//! A 2D blur that takes its input one row at a time.
+//!
+//! The helpers in the crate root blur all the rows of a buffer before they
+//! blur any of its columns, so they need the whole image in memory at once.
+//! [`StreamingBlur`] instead blurs each row as it comes in, and then feeds it
+//! into a running sum for every column, the same way that [`StackBlur`] keeps
+//! a running sum for a single line. That way, each finished row is available
+//! only `radius_y` rows after it was pushed, and only about `radius_y * 2`
+//! rows need to be kept around, no matter how tall the image is.
+//!
+//! The same running sums also blur video over time. [`TemporalBlur`] treats
+//! every frame as a single row, so that each pixel is blurred with the pixels
+//! at the same position in the frames around it, and each blurred frame is
+//! available `radius` frames after it was pushed. [`ArgbTemporalBlur`] does the
+//! same for frames of packed ARGB pixels.
+//!
+//! [`StackBlur`]: crate::iter::StackBlur
+
+use std::collections::VecDeque;
+
+use imgref::{ImgRef, ImgRefMut};
+
+use crate::traits::StackBlurrable;
+use crate::color::{ArgbU32, ArgbU64};
+use crate::iter::{EdgeMode, Kernel};
+use crate::lines::{LineBlur, Stack};
+use crate::{fits_u32, ARGB_CHANNEL_MAX};
+
+/// A 2D Stackblur over a stream of rows.
+///
+/// Push every row of the image into [`StreamingBlur::push_row`] in order. Once
+/// `radius_y` more rows have been pushed after a row, it is finished and
+/// returned by the call that pushed the last of them. After the last row of the
+/// image, call [`StreamingBlur::finish_row`] until it returns `None` to get the
+/// `radius_y` rows that are still left.
+///
+/// The result is the same as that of [`blur_xy`](crate::blur_xy): each row is
+/// blurred by a [`StackBlur`](crate::iter::StackBlur) with `radius_x`, and the
+/// columns are blurred by `radius_y`, with the weights renormalized at the
+/// edges. The sums of the columns need the same precision as those of a
+/// [`StackBlur`](crate::iter::StackBlur) with `radius_y` do.
+///
+/// Between the rows that are kept and the sums of the columns, this holds
+/// `(radius_y * 2 + 5) * width` [`StackBlurrable`] values.
+pub struct StreamingBlur<B: StackBlurrable> {
+	width: usize,
+	rows: Stack,
+	radius_y: usize,
+	ops: VecDeque<B>,
+	/// The last `radius_y * 2 + 1` rows after blurring them, oldest first,
+	/// along with whether they are part of the image (1) or padding (0).
+	window: VecDeque<(Vec<B>, usize)>,
+	/// The row that the next one is blurred into.
+	spare: Vec<B>,
+	/// The sums of every column over the window, weighted like a tent.
+	sums: Vec<B>,
+	/// The sums of every column over the rows in the window after its center.
+	ahead: Vec<B>,
+	/// The sums of every column over the rows in the window up to and
+	/// including its center.
+	behind: Vec<B>,
+	/// The same as `sums`, `ahead` and `behind`, but for the weights, which
+	/// are shared by all the columns.
+	dnom: (usize, usize, usize),
+	/// How many rows have gone into the window, including padding at the end.
+	advanced: usize,
+	/// How many rows of the image have been pushed.
+	height: usize,
+	/// How many rows have been finished.
+	finished: usize,
+	finishing: bool
+}
+
+impl<B: StackBlurrable> StreamingBlur<B> {
+	/// Creates a new [`StreamingBlur`] for an image that is `width` pixels wide,
+	/// which blurs its rows by `radius_x` and its columns by `radius_y`.
+	pub fn new(width: usize, radius_x: usize, radius_y: usize) -> Self {
+		let window = std::iter::repeat_with(|| (vec![B::default(); width], 0)).take(radius_y * 2 + 1).collect();
+
+		Self {
+			width,
+			rows: Stack { radius: radius_x, edge_mode: EdgeMode::Renormalize },
+			radius_y,
+			ops: VecDeque::new(),
+			window,
+			spare: Vec::with_capacity(width),
+			sums: vec![B::default(); width],
+			ahead: vec![B::default(); width],
+			behind: vec![B::default(); width],
+			dnom: (0, 0, 0),
+			advanced: 0,
+			height: 0,
+			finished: 0,
+			finishing: false
+		}
+	}
+
+	/// Returns the width of the image, which every row must match.
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	/// Pushes the next row of the image, which must have exactly
+	/// [`width`](Self::width) pixels, and returns the row that it finished,
+	/// if there is one.
+	///
+	/// Panics if the row has the wrong number of pixels, or if
+	/// [`finish_row`](Self::finish_row) has already been called.
+	pub fn push_row(&mut self, row: impl IntoIterator<Item = B>) -> Option<impl Iterator<Item = B> + '_> {
+		self.push(row);
+		self.take_row()
+	}
+
+	/// Blurs `row` and moves the window down onto it, without returning the row
+	/// that it finished, so that the caller can take it with
+	/// [`take_row`](Self::take_row) without borrowing from `row`.
+	fn push(&mut self, row: impl IntoIterator<Item = B>) {
+		assert!(!self.finishing, "cannot push more rows after finishing");
+
+		let mut spare = std::mem::take(&mut self.spare);
+		spare.clear();
+
+		if LineBlur::<B>::is_identity(&self.rows) {
+			spare.extend(row);
+		} else {
+			self.rows.blur_line(row.into_iter(), |pixel| spare.push(pixel), &mut self.ops);
+		}
+
+		assert_eq!(spare.len(), self.width, "every row must be as wide as the image");
+
+		self.height += 1;
+		self.advance(spare, 1);
+	}
+
+	/// Returns the next row that is left over after the last row of the image
+	/// has been pushed, or `None` once every row has been returned.
+	///
+	/// No more rows can be pushed after this has been called.
+	pub fn finish_row(&mut self) -> Option<impl Iterator<Item = B> + '_> {
+		self.finishing = true;
+
+		if self.finished == self.height {
+			return None;
+		}
+
+		// If the image is shorter than `radius_y`, no rows have been finished
+		// yet, and it takes more than one row of padding to get to the first.
+		while self.advanced <= self.radius_y || self.advanced - self.radius_y - 1 < self.finished {
+			let mut spare = std::mem::take(&mut self.spare);
+			spare.clear();
+			spare.resize(self.width, B::default());
+			self.advance(spare, 0);
+		}
+
+		self.take_row()
+	}
+
+	/// Moves the center of the window down by one row, with `row` coming in at
+	/// the bottom. `weight` is 1 for rows of the image and 0 for padding.
+	fn advance(&mut self, row: Vec<B>, weight: usize) {
+		self.window.push_back((row, weight));
+
+		let radius = self.radius_y;
+		let (oldest, oldest_weight) = &self.window[0];
+		let (next, next_weight) = &self.window[radius + 1];
+		let (newest, _) = &self.window[radius * 2 + 1];
+
+		// The row after the center moves from `ahead` to `behind`, the newest
+		// row joins `ahead`, and the oldest row leaves `behind`. Every row in
+		// `ahead` gains one weight, and every row in `behind` loses one.
+		for x in 0..self.width {
+			self.sums[x] += self.ahead[x].clone();
+			self.sums[x] += newest[x].clone();
+			self.sums[x] -= self.behind[x].clone();
+			self.behind[x] += next[x].clone();
+			self.behind[x] -= oldest[x].clone();
+			self.ahead[x] += newest[x].clone();
+			self.ahead[x] -= next[x].clone();
+		}
+
+		let (sum, ahead, behind) = &mut self.dnom;
+		*sum = *sum + *ahead + weight - *behind;
+		*behind = *behind + next_weight - oldest_weight;
+		*ahead = *ahead + weight - next_weight;
+
+		self.spare = self.window.pop_front().unwrap().0;
+		self.advanced += 1;
+	}
+
+	/// Returns the row at the center of the window, if it is the next one to
+	/// be finished.
+	fn take_row(&mut self) -> Option<impl Iterator<Item = B> + '_> {
+		if self.advanced <= self.radius_y || self.finished == self.height {
+			return None;
+		}
+
+		self.finished += 1;
+		let dnom = self.dnom.0;
+		Some(self.sums.iter().map(move |sum| sum.clone() / dnom))
+	}
+}
+
+/// A Stackblur over time, which blurs every pixel of a video with the pixels at
+/// the same position in the frames before and after it.
+///
+/// Push every frame into [`TemporalBlur::push_frame`] in order. Once `radius`
+/// more frames have been pushed after a frame, it is finished and returned by
+/// the call that pushed the last of them, as an iterator over its pixels in
+/// row-major order. After the last frame of the video, call
+/// [`TemporalBlur::finish_frame`] until it returns `None` to get the `radius`
+/// frames that are still left.
+///
+/// Every pixel comes out exactly as a [`StackBlur`](crate::iter::StackBlur)
+/// over that pixel of every frame would yield it, including the renormalized
+/// weights at the start and end of the video. Each pixel keeps running sums
+/// that are updated once per frame, so the cost of a frame does not depend on
+/// the radius, but the last `radius * 2 + 1` frames have to be kept in order to
+/// take them back out of the sums. Altogether, this holds
+/// `(radius * 2 + 5) * width * height` [`StackBlurrable`] values.
+pub struct TemporalBlur<B: StackBlurrable> {
+	width: usize,
+	height: usize,
+	frames: StreamingBlur<B>
+}
+
+impl<B: StackBlurrable> TemporalBlur<B> {
+	/// Creates a new [`TemporalBlur`] for frames of `width` by `height` pixels,
+	/// which blurs every pixel over `radius` frames in each direction.
+	pub fn new(width: usize, height: usize, radius: usize) -> Self {
+		Self { width, height, frames: StreamingBlur::new(width * height, 0, radius) }
+	}
+
+	/// Returns the width of the frames.
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	/// Returns the height of the frames.
+	pub fn height(&self) -> usize {
+		self.height
+	}
+
+	/// Pushes the next frame of the video, and returns the pixels of the frame
+	/// that it finished, if there is one.
+	///
+	/// The provided closure is used to convert from the frame's native pixel
+	/// format to [`StackBlurrable`] values.
+	///
+	/// Panics if the frame is not [`width`](Self::width) by
+	/// [`height`](Self::height) pixels, or if
+	/// [`finish_frame`](Self::finish_frame) has already been called.
+	pub fn push_frame<T>(&mut self, frame: ImgRef<T>, to_blurrable: impl FnMut(&T) -> B) -> Option<impl Iterator<Item = B> + '_> {
+		assert_eq!((frame.width(), frame.height()), (self.width, self.height), "every frame must be the same size");
+		self.frames.push(frame.rows().flatten().map(to_blurrable));
+		self.frames.take_row()
+	}
+
+	/// Returns the pixels of the next frame that is left over after the last
+	/// frame of the video has been pushed, or `None` once every frame has been
+	/// returned.
+	///
+	/// No more frames can be pushed after this has been called.
+	pub fn finish_frame(&mut self) -> Option<impl Iterator<Item = B> + '_> {
+		self.frames.finish_row()
+	}
+}
+
+/// The [`TemporalBlur`] behind an [`ArgbTemporalBlur`], with as many bits per
+/// channel as its radius needs.
+enum ArgbFrames {
+	U32(TemporalBlur<ArgbU32>),
+	U64(TemporalBlur<ArgbU64>)
+}
+
+/// A [`TemporalBlur`] for video with 32-bit packed ARGB pixels (0xAARRGGBB).
+///
+/// This writes every finished frame into an output buffer. Like
+/// [`blur_argb`](crate::blur_argb), it uses 32 bits per channel, or 64 bits
+/// when the radius is larger than
+/// [`ARGB_U32_MAX_RADIUS`](crate::ARGB_U32_MAX_RADIUS).
+///
+/// Note that this is *linear*.
+pub struct ArgbTemporalBlur(ArgbFrames);
+
+impl ArgbTemporalBlur {
+	/// Creates a new [`ArgbTemporalBlur`] for frames of `width` by `height`
+	/// pixels, which blurs every pixel over `radius` frames in each direction.
+	pub fn new(width: usize, height: usize, radius: usize) -> Self {
+		if fits_u32(ARGB_CHANNEL_MAX, Kernel::Tent.total_weight(radius)) {
+			Self(ArgbFrames::U32(TemporalBlur::new(width, height, radius)))
+		} else {
+			Self(ArgbFrames::U64(TemporalBlur::new(width, height, radius)))
+		}
+	}
+
+	/// Pushes the next frame of the video, and writes the frame that it
+	/// finished to `output`, if there is one. Returns whether `output` was
+	/// written to.
+	///
+	/// Panics if `frame` or `output` is not the size that this was created
+	/// with, or if [`finish_frame`](Self::finish_frame) has already been
+	/// called.
+	pub fn push_frame(&mut self, frame: ImgRef<u32>, output: &mut ImgRefMut<u32>) -> bool {
+		assert_eq!((output.width(), output.height()), self.size(), "the output must be the same size as the frames");
+
+		match &mut self.0 {
+			ArgbFrames::U32(blur) => write_frame(output, blur.push_frame(frame, |i| ArgbU32::from_u32(*i)).map(|pixels| pixels.map(ArgbU32::to_u32))),
+			ArgbFrames::U64(blur) => write_frame(output, blur.push_frame(frame, |i| ArgbU64::from_u32(*i)).map(|pixels| pixels.map(ArgbU64::to_u32)))
+		}
+	}
+
+	/// Writes the next frame that is left over after the last frame of the
+	/// video has been pushed to `output`. Returns whether `output` was written
+	/// to, which it isn't once every frame has been returned.
+	///
+	/// No more frames can be pushed after this has been called. Panics if
+	/// `output` is not the size that this was created with.
+	pub fn finish_frame(&mut self, output: &mut ImgRefMut<u32>) -> bool {
+		assert_eq!((output.width(), output.height()), self.size(), "the output must be the same size as the frames");
+
+		match &mut self.0 {
+			ArgbFrames::U32(blur) => write_frame(output, blur.finish_frame().map(|pixels| pixels.map(ArgbU32::to_u32))),
+			ArgbFrames::U64(blur) => write_frame(output, blur.finish_frame().map(|pixels| pixels.map(ArgbU64::to_u32)))
+		}
+	}
+
+	/// Returns the size of the frames.
+	fn size(&self) -> (usize, usize) {
+		match &self.0 {
+			ArgbFrames::U32(blur) => (blur.width(), blur.height()),
+			ArgbFrames::U64(blur) => (blur.width(), blur.height())
+		}
+	}
+}
+
+/// Writes the pixels of a finished frame to `output` in row-major order, if
+/// there is a finished frame.
+fn write_frame(output: &mut ImgRefMut<u32>, pixels: Option<impl Iterator<Item = u32>>) -> bool {
+	match pixels {
+		Some(pixels) => {
+			output.rows_mut().flatten().zip(pixels).for_each(|(place, pixel)| *place = pixel);
+			true
+		}
+
+		None => false
+	}
+}
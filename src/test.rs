@@ -261,3 +261,1385 @@ fn stackblur_1024(bencher: &mut Bencher) {
 	let mut buf = vec![0; WIDTH * HEIGHT];
 	bencher.iter(|| stackblur::blur(&mut buf, WIDTH_NONZERO, HEIGHT_NONZERO, unsafe { NonZeroU32::new_unchecked(1024) }));
 }
+
+#[bench]
+#[inline(never)]
+fn column_blocks_16(bencher: &mut Bencher) {
+	use crate::color::ArgbU32;
+	use crate::iter::EdgeMode;
+	use crate::lines::{blur_lines, Stack};
+
+	let mut buf = ImgVec::new(vec![0; WIDTH * HEIGHT], WIDTH, HEIGHT);
+	let rows = Stack { radius: 0, edge_mode: EdgeMode::Renormalize };
+	let cols = Stack { radius: 16, edge_mode: EdgeMode::Renormalize };
+	bencher.iter(|| blur_lines(&mut buf.as_mut(), &rows, &cols, |&pixel| ArgbU32::from_u32(pixel), ArgbU32::to_u32));
+}
+
+#[bench]
+#[inline(never)]
+fn single_columns_16(bencher: &mut Bencher) {
+	use crate::color::ArgbU32;
+	use crate::iter::EdgeMode;
+	use crate::lines::{blur_column, Stack};
+
+	let mut buf = vec![0; WIDTH * HEIGHT];
+	let cols = Stack { radius: 16, edge_mode: EdgeMode::Renormalize };
+	let mut ops = Default::default();
+	bencher.iter(|| for x in 0..WIDTH {
+		unsafe { blur_column(buf.as_mut_ptr(), WIDTH, HEIGHT, x, &cols, |&pixel| ArgbU32::from_u32(pixel), |pixel, _| pixel.to_u32(), &mut ops) };
+	});
+}
+
+#[bench]
+#[inline(never)]
+fn column_blocks_128(bencher: &mut Bencher) {
+	use crate::color::ArgbU32;
+	use crate::iter::EdgeMode;
+	use crate::lines::{blur_lines, Stack};
+
+	let mut buf = ImgVec::new(vec![0; WIDTH * HEIGHT], WIDTH, HEIGHT);
+	let rows = Stack { radius: 0, edge_mode: EdgeMode::Renormalize };
+	let cols = Stack { radius: 128, edge_mode: EdgeMode::Renormalize };
+	bencher.iter(|| blur_lines(&mut buf.as_mut(), &rows, &cols, |&pixel| ArgbU32::from_u32(pixel), ArgbU32::to_u32));
+}
+
+#[bench]
+#[inline(never)]
+fn single_columns_128(bencher: &mut Bencher) {
+	use crate::color::ArgbU32;
+	use crate::iter::EdgeMode;
+	use crate::lines::{blur_column, Stack};
+
+	let mut buf = vec![0; WIDTH * HEIGHT];
+	let cols = Stack { radius: 128, edge_mode: EdgeMode::Renormalize };
+	let mut ops = Default::default();
+	bencher.iter(|| for x in 0..WIDTH {
+		unsafe { blur_column(buf.as_mut_ptr(), WIDTH, HEIGHT, x, &cols, |&pixel| ArgbU32::from_u32(pixel), |pixel, _| pixel.to_u32(), &mut ops) };
+	});
+}
+
+/// Returns how far blurring a line with `kernel` can be from a Gaussian blur
+/// with the standard deviation `sigma`, as a fraction of the range of the line.
+///
+/// The worst input is 1 wherever `kernel` weighs a pixel more than the
+/// Gaussian does and 0 everywhere else, so this is the sum of those excesses.
+fn gaussian_error(kernel: &[f64], center: usize, sigma: f64) -> f64 {
+	const STEPS: usize = 64;
+
+	let density = |x: f64| (-x * x / (2.0 * sigma * sigma)).exp() / (sigma * (2.0 * std::f64::consts::PI).sqrt());
+
+	kernel.iter().enumerate().map(|(i, weight)| {
+		let x = i as f64 - center as f64 - 0.5;
+		let integral = (0..STEPS).map(|step| density(x + (step as f64 + 0.5) / STEPS as f64)).sum::<f64>() / STEPS as f64;
+		(weight - integral).max(0.0)
+	}).sum()
+}
+
+#[test]
+fn sigma_to_radius_error() {
+	use crate::float::StackBlurrableF64;
+
+	// The bounds documented on `sigma_to_radius`.
+	for (passes, bound) in [(1, 0.057), (2, 0.020), (3, 0.013), (4, 0.009)] {
+		for sigma in (8..=80).map(|quarters| quarters as f32 / 4.0) {
+			let center = (sigma * 8.0) as usize;
+			let mut line = ImgVec::new(vec![0.0; center * 2 + 1], center * 2 + 1, 1);
+			line.buf_mut()[center] = 1.0;
+
+			crate::blur_gaussian(&mut line.as_mut(), sigma, 0.0, passes, |&value| StackBlurrableF64(value), |value| value.0);
+
+			let error = gaussian_error(line.buf(), center, sigma as f64);
+			assert!(error <= bound + 0.0005, "{} passes at sigma {} are off by {}", passes, sigma, error);
+		}
+	}
+}
+
+/// Returns an image of the given size filled with noise from a fixed seed, so
+/// that the tests that compare two ways of blurring it have something to blur.
+fn noise(width: usize, height: usize) -> ImgVec<u32> {
+	let mut state = 0x2545f491u32;
+
+	ImgVec::new((0..width * height).map(|_| {
+		state ^= state << 13;
+		state ^= state >> 17;
+		state ^= state << 5;
+		state
+	}).collect(), width, height)
+}
+
+#[test]
+fn streaming_blur_matches_blur_xy() {
+	use crate::color::StackBlurrableU32;
+	use crate::stream::StreamingBlur;
+
+	const WIDTH: usize = 13;
+	const RADIUS_X: usize = 2;
+	const RADIUS_Y: usize = 3;
+
+	// Shorter than, as tall as and taller than the window of `2 * RADIUS_Y + 1`
+	// rows.
+	for height in [1, 2, RADIUS_Y, RADIUS_Y * 2, RADIUS_Y * 2 + 1, RADIUS_Y * 2 + 2, 20] {
+		let mut input = noise(WIDTH, height);
+		input.pixels_mut().for_each(|pixel| *pixel &= 0xff);
+
+		let mut expected = input.clone();
+		crate::blur_xy(&mut expected.as_mut(), RADIUS_X, RADIUS_Y, |&pixel| StackBlurrableU32(pixel), |pixel| pixel.0);
+
+		let mut streaming = StreamingBlur::new(WIDTH, RADIUS_X, RADIUS_Y);
+		let mut actual = Vec::with_capacity(WIDTH * height);
+
+		for row in input.rows() {
+			if let Some(finished) = streaming.push_row(row.iter().map(|&pixel| StackBlurrableU32(pixel))) {
+				actual.extend(finished.map(|pixel| pixel.0));
+			}
+		}
+
+		while let Some(finished) = streaming.finish_row() {
+			actual.extend(finished.map(|pixel| pixel.0));
+		}
+
+		assert_eq!(actual, expected.buf()[..], "height {}", height);
+	}
+}
+
+/// Blurs `buffer` like [`lines::blur_lines`](crate::lines::blur_lines) does,
+/// but walks down every column by itself rather than in blocks.
+fn blur_lines_per_column<B: crate::traits::StackBlurrable, L: crate::lines::LineBlur<B>>(
+	buffer: &mut imgref::ImgRefMut<u32>,
+	line: &L,
+	to_blurrable: impl Fn(&u32) -> B,
+	to_pixel: impl Fn(B) -> u32
+) {
+	use imgref_iter::traits::ImgIter;
+
+	if line.is_identity() {
+		return;
+	}
+
+	let mut ops = L::Ops::default();
+
+	for row in buffer.rows_mut() {
+		let mut blurred = Vec::with_capacity(row.len());
+		line.blur_line(row.iter().map(&to_blurrable), |pixel| blurred.push(to_pixel(pixel)), &mut ops);
+		row.copy_from_slice(&blurred);
+	}
+
+	let cols = buffer.as_ref().iter_cols().map(|col| {
+		let mut blurred = Vec::with_capacity(buffer.height());
+		line.blur_line(col.map(&to_blurrable), |pixel| blurred.push(to_pixel(pixel)), &mut ops);
+		blurred
+	}).collect::<Vec<_>>();
+
+	for (x, col) in cols.into_iter().enumerate() {
+		for (y, pixel) in col.into_iter().enumerate() {
+			buffer[(x, y)] = pixel;
+		}
+	}
+}
+
+/// Checks that `blur` leaves a buffer exactly like [`blur_lines_per_column`]
+/// does with `line`, for widths on both sides of a multiple of the column
+/// blocks, and within a larger buffer.
+fn check_column_blocks<B: crate::traits::StackBlurrable, L: crate::lines::LineBlur<B>>(
+	name: &str,
+	line: &L,
+	to_blurrable: impl Fn(&u32) -> B + Copy,
+	to_pixel: impl Fn(B) -> u32 + Copy,
+	mut blur: impl FnMut(&mut imgref::ImgRefMut<u32>)
+) {
+	const HEIGHT: usize = 23;
+
+	for width in [1, 5, 15, 16, 17, 33, 40] {
+		for (left, top, right) in [(0, 0, 0), (3, 2, 5)] {
+			let mut expected = noise(left + width + right, top + HEIGHT + 1);
+			let mut actual = expected.clone();
+
+			blur_lines_per_column(&mut expected.sub_image_mut(left, top, width, HEIGHT), line, to_blurrable, to_pixel);
+			blur(&mut actual.sub_image_mut(left, top, width, HEIGHT));
+
+			assert!(actual.buf() == expected.buf(), "{} differs at width {} and offset {}", name, width, left);
+		}
+	}
+}
+
+#[test]
+fn column_blocks_match_single_columns() {
+	use crate::color::{ArgbU32, ArgbU64};
+	use crate::iter::{EdgeMode, Kernel};
+	use crate::lines::{self, Fractional, Gaussian, Shaped, Stack};
+
+	// `ArgbU32` is small enough for column blocks, and `ArgbU64` is not.
+	macro_rules! check {
+		($name:expr, $line:expr) => {{
+			let line = $line;
+			check_column_blocks($name, &line, |&pixel| ArgbU32::from_u32(pixel), ArgbU32::to_u32, |buffer| lines::blur_lines(buffer, &line, &line, |&pixel| ArgbU32::from_u32(pixel), ArgbU32::to_u32));
+			check_column_blocks($name, &line, |&pixel| ArgbU64::from_u32(pixel), ArgbU64::to_u32, |buffer| lines::blur_lines(buffer, &line, &line, |&pixel| ArgbU64::from_u32(pixel), ArgbU64::to_u32));
+
+			#[cfg(feature = "rayon")]
+			{
+				check_column_blocks($name, &line, |&pixel| ArgbU32::from_u32(pixel), ArgbU32::to_u32, |buffer| lines::par_blur_lines(buffer, &line, &line, |&pixel| ArgbU32::from_u32(pixel), ArgbU32::to_u32));
+				check_column_blocks($name, &line, |&pixel| ArgbU64::from_u32(pixel), ArgbU64::to_u32, |buffer| lines::par_blur_lines(buffer, &line, &line, |&pixel| ArgbU64::from_u32(pixel), ArgbU64::to_u32));
+			}
+		}};
+	}
+
+	for edge_mode in [EdgeMode::Renormalize, EdgeMode::Clamp, EdgeMode::Mirror, EdgeMode::Wrap] {
+		// The second radius is larger than the buffer is tall.
+		for radius in [3, 30] {
+			check!("Stack", Stack { radius, edge_mode });
+		}
+
+		check!("Fractional", Fractional { radius: 2.5, edge_mode });
+	}
+
+	for kernel in [Kernel::Box, Kernel::Tent, Kernel::Quadratic] {
+		check!("Shaped", Shaped { radius: 3, kernel });
+	}
+
+	check!("Gaussian", Gaussian { radius: 2.5, passes: 3 });
+
+	// The helpers in the crate root go through the same machinery.
+	let to_blurrable = |&pixel: &u32| ArgbU32::from_u32(pixel);
+	let stack = Stack { radius: 4, edge_mode: EdgeMode::Renormalize };
+	check_column_blocks("blur", &stack, to_blurrable, ArgbU32::to_u32, |buffer| crate::blur(buffer, 4, to_blurrable, ArgbU32::to_u32));
+	check_column_blocks("blur_xy", &stack, to_blurrable, ArgbU32::to_u32, |buffer| crate::blur_xy(buffer, 4, 4, to_blurrable, ArgbU32::to_u32));
+	#[cfg(feature = "rayon")]
+	check_column_blocks("par_blur", &stack, to_blurrable, ArgbU32::to_u32, |buffer| crate::par_blur(buffer, 4, to_blurrable, ArgbU32::to_u32));
+}
+
+#[test]
+fn bytes_match_packed_argb() {
+	use crate::bytes::{self, ChannelLayout};
+	use crate::error::BlurError;
+
+	const WIDTH: usize = 7;
+	const HEIGHT: usize = 5;
+
+	// The position of the alpha channel, if any, and those of red, green and
+	// blue, which are all the same for gray.
+	fn positions(layout: ChannelLayout) -> (Option<usize>, [usize; 3]) {
+		match layout {
+			ChannelLayout::Rgba => (Some(3), [0, 1, 2]),
+			ChannelLayout::Bgra => (Some(3), [2, 1, 0]),
+			ChannelLayout::Argb => (Some(0), [1, 2, 3]),
+			ChannelLayout::Abgr => (Some(0), [3, 2, 1]),
+			ChannelLayout::Rgb => (None, [0, 1, 2]),
+			ChannelLayout::Bgr => (None, [2, 1, 0]),
+			ChannelLayout::Gray => (None, [0, 0, 0]),
+			ChannelLayout::GrayAlpha => (Some(1), [0, 0, 0])
+		}
+	}
+
+	// Blurs the pixels by packing them into ARGB for `blur_argb`, with opaque
+	// alpha for layouts without it, and unpacking them again.
+	fn expected<const N: usize>(pixels: &ImgVec<[u8; N]>, layout: ChannelLayout, radius: usize) -> Vec<[u8; N]> {
+		let (alpha, colors) = positions(layout);
+		let mut packed = ImgVec::new(pixels.buf().iter().map(|pixel| {
+			let [r, g, b] = colors.map(|position| pixel[position]);
+			u32::from_be_bytes([alpha.map_or(u8::MAX, |position| pixel[position]), r, g, b])
+		}).collect(), pixels.width(), pixels.height());
+
+		crate::blur_argb(&mut packed.as_mut(), radius);
+
+		packed.buf().iter().map(|argb| {
+			let [a, r, g, b] = argb.to_be_bytes();
+			let mut pixel = [0; N];
+			colors.into_iter().zip([r, g, b]).for_each(|(position, value)| pixel[position] = value);
+			alpha.into_iter().for_each(|position| pixel[position] = a);
+			pixel
+		}).collect()
+	}
+
+	fn check<const N: usize>(layout: ChannelLayout) {
+		for radius in [1, 4, crate::ARGB_U32_MAX_RADIUS + 1] {
+			let mut pixels = noise(WIDTH, HEIGHT).map_buf(|buf| buf.into_iter().map(|noise| noise.to_be_bytes()[..N].try_into().unwrap()).collect::<Vec<[u8; N]>>());
+			let expected = expected(&pixels, layout, radius);
+
+			// The raw version, with a stride of one pixel more than the width.
+			let stride = (WIDTH + 1) * N;
+			let mut raw = vec![0xaa; stride * HEIGHT];
+			for (y, row) in pixels.rows().enumerate() {
+				raw[y * stride..][..WIDTH * N].copy_from_slice(&row.concat());
+			}
+
+			bytes::blur_bytes(&mut pixels.as_mut(), layout, radius);
+			assert_eq!(pixels.buf(), &expected, "{:?} at radius {}", layout, radius);
+
+			bytes::try_blur_raw_bytes(&mut raw, WIDTH, HEIGHT, stride, layout, radius).unwrap();
+			for (y, row) in raw.chunks(stride).enumerate() {
+				assert_eq!(&row[..WIDTH * N], &expected[y * WIDTH..][..WIDTH].concat()[..], "{:?} at radius {}", layout, radius);
+				assert!(row[WIDTH * N..].iter().all(|&byte| byte == 0xaa), "{:?} wrote past the width", layout);
+			}
+		}
+	}
+
+	check::<4>(ChannelLayout::Rgba);
+	check::<4>(ChannelLayout::Bgra);
+	check::<4>(ChannelLayout::Argb);
+	check::<4>(ChannelLayout::Abgr);
+	check::<3>(ChannelLayout::Rgb);
+	check::<3>(ChannelLayout::Bgr);
+	check::<1>(ChannelLayout::Gray);
+	check::<2>(ChannelLayout::GrayAlpha);
+
+	let mut raw = vec![0; 4 * WIDTH * HEIGHT];
+	assert!(matches!(bytes::try_blur_bytes(&mut ImgVec::new(vec![[0; 4]; 4], 2, 2).as_mut(), ChannelLayout::Rgb, 1), Err(BlurError::UnsupportedParameter { parameter: "layout", .. })));
+	assert!(matches!(bytes::try_blur_raw_bytes(&mut raw, WIDTH, HEIGHT, WIDTH * 4 + 1, ChannelLayout::Rgba, 1), Err(BlurError::UnsupportedParameter { parameter: "stride", .. })));
+	assert!(matches!(bytes::try_blur_raw_bytes(&mut raw, WIDTH, HEIGHT + 1, WIDTH * 4, ChannelLayout::Rgba, 1), Err(BlurError::UnsupportedParameter { parameter: "buffer", .. })));
+}
+
+#[test]
+fn float_rounding_error() {
+	// The bounds documented in the `float` module, at radius 1. Every tenfold
+	// increase of the radius divides them by ten.
+	for (len, bound) in [(1_000, 3e-4), (10_000, 1e-2), (100_000, 0.3)] {
+		let values = noise(len, 1).map_buf(|buf| buf.into_iter().map(|noise| noise as f64 / u32::MAX as f64).collect::<Vec<_>>());
+
+		for (radius, bound) in [(1, bound), (10, bound / 10.0), (100, bound / 100.0)] {
+			let mut single = ImgVec::new(values.buf().iter().map(|&value| value as f32).collect(), len, 1);
+			let mut double = values.clone();
+			crate::float::blur_f32(&mut single.as_mut(), radius);
+			crate::float::blur_f64(&mut double.as_mut(), radius);
+
+			let error = single.buf().iter().zip(double.buf()).map(|(&single, double)| (single as f64 - double).abs()).fold(0.0, f64::max);
+			assert!(error <= bound, "{} pixels at radius {} are off by {}", len, radius, error);
+		}
+	}
+}
+
+#[test]
+fn shadow_extent_and_origin() {
+	use crate::shadow::{Shadow, Softness};
+
+	let alphas = |image: &ImgVec<u32>| image.pixels().map(|pixel| pixel >> 24).collect::<Vec<_>>();
+
+	// A single opaque pixel, blurred by a tent of radius 1 along the rows and
+	// then the columns, reaches one pixel past the layer in every direction.
+	let dot = ImgVec::new(vec![0xFF000000u32], 1, 1);
+	let shadow = Shadow::drop_shadow(3, -2, Softness::Radius(1.0), 0xFF000000).render(dot.as_ref());
+	assert_eq!((shadow.image.width(), shadow.image.height()), (3, 3));
+	assert_eq!(shadow.origin, (2, -3));
+	assert_eq!(alphas(&shadow.image), [15, 31, 15, 31, 63, 31, 15, 31, 15]);
+
+	// Spreading without blurring grows or shrinks the square by exactly the
+	// spread, which only counts towards the extent when it grows.
+	let square = ImgVec::new(vec![0xFF000000u32; 9], 3, 3);
+	let grown = Shadow::outer_glow(1, Softness::Radius(0.0), 0xFF000000).render(square.as_ref());
+	assert_eq!((grown.image.width(), grown.image.height(), grown.origin), (5, 5, (-1, -1)));
+	assert!(alphas(&grown.image).iter().all(|&alpha| alpha == 255));
+
+	let shrunk = Shadow::outer_glow(-1, Softness::Radius(0.0), 0xFF000000).render(square.as_ref());
+	assert_eq!((shrunk.image.width(), shrunk.image.height(), shrunk.origin), (3, 3, (0, 0)));
+	assert_eq!(alphas(&shrunk.image), [0, 0, 0, 0, 255, 0, 0, 0, 0]);
+
+	// Padding the layer with transparent pixels only moves the shadow, so the
+	// shadow of the padded layer has nothing past the extent of the original.
+	const PADDING: usize = 6;
+
+	for shadow in [
+		Shadow::drop_shadow(2, 1, Softness::Radius(2.5), 0x80204060),
+		Shadow::drop_shadow(-1, 3, Softness::Sigma(1.5), 0xFF000000),
+		Shadow { spread: 2, ..Shadow::outer_glow(0, Softness::Radius(1.0), 0xFFFFFFFF) },
+		Shadow { spread: -1, opacity: 0.5, ..Shadow::drop_shadow(1, 1, Softness::Sigma(1.0), 0xFF000000) }
+	] {
+		let layer = noise(5, 4);
+		let mut padded = ImgVec::new(vec![0; (5 + PADDING * 2) * (4 + PADDING * 2)], 5 + PADDING * 2, 4 + PADDING * 2);
+		for (y, row) in layer.rows().enumerate() {
+			padded.sub_image_mut(PADDING, PADDING + y, 5, 1).rows_mut().next().unwrap().copy_from_slice(row);
+		}
+
+		let extent = shadow.extent();
+		let unpadded = shadow.render(layer.as_ref());
+		let padded = shadow.render(padded.as_ref());
+		assert_eq!((unpadded.image.width(), unpadded.image.height()), (5 + extent * 2, 4 + extent * 2), "{:?}", shadow);
+		assert_eq!(unpadded.origin, (shadow.offset.0 - extent as isize, shadow.offset.1 - extent as isize), "{:?}", shadow);
+		// Both are relative to their own layer, so the padding moves the
+		// shadow of the original to the same place within the padded one.
+		assert_eq!(padded.origin, unpadded.origin, "{:?}", shadow);
+
+		for (y, row) in padded.image.rows().enumerate() {
+			for (x, &pixel) in row.iter().enumerate() {
+				let inside = (PADDING..PADDING + unpadded.image.width()).contains(&x) && (PADDING..PADDING + unpadded.image.height()).contains(&y);
+				let expected = if inside { unpadded.image[(x - PADDING, y - PADDING)] >> 24 } else { 0 };
+				assert_eq!(pixel >> 24, expected, "{:?} at {}, {}", shadow, x, y);
+			}
+		}
+	}
+
+	// Compositing puts the shadow underneath the layer at its offset, and
+	// leaves the opaque pixels of the layer alone.
+	let mut layer = ImgVec::new(vec![0u32; 25], 5, 5);
+	layer[(1usize, 1usize)] = 0xFF123456;
+	Shadow::drop_shadow(2, 2, Softness::Radius(0.0), 0xFF000000).composite(&mut layer.as_mut());
+	assert_eq!(layer[(1usize, 1usize)], 0xFF123456);
+	assert_eq!(layer[(3usize, 3usize)], 0xFF000000);
+	assert_eq!(layer.pixels().filter(|&pixel| pixel != 0).count(), 2);
+}
+
+#[test]
+fn difference_matches_two_blurs() {
+	use crate::color::ChannelMask;
+	use crate::difference::{blur_difference_argb, blur_difference_f32, try_blur_difference_argb, try_blur_difference_f32};
+	use crate::error::BlurError;
+
+	const WIDTH: usize = 19;
+	const HEIGHT: usize = 14;
+
+	let input = noise(WIDTH, HEIGHT);
+	let floats = ImgVec::new(input.buf().iter().map(|&pixel| (pixel & 0xff) as f32 / 255.0).collect::<Vec<_>>(), WIDTH, HEIGHT);
+
+	for (radius_narrow, radius_wide) in [(0, 1), (1, 4), (3, 2), (2, 20)] {
+		let blurred = |radius| {
+			let mut blurred = floats.clone();
+			crate::float::blur_f32(&mut blurred.as_mut(), radius);
+			blurred
+		};
+
+		// The difference is signed, so it goes negative wherever the wide blur
+		// is brighter, and it is whatever the two blurs would be apart.
+		let (narrow, wide) = (blurred(radius_narrow), blurred(radius_wide));
+		let mut difference = ImgVec::new(vec![0.0; WIDTH * HEIGHT], WIDTH, HEIGHT);
+		blur_difference_f32(floats.as_ref(), &mut difference.as_mut(), radius_narrow, radius_wide);
+		assert!(difference.buf().iter().any(|&value| value < 0.0));
+
+		for ((&difference, &narrow), &wide) in difference.buf().iter().zip(narrow.buf()).zip(wide.buf()) {
+			assert!((difference - (narrow - wide)).abs() <= 1e-5, "radii {}, {}: {} is not {} - {}", radius_narrow, radius_wide, difference, narrow, wide);
+		}
+
+		// Every channel in the mask is encoded around 128, and the alpha is
+		// left as it was in the input.
+		let blurred = |radius| {
+			let mut blurred = input.clone();
+			crate::blur_argb(&mut blurred.as_mut(), radius);
+			blurred
+		};
+
+		let (narrow, wide) = (blurred(radius_narrow), blurred(radius_wide));
+		let mut difference = ImgVec::new(vec![0; WIDTH * HEIGHT], WIDTH, HEIGHT);
+		blur_difference_argb(input.as_ref(), &mut difference.as_mut(), radius_narrow, radius_wide, ChannelMask::COLOR);
+
+		for (((&difference, &pixel), &narrow), &wide) in difference.buf().iter().zip(input.buf()).zip(narrow.buf()).zip(wide.buf()) {
+			let expected = u32::from_be_bytes([0, 1, 2, 3].map(|channel| {
+				let (narrow, wide) = (narrow.to_be_bytes()[channel] as i32, wide.to_be_bytes()[channel] as i32);
+				(narrow - wide + 128).clamp(0, 255) as u8
+			}));
+
+			assert_eq!(difference, expected & 0x00ffffff | pixel & 0xff000000, "radii {}, {}", radius_narrow, radius_wide);
+		}
+	}
+
+	// An output of a different size is refused and left alone.
+	let mut output = ImgVec::new(vec![0.5; WIDTH * (HEIGHT - 1)], WIDTH, HEIGHT - 1);
+	assert!(matches!(try_blur_difference_f32(floats.as_ref(), &mut output.as_mut(), 1, 4), Err(BlurError::UnsupportedParameter { .. })));
+	assert!(output.buf().iter().all(|&value| value == 0.5));
+
+	let mut output = ImgVec::new(vec![0; (WIDTH + 1) * HEIGHT], WIDTH + 1, HEIGHT);
+	assert!(matches!(try_blur_difference_argb(input.as_ref(), &mut output.as_mut(), 1, 4, ChannelMask::ALL), Err(BlurError::UnsupportedParameter { .. })));
+	assert!(output.buf().iter().all(|&pixel| pixel == 0));
+}
+
+#[test]
+fn guided_filter_flat_and_step() {
+	use crate::guided::GuidedFilter;
+
+	const WIDTH: usize = 24;
+	const HEIGHT: usize = 9;
+	const EDGE: usize = 11;
+
+	let filter = GuidedFilter::new(4, 0.0001);
+	let noise = ImgVec::new(noise(WIDTH, HEIGHT).buf().iter().map(|&pixel| (pixel & 0xff) as f32 / 255.0 - 0.5).collect::<Vec<_>>(), WIDTH, HEIGHT);
+
+	// Within a flat region, the filter leaves a flat input as it is, and a guide
+	// without edges lets it average the noise of the input away.
+	let flat = ImgVec::new(vec![0.25; WIDTH * HEIGHT], WIDTH, HEIGHT);
+	let mut output = flat.clone();
+	filter.filter_self(&mut output.as_mut());
+	assert!(output.buf().iter().all(|&value| (value - 0.25).abs() <= 1e-5), "{:?}", output.buf());
+
+	let mut output = ImgVec::new(noise.buf().iter().map(|&value| 0.25 + value * 0.1).collect(), WIDTH, HEIGHT);
+	let spread = |image: &ImgVec<f32>| image.buf().iter().fold(0.0f32, |spread, &value| spread.max((value - 0.25).abs()));
+	let before = spread(&output);
+	filter.filter_gray(flat.as_ref(), &mut output.as_mut());
+	assert!(spread(&output) < before / 2.0, "{} is not much less than {}", spread(&output), before);
+
+	// Across a step in the guide, the noise is smoothed away on both sides, but
+	// the step itself stays as sharp as it was, unlike with a plain blur.
+	let step = ImgVec::new((0..WIDTH * HEIGHT).map(|i| if i % WIDTH < EDGE { 0.0 } else { 1.0 }).collect::<Vec<f32>>(), WIDTH, HEIGHT);
+	let noisy = ImgVec::new(step.buf().iter().zip(noise.buf()).map(|(&value, &noise)| value + noise * 0.1).collect::<Vec<_>>(), WIDTH, HEIGHT);
+
+	let check = |output: &ImgVec<f32>, what: &str| {
+		for ((&value, &expected), i) in output.buf().iter().zip(step.buf()).zip(0..) {
+			assert!((value - expected).abs() <= 0.03, "{} at {}, {}: {} is not {}", what, i % WIDTH, i / WIDTH, value, expected);
+		}
+	};
+
+	let mut output = noisy.clone();
+	filter.filter_gray(step.as_ref(), &mut output.as_mut());
+	check(&output, "gray guide");
+
+	let mut output = noisy.clone();
+	let color = ImgVec::new(step.buf().iter().map(|&value| [value, 0.5, 1.0 - value]).collect(), WIDTH, HEIGHT);
+	filter.filter_color(color.as_ref(), &mut output.as_mut());
+	check(&output, "color guide");
+
+	let mut output = step.clone();
+	filter.filter_self(&mut output.as_mut());
+	check(&output, "self guide");
+
+	let mut blurred = noisy.clone();
+	crate::float::blur_f32(&mut blurred.as_mut(), 4);
+	assert!((blurred[(EDGE, 0)] - 1.0).abs() > 0.03);
+}
+
+#[test]
+fn pyramid_collapse() {
+	use crate::color::ArgbU32;
+	use crate::float::StackBlurrableF32;
+	use crate::pyramid::Pyramid;
+
+	const WIDTH: usize = 37;
+	const HEIGHT: usize = 22;
+	const LEVELS: usize = 5;
+
+	// Collapsing a Laplacian pyramid of a wrapping integer type gives back the
+	// exact image, even though its levels wrap around wherever they are
+	// negative.
+	let input = noise(WIDTH, HEIGHT).map_buf(|buf| buf.into_iter().map(|pixel| pixel & 0x00ffffff | 0xff000000).collect::<Vec<_>>());
+	let mut pyramid = Pyramid::<ArgbU32>::new(2);
+	pyramid.build_laplacian(input.as_ref(), LEVELS, |&pixel| ArgbU32::from_u32(pixel));
+	assert_eq!(pyramid.len(), LEVELS);
+	assert_eq!(pyramid.size(LEVELS - 1), (3, 2));
+	pyramid.collapse();
+	assert!(pyramid.level(0).pixels().map(|pixel| pixel.to_u32()).eq(input.pixels()));
+
+	#[cfg(feature = "rayon")]
+	{
+		pyramid.par_build_laplacian(input.as_ref(), LEVELS, |&pixel| ArgbU32::from_u32(pixel));
+		pyramid.par_collapse();
+		assert!(pyramid.level(0).pixels().map(|pixel| pixel.to_u32()).eq(input.pixels()));
+	}
+
+	let floats = |image: &ImgVec<u32>, shift: u32| ImgVec::new(image.buf().iter().map(|&pixel| ((pixel >> shift) & 0xff) as f32 / 255.0).collect::<Vec<_>>(), WIDTH, HEIGHT);
+	let (a, b) = (floats(&input, 0), floats(&input, 8));
+
+	let mut pyramid_a = Pyramid::new(2);
+	pyramid_a.build_laplacian(a.as_ref(), LEVELS, |&value| StackBlurrableF32(value));
+	pyramid_a.collapse();
+	for (collapsed, &value) in pyramid_a.level(0).pixels().zip(a.buf()) {
+		assert!((collapsed.0 - value).abs() <= 1e-5, "{} is not {}", collapsed.0, value);
+	}
+
+	// Blending two images by a constant mask mixes every level of them the same
+	// way, so collapsing the blend gives the same mix of the images.
+	const WEIGHT: f32 = 0.25;
+
+	let mask = ImgVec::new(vec![WEIGHT; WIDTH * HEIGHT], WIDTH, HEIGHT);
+	let mut pyramid_mask = Pyramid::new(2);
+	pyramid_mask.build_gaussian(mask.as_ref(), LEVELS, |&value| StackBlurrableF32(value));
+
+	let mut pyramid_b = Pyramid::new(2);
+	pyramid_a.build_laplacian(a.as_ref(), LEVELS, |&value| StackBlurrableF32(value));
+	pyramid_b.build_laplacian(b.as_ref(), LEVELS, |&value| StackBlurrableF32(value));
+
+	for index in 0..LEVELS {
+		let (b, mask) = (pyramid_b.level(index), pyramid_mask.level(index));
+		for ((a, b), mask) in pyramid_a.level_mut(index).pixels_mut().zip(b.pixels()).zip(mask.pixels()) {
+			a.0 = a.0 * mask.0 + b.0 * (1.0 - mask.0);
+		}
+	}
+
+	pyramid_a.collapse();
+	for ((blended, &a), &b) in pyramid_a.level(0).pixels().zip(a.buf()).zip(b.buf()) {
+		let expected = a * WEIGHT + b * (1.0 - WEIGHT);
+		assert!((blended.0 - expected).abs() <= 1e-5, "{} is not {}", blended.0, expected);
+	}
+}
+
+#[test]
+fn temporal_blur_matches_stack_blur() {
+	use std::collections::VecDeque;
+	use crate::color::ArgbU32;
+	use crate::iter::StackBlur;
+	use crate::stream::{ArgbTemporalBlur, TemporalBlur};
+
+	const WIDTH: usize = 5;
+	const HEIGHT: usize = 3;
+	const RADIUS: usize = 3;
+
+	// Fewer frames than, as many as and more than the window of
+	// `2 * RADIUS + 1` frames.
+	for len in [1, 2, RADIUS, RADIUS * 2, RADIUS * 2 + 1, RADIUS * 2 + 2, 15] {
+		let video = noise(WIDTH, HEIGHT * len);
+		let frames = (0..len).map(|index| video.sub_image(0, HEIGHT * index, WIDTH, HEIGHT)).collect::<Vec<_>>();
+
+		// Every pixel is blurred on its own, over that pixel of every frame.
+		let mut expected = vec![Vec::with_capacity(WIDTH * HEIGHT); len];
+		for (x, y) in (0..HEIGHT).flat_map(|y| (0..WIDTH).map(move |x| (x, y))) {
+			let pixels = frames.iter().map(|frame| ArgbU32::from_u32(frame[(x, y)]));
+			for (frame, pixel) in expected.iter_mut().zip(StackBlur::new(pixels, RADIUS, VecDeque::new())) {
+				frame.push(pixel.to_u32());
+			}
+		}
+
+		let mut temporal = TemporalBlur::new(WIDTH, HEIGHT, RADIUS);
+		let mut actual = Vec::with_capacity(len);
+
+		for frame in &frames {
+			if let Some(finished) = temporal.push_frame(*frame, |&pixel| ArgbU32::from_u32(pixel)) {
+				actual.push(finished.map(ArgbU32::to_u32).collect::<Vec<_>>());
+			}
+		}
+
+		while let Some(finished) = temporal.finish_frame() {
+			actual.push(finished.map(ArgbU32::to_u32).collect());
+		}
+
+		assert_eq!(actual, expected, "{} frames", len);
+
+		let mut argb = ArgbTemporalBlur::new(WIDTH, HEIGHT, RADIUS);
+		let mut output = ImgVec::new(vec![0; WIDTH * HEIGHT], WIDTH, HEIGHT);
+		let mut actual = Vec::with_capacity(len);
+
+		for frame in &frames {
+			if argb.push_frame(*frame, &mut output.as_mut()) {
+				actual.push(output.buf().clone());
+			}
+		}
+
+		while argb.finish_frame(&mut output.as_mut()) {
+			actual.push(output.buf().clone());
+		}
+
+		assert_eq!(actual, expected, "{} frames", len);
+	}
+}
+
+#[test]
+fn variable_blur_matches_stack_blur() {
+	use std::collections::VecDeque;
+	use crate::color::StackBlurrableU32;
+	use crate::iter::{StackBlur, VariableStackBlur};
+
+	const WIDTH: usize = 17;
+	const HEIGHT: usize = 11;
+
+	let mut input = noise(WIDTH, HEIGHT);
+	input.pixels_mut().for_each(|pixel| *pixel &= 0xff);
+
+	// With the same radius everywhere, the rows and columns are blurred the
+	// same way as by `blur_xy`.
+	for radius in [0, 1, 3, 20] {
+		let mut expected = input.clone();
+		crate::blur_xy(&mut expected.as_mut(), radius, radius, |&pixel| StackBlurrableU32(pixel), |pixel| pixel.0);
+
+		let radii = ImgVec::new(vec![radius as u16; WIDTH * HEIGHT], WIDTH, HEIGHT);
+		let mut actual = input.clone();
+		crate::blur_variable(&mut actual.as_mut(), radii.as_ref(), |&pixel| StackBlurrableU32(pixel), |pixel| pixel.0);
+		assert_eq!(actual.buf(), expected.buf(), "radius {}", radius);
+	}
+
+	// Along a single line, every item comes out as a `StackBlur` with its own
+	// radius would yield it, whatever the radii of its neighbors.
+	let line = input.buf()[..WIDTH * 3].to_vec();
+	// Mostly small radii, including 0, and a few that reach past both ends.
+	let radii = noise(line.len(), 1).buf().iter().map(|&radius| match radius % 12 {
+		radius @ 0..=8 => radius as usize,
+		_ => 60
+	}).collect::<Vec<_>>();
+	assert!(radii.contains(&0) && radii.contains(&60));
+	let mut ops = Vec::new();
+
+	for _ in 0..2 {
+		let mut blur = VariableStackBlur::new(line.iter().map(|&pixel| StackBlurrableU32(pixel)).zip(radii.iter().copied()), ops);
+
+		for (index, (actual, &radius)) in blur.by_ref().zip(&radii).enumerate() {
+			let expected = StackBlur::new(line.iter().map(|&pixel| StackBlurrableU32(pixel)), radius, VecDeque::new()).nth(index).unwrap();
+			assert_eq!(actual.0, expected.0, "radius {} at {}", radius, index);
+		}
+
+		ops = blur.into_ops();
+	}
+}
+
+/// Blurs `line` the slow way, by weighting every item within `radius` of each
+/// position by `weight` of its distance, and making up the items off the edges
+/// the way `edge_mode` describes.
+fn naive_blur(line: &[u32], radius: usize, edge_mode: crate::iter::EdgeMode, weight: impl Fn(usize) -> u64) -> Vec<u32> {
+	use crate::iter::EdgeMode;
+
+	let len = line.len() as isize;
+	let item = |index: isize| match edge_mode {
+		_ if (0..len).contains(&index) => Some(line[index as usize]),
+		EdgeMode::Renormalize => None,
+		EdgeMode::Clamp => Some(line[index.clamp(0, len - 1) as usize]),
+		EdgeMode::Mirror if len == 1 => Some(line[0]),
+		EdgeMode::Mirror => {
+			let period = (len - 1) * 2;
+			let index = index.rem_euclid(period);
+			Some(line[index.min(period - index) as usize])
+		}
+		EdgeMode::Wrap => Some(line[index.rem_euclid(len) as usize])
+	};
+
+	(0..len).map(|center| {
+		let (mut sum, mut total) = (0, 0);
+
+		for offset in -(radius as isize)..=radius as isize {
+			if let Some(item) = item(center + offset) {
+				sum += weight(offset.unsigned_abs()) * item as u64;
+				total += weight(offset.unsigned_abs());
+			}
+		}
+
+		(sum / total) as u32
+	}).collect()
+}
+
+/// Returns the weights of `kernel` with the given radius, from one end to the
+/// other, by convolving the box blurs that it is made of.
+fn kernel_weights(kernel: crate::iter::Kernel, radius: usize) -> Vec<u64> {
+	use crate::iter::Kernel;
+
+	let widths = match kernel {
+		Kernel::Box => vec![radius * 2 + 1],
+		Kernel::Tent => vec![radius + 1; 2],
+		Kernel::Quadratic => (0..3).map(|index| (radius * 2 + 3) / 3 + usize::from(index < (radius * 2 + 3) % 3)).collect()
+	};
+
+	widths.into_iter().fold(vec![1], |weights, width| {
+		let mut convolved = vec![0; weights.len() + width - 1];
+		for (index, weight) in weights.into_iter().enumerate() {
+			convolved[index..index + width].iter_mut().for_each(|sum| *sum += weight);
+		}
+
+		convolved
+	})
+}
+
+#[test]
+fn edge_modes_match_naive_blur() {
+	use std::collections::VecDeque;
+	use crate::color::StackBlurrableU32;
+	use crate::iter::{EdgeMode, StackBlur};
+
+	let items = noise(12, 1).buf().iter().map(|&item| item & 0xff).collect::<Vec<_>>();
+
+	for mode in [EdgeMode::Renormalize, EdgeMode::Clamp, EdgeMode::Mirror, EdgeMode::Wrap] {
+		for len in [1, 2, 3, 5, 12] {
+			let line = &items[..len];
+
+			// Including radii that reach past the other end of the line, where
+			// Mirror and Wrap have to go around more than once.
+			for radius in [0, 1, 2, 4, 11, 30] {
+				let expected = naive_blur(line, radius, mode, |distance| (radius + 1 - distance) as u64);
+				let actual = StackBlur::with_edge_mode(line.iter().map(|&item| StackBlurrableU32(item)), radius, mode, VecDeque::new()).map(|item| item.0).collect::<Vec<_>>();
+				assert_eq!(actual, expected, "{:?} of {:?} at radius {}", mode, line, radius);
+			}
+		}
+	}
+}
+
+#[test]
+fn masked_blurs_leave_other_channels_alone() {
+	use crate::color::ChannelMask;
+
+	const WIDTH: usize = 21;
+	const HEIGHT: usize = 13;
+
+	let input = noise(WIDTH, HEIGHT);
+
+	for radius in [1, 6, crate::ARGB_U32_MAX_RADIUS + 1] {
+		let mut blurred = input.clone();
+		crate::blur_argb(&mut blurred.as_mut(), radius);
+
+		for mask in [ChannelMask::ALPHA, ChannelMask::COLOR, ChannelMask::RED.union(ChannelMask::BLUE)] {
+			// Every byte in the mask is blurred exactly like `blur_argb` blurs it,
+			// and every other byte is exactly what it was.
+			let bits = mask.bits();
+			let check = |actual: &ImgVec<u32>, what: &str| {
+				for ((&actual, &pixel), &blurred) in actual.buf().iter().zip(input.buf()).zip(blurred.buf()) {
+					assert_eq!(actual & !bits, pixel & !bits, "{} left {:?} at radius {}", what, mask, radius);
+					assert_eq!(actual & bits, blurred & bits, "{} blurred {:?} at radius {}", what, mask, radius);
+				}
+			};
+
+			let mut actual = input.clone();
+			crate::blur_argb_masked(&mut actual.as_mut(), radius, mask);
+			check(&actual, "blur_argb_masked");
+
+			#[cfg(feature = "rayon")]
+			{
+				let mut actual = input.clone();
+				crate::par_blur_argb_masked(&mut actual.as_mut(), radius, mask);
+				check(&actual, "par_blur_argb_masked");
+			}
+
+			#[cfg(feature = "simd")]
+			{
+				let mut actual = input.clone();
+				crate::simd_blur_argb_masked::<8>(&mut actual.as_mut(), radius, mask);
+				check(&actual, "simd_blur_argb_masked");
+			}
+
+			#[cfg(all(feature = "simd", feature = "rayon"))]
+			{
+				let mut actual = input.clone();
+				crate::par_simd_blur_argb_masked::<8>(&mut actual.as_mut(), radius, mask);
+				check(&actual, "par_simd_blur_argb_masked");
+			}
+		}
+	}
+}
+
+#[test]
+fn try_helpers_check_their_parameters() {
+	use crate::color::StackBlurrableU32;
+	use crate::error::BlurError;
+	use crate::iter::Kernel;
+
+	let tent = |radius| Kernel::Tent.total_weight(radius);
+	let fits_u64 = |radius: usize| 255 * (radius as u128 + 1).pow(2) <= u64::MAX as u128;
+
+	// The largest radius that the `_argb` helpers report is the last one whose
+	// sums still fit in 64 bits per channel, and it is the same one that they
+	// report for every radius past it.
+	let Err(BlurError::RadiusTooLarge { radius, max_radius }) = crate::check_radius(crate::ARGB_CHANNEL_MAX, usize::MAX, tent) else { panic!("usize::MAX fits") };
+	assert_eq!((radius, max_radius), (usize::MAX, crate::ARGB_U64_MAX_RADIUS));
+	assert!(fits_u64(max_radius) && !fits_u64(max_radius + 1));
+	assert_eq!(crate::check_radius(crate::ARGB_CHANNEL_MAX, max_radius, tent), Ok(()));
+	assert_eq!(crate::check_radius(crate::ARGB_CHANNEL_MAX, max_radius + 1, tent), Err(BlurError::RadiusTooLarge { radius: max_radius + 1, max_radius }));
+
+	let mut buffer = noise(4, 3);
+	let input = buffer.clone();
+	assert_eq!(crate::try_blur_argb(&mut buffer.as_mut(), max_radius + 1), Err(BlurError::RadiusTooLarge { radius: max_radius + 1, max_radius }));
+	assert_eq!(buffer.buf(), input.buf());
+
+	// The generic helpers only refuse radii whose weights don't add up in a
+	// `usize`, and otherwise blur just like the helpers they wrap.
+	let Err(BlurError::RadiusTooLarge { max_radius, .. }) = crate::try_blur(&mut buffer.as_mut(), usize::MAX / 2, |&pixel| StackBlurrableU32(pixel), |pixel| pixel.0) else { panic!("usize::MAX / 2 fits") };
+	assert!(tent(max_radius) < usize::MAX && tent(max_radius + 1) == usize::MAX);
+	assert!(matches!(crate::try_blur_kernel(&mut buffer.as_mut(), 1, usize::MAX, Kernel::Box, |&pixel| StackBlurrableU32(pixel), |pixel| pixel.0), Err(BlurError::RadiusTooLarge { .. })));
+	assert_eq!(buffer.buf(), input.buf());
+
+	let mut expected = buffer.clone();
+	crate::blur_xy(&mut expected.as_mut(), 2, 1, |&pixel| StackBlurrableU32(pixel & 0xff), |pixel| pixel.0);
+	assert_eq!(crate::try_blur_xy(&mut buffer.as_mut(), 2, 1, |&pixel| StackBlurrableU32(pixel & 0xff), |pixel| pixel.0), Ok(()));
+	assert_eq!(buffer.buf(), expected.buf());
+
+	// A mismatched output is named as such, not as the input.
+	let input = noise(4, 3);
+	let (mut right, mut wrong) = (input.clone(), ImgVec::new(vec![0; 12], 3, 4));
+	let error = |parameter| Err(BlurError::UnsupportedParameter { parameter, reason: "must be the same size as the input" });
+	assert_eq!(crate::try_blur_argb_multi(input.as_ref(), [&mut right.as_mut(), &mut wrong.as_mut()], [1, 2]), error("outputs"));
+	assert_eq!(crate::difference::try_blur_difference_argb(input.as_ref(), &mut wrong.as_mut(), 1, 2, crate::color::ChannelMask::ALL), error("output"));
+	assert_eq!(crate::difference::try_blur_difference(input.as_ref(), &mut wrong.as_mut(), 1, 2, |&pixel| StackBlurrableU32(pixel), |pixel| pixel.0), error("output"));
+}
+
+#[test]
+fn straight_alpha_round_trips_and_halos() {
+	use crate::color::ArgbU32;
+
+	const WIDTH: usize = 16;
+	const HEIGHT: usize = 5;
+
+	// Opaque pixels come back exactly, and fully transparent ones come back
+	// black, whatever their color was.
+	let opaque = noise(WIDTH, HEIGHT).map_buf(|buf| buf.into_iter().map(|pixel| pixel | 0xff000000).collect::<Vec<_>>());
+	for &pixel in opaque.buf() {
+		assert_eq!(ArgbU32::from_u32_straight(pixel).to_u32_straight(), pixel);
+		assert_eq!(ArgbU32::from_u32_straight(pixel & 0x00ffffff).to_u32_straight(), 0);
+	}
+
+	let mut flat = ImgVec::new(vec![0xff336699; WIDTH * HEIGHT], WIDTH, HEIGHT);
+	crate::blur_argb_straight(&mut flat.as_mut(), 3);
+	assert!(flat.pixels().all(|pixel| pixel == 0xff336699));
+
+	let mut transparent = opaque.map_buf(|buf| buf.into_iter().map(|pixel| pixel & 0x00ffffff).collect::<Vec<_>>());
+	crate::blur_argb_straight(&mut transparent.as_mut(), 3);
+	assert!(transparent.pixels().all(|pixel| pixel == 0));
+
+	// A shape next to transparent pixels of some other color fades out without
+	// taking on any of that color, which blurring the channels one at a time
+	// would give it.
+	const COLOR: u32 = 0x204080;
+	let shape = ImgVec::new((0..WIDTH * HEIGHT).map(|i| if i % WIDTH < WIDTH / 2 { 0xff000000 | COLOR } else { 0x00ffffff }).collect::<Vec<_>>(), WIDTH, HEIGHT);
+
+	let check = |blurred: &ImgVec<u32>, what: &str| {
+		for (index, &pixel) in blurred.buf().iter().enumerate() {
+			let [alpha, color @ ..] = pixel.to_be_bytes();
+			let expected = &COLOR.to_be_bytes()[1..];
+			if alpha == 0 {
+				assert_eq!(pixel, 0, "{} at {}", what, index);
+			} else {
+				assert!(color.iter().zip(expected).all(|(&actual, &expected)| actual.abs_diff(expected) <= 1), "{} tinted {:08x} at {}", what, pixel, index);
+			}
+		}
+
+		assert!(blurred.pixels().any(|pixel| (1..255).contains(&(pixel >> 24))), "{} left no halo", what);
+	};
+
+	let mut blurred = shape.clone();
+	crate::blur_argb_straight(&mut blurred.as_mut(), 4);
+	check(&blurred, "blur_argb_straight");
+
+	#[cfg(feature = "rayon")]
+	{
+		let mut blurred = shape.clone();
+		crate::par_blur_argb_straight(&mut blurred.as_mut(), 4);
+		check(&blurred, "par_blur_argb_straight");
+	}
+
+	let mut tinted = shape.clone();
+	crate::blur_argb(&mut tinted.as_mut(), 4);
+	assert!(tinted.pixels().any(|pixel| pixel & 0xff0000 > 0x300000));
+}
+
+#[test]
+fn weighted_blur_matches_naive_normalized_convolution() {
+	use std::collections::VecDeque;
+	use crate::color::StackBlurrableU32;
+	use crate::float::StackBlurrableF64;
+	use crate::iter::{Kernel, WeightedBlur};
+
+	const WIDTH: usize = 23;
+	const HEIGHT: usize = 9;
+
+	// Plenty of weights of 0, including runs longer than some of the radii.
+	let values = noise(WIDTH, HEIGHT).map_buf(|buf| buf.into_iter().map(|value| value & 0xff).collect::<Vec<_>>());
+	let weights = ImgVec::new(noise(WIDTH, HEIGHT).buf().iter().enumerate().map(|(index, &weight)| if index % WIDTH < 5 { 0 } else { (weight >> 8) as u8 % 4 * 85 }).collect::<Vec<_>>(), WIDTH, HEIGHT);
+
+	for kernel in [Kernel::Box, Kernel::Tent, Kernel::Quadratic] {
+		for radius in [0, 1, 3, 7] {
+			let k_x = kernel_weights(kernel, radius);
+			let k = |offset: isize| k_x[(offset + radius as isize) as usize];
+
+			// Along a single line, every output and the sum of the weights that
+			// went into it are exactly what the slow way gives.
+			for (line, line_weights) in values.rows().zip(weights.rows()) {
+				let blur = WeightedBlur::new(line.iter().map(|&value| StackBlurrableU32(value)).zip(line_weights.iter().map(|&weight| weight as usize)), radius, kernel, VecDeque::new());
+
+				for (center, (value, weight)) in blur.enumerate() {
+					let (mut sum, mut total) = (0, 0);
+					for index in center.saturating_sub(radius)..(center + radius + 1).min(WIDTH) {
+						let weight = line_weights[index] as u64 * k(index as isize - center as isize);
+						sum += weight * line[index] as u64;
+						total += weight;
+					}
+
+					assert_eq!((value.0 as u64, weight as u64), (sum.checked_div(total).unwrap_or(0), total), "{:?} at radius {}", kernel, radius);
+				}
+			}
+
+			// In 2D, it is a normalized convolution with the kernel along both
+			// axes, and pixels without any weight in reach come out as 0.
+			let mut actual = ImgVec::new(values.buf().iter().map(|&value| value as f64).collect::<Vec<_>>(), WIDTH, HEIGHT);
+			crate::blur_weighted(&mut actual.as_mut(), weights.as_ref(), radius, radius + 1, kernel, |&value| StackBlurrableF64(value), |value| value.0);
+
+			let k_y = kernel_weights(kernel, radius + 1);
+			for (x, y) in (0..HEIGHT).flat_map(|y| (0..WIDTH).map(move |x| (x, y))) {
+				let (mut sum, mut total) = (0.0, 0.0);
+				for y2 in y.saturating_sub(radius + 1)..(y + radius + 2).min(HEIGHT) {
+					for x2 in x.saturating_sub(radius)..(x + radius + 1).min(WIDTH) {
+						let weight = (weights[(x2, y2)] as u64 * k(x2 as isize - x as isize) * k_y[y2 + radius + 1 - y]) as f64;
+						sum += weight * values[(x2, y2)] as f64;
+						total += weight;
+					}
+				}
+
+				let expected = if total == 0.0 { 0.0 } else { sum / total };
+				assert!((actual[(x, y)] - expected).abs() <= 1e-9, "{:?} at radius {}: {} is not {} at {}, {}", kernel, radius, actual[(x, y)], expected, x, y);
+			}
+		}
+	}
+}
+
+#[test]
+fn unsharp_mask_overshoots_edges() {
+	use crate::color::ChannelMask;
+
+	const WIDTH: usize = 8;
+	const HEIGHT: usize = 4;
+
+	// The alpha is noise, which `ChannelMask::COLOR` must leave alone, and the
+	// color steps from 64 to 192 halfway along every row.
+	let alphas = noise(WIDTH, HEIGHT);
+	let step = ImgVec::new((0..WIDTH * HEIGHT).map(|i| alphas.buf()[i] & 0xff000000 | if i % WIDTH < WIDTH / 2 { 0x404040 } else { 0xc0c0c0 }).collect::<Vec<_>>(), WIDTH, HEIGHT);
+	let sharpen = |image: &ImgVec<u32>, amount, threshold, mask| {
+		let mut image = image.clone();
+		crate::unsharp_mask_argb(&mut image.as_mut(), 1, amount, threshold, mask);
+		image
+	};
+
+	let flat = ImgVec::new(vec![0x80336699; WIDTH * HEIGHT], WIDTH, HEIGHT);
+	assert_eq!(sharpen(&flat, 2.0, 0, ChannelMask::ALL).buf(), flat.buf());
+
+	// A tent of radius 1 blurs the two pixels at the step to
+	// (64 + 2 * 64 + 192) / 4 = 96 and (64 + 2 * 192 + 192) / 4 = 160, so they
+	// move away from those by `amount` times 32, as far as a byte goes.
+	for (amount, threshold, low, high) in [(1.0, 0, 32, 224), (0.5, 32, 48, 208), (3.0, 0, 0, 255), (1.0, 33, 64, 192)] {
+		let sharpened = sharpen(&step, amount, threshold, ChannelMask::COLOR);
+
+		for (i, (&pixel, &original)) in sharpened.buf().iter().zip(step.buf()).enumerate() {
+			let expected = match i % WIDTH {
+				x if x == WIDTH / 2 - 1 => low,
+				x if x == WIDTH / 2 => high,
+				_ => original & 0xff
+			};
+
+			assert_eq!(pixel, original & 0xff000000 | (expected * 0x010101), "amount {}, threshold {} at {}", amount, threshold, i);
+		}
+	}
+
+	// Every flavor of the helper agrees with the plain one.
+	#[cfg(any(feature = "rayon", feature = "simd"))]
+	for mask in [ChannelMask::ALL, ChannelMask::COLOR, ChannelMask::GREEN] {
+		let input = noise(21, 13);
+		let expected = sharpen(&input, 1.5, 4, mask);
+
+		#[cfg(feature = "rayon")]
+		{
+			let mut actual = input.clone();
+			crate::par_unsharp_mask_argb(&mut actual.as_mut(), 1, 1.5, 4, mask);
+			assert_eq!(actual.buf(), expected.buf(), "par_unsharp_mask_argb with {:?}", mask);
+		}
+
+		#[cfg(feature = "simd")]
+		{
+			let mut actual = input.clone();
+			crate::simd_unsharp_mask_argb::<8>(&mut actual.as_mut(), 1, 1.5, 4, mask);
+			assert_eq!(actual.buf(), expected.buf(), "simd_unsharp_mask_argb with {:?}", mask);
+		}
+
+		#[cfg(all(feature = "simd", feature = "rayon"))]
+		{
+			let mut actual = input.clone();
+			crate::par_simd_unsharp_mask_argb::<8>(&mut actual.as_mut(), 1, 1.5, 4, mask);
+			assert_eq!(actual.buf(), expected.buf(), "par_simd_unsharp_mask_argb with {:?}", mask);
+		}
+	}
+}
+
+#[test]
+fn multi_blur_matches_separate_blurs() {
+	use std::collections::VecDeque;
+	use crate::color::StackBlurrableU32;
+	use crate::float::StackBlurrableF64;
+	use crate::iter::{MultiStackBlur, StackBlur};
+
+	const WIDTH: usize = 19;
+	const HEIGHT: usize = 7;
+
+	let input = noise(WIDTH, HEIGHT);
+	let radii_sets: [[usize; 3]; 4] = [[0, 1, 2], [3, 0, 9], [5, 5, 5], [0, 25, 1]];
+	let mut ops = VecDeque::new();
+
+	// Along a line, every output is exactly what a `StackBlur` with that
+	// radius yields, including radii that reach past both ends.
+	for radii in radii_sets {
+		for row in input.rows() {
+			let mut blur = MultiStackBlur::new(row.iter().map(|&pixel| StackBlurrableU32(pixel & 0xff)), radii, ops);
+			let actual = blur.by_ref().collect::<Vec<_>>();
+			assert_eq!(actual.len(), row.len());
+
+			for (n, &radius) in radii.iter().enumerate() {
+				let expected = StackBlur::new(row.iter().map(|&pixel| StackBlurrableU32(pixel & 0xff)), radius, VecDeque::new());
+
+				for (index, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+					assert_eq!(actual[n].0, expected.0, "radius {} of {:?} at {}", radius, radii, index);
+				}
+			}
+
+			ops = blur.into_ops();
+		}
+	}
+
+	// In floating point, nothing is rounded between the rows and the columns,
+	// so `blur_multi` leaves what `blur` would.
+	let input = ImgVec::new(input.buf().iter().map(|&pixel| (pixel & 0xffff) as f64).collect(), WIDTH, HEIGHT);
+
+	for radii in radii_sets {
+		let mut outputs = [(); 3].map(|_| ImgVec::new(vec![0.0; WIDTH * HEIGHT], WIDTH, HEIGHT));
+		let [a, b, c] = &mut outputs;
+		crate::blur_multi(input.as_ref(), [&mut a.as_mut(), &mut b.as_mut(), &mut c.as_mut()], radii, |&value| StackBlurrableF64(value), |value| value.0);
+
+		for (output, &radius) in outputs.iter().zip(&radii) {
+			let mut expected = input.clone();
+			crate::blur(&mut expected.as_mut(), radius, |&value| StackBlurrableF64(value), |value| value.0);
+
+			for (index, (&actual, &expected)) in output.buf().iter().zip(expected.buf()).enumerate() {
+				assert!((actual - expected).abs() < 1e-9, "radius {} of {:?} at {}: {} != {}", radius, radii, index, actual, expected);
+			}
+		}
+
+		#[cfg(feature = "rayon")]
+		{
+			let mut par_outputs = [(); 3].map(|_| ImgVec::new(vec![0.0; WIDTH * HEIGHT], WIDTH, HEIGHT));
+			let [a, b, c] = &mut par_outputs;
+			crate::par_blur_multi(input.as_ref(), [&mut a.as_mut(), &mut b.as_mut(), &mut c.as_mut()], radii, |&value| StackBlurrableF64(value), |value| value.0);
+
+			for (actual, expected) in par_outputs.iter().zip(&outputs) {
+				assert_eq!(actual.buf(), expected.buf(), "par_blur_multi with {:?}", radii);
+			}
+		}
+	}
+}
+
+#[test]
+fn morphology_matches_naive_min_and_max() {
+	use std::collections::VecDeque;
+	use crate::iter::{Dilate, Erode};
+
+	const WIDTH: usize = 23;
+	const HEIGHT: usize = 9;
+
+	// Full noise, and noise with only a few levels so there are plenty of ties.
+	let wide = noise(WIDTH, HEIGHT);
+	let narrow = ImgVec::new(wide.buf().iter().map(|&pixel| pixel % 3).collect(), WIDTH, HEIGHT);
+
+	// The largest and smallest items within `radius` of `center`, leaving out
+	// the ones off the edges.
+	let window = |line: &[u32], center: usize, radius: usize| {
+		let window = &line[center.saturating_sub(radius)..(center + radius + 1).min(line.len())];
+		(*window.iter().max().unwrap(), *window.iter().min().unwrap())
+	};
+
+	// Radii up to and past the length of the rows and columns.
+	let radii = [0, 1, 2, 5, HEIGHT, WIDTH, WIDTH * 2];
+	let mut dilate_ops = VecDeque::new();
+	let mut erode_ops = VecDeque::new();
+
+	for input in [&wide, &narrow] {
+		for radius in radii {
+			for row in input.rows() {
+				let mut dilate = Dilate::new(row.iter().copied(), radius, dilate_ops);
+				let mut erode = Erode::new(row.iter().copied(), radius, erode_ops);
+
+				for center in 0..row.len() {
+					let (max, min) = window(row, center, radius);
+					assert_eq!(dilate.next(), Some(max), "dilating by {} at {}", radius, center);
+					assert_eq!(erode.next(), Some(min), "eroding by {} at {}", radius, center);
+				}
+
+				assert_eq!(dilate.next(), None);
+				assert_eq!(erode.next(), None);
+				dilate_ops = dilate.into_ops();
+				erode_ops = erode.into_ops();
+			}
+
+			// In 2D, the window is a square clipped to the edges of the buffer.
+			let rows = input.rows().map(|row| (0..WIDTH).map(|x| window(row, x, radius)).collect::<Vec<_>>()).collect::<Vec<_>>();
+			let mut expected_max = Vec::with_capacity(WIDTH * HEIGHT);
+			let mut expected_min = Vec::with_capacity(WIDTH * HEIGHT);
+
+			for y in 0..HEIGHT {
+				for x in 0..WIDTH {
+					let square = &rows[y.saturating_sub(radius)..(y + radius + 1).min(HEIGHT)];
+					expected_max.push(square.iter().map(|row| row[x].0).max().unwrap());
+					expected_min.push(square.iter().map(|row| row[x].1).min().unwrap());
+				}
+			}
+
+			let mut dilated = input.clone();
+			crate::dilate(&mut dilated.as_mut(), radius, |&pixel| pixel, |pixel| pixel);
+			assert_eq!(dilated.buf(), &expected_max[..], "dilate by {}", radius);
+
+			let mut eroded = input.clone();
+			crate::erode(&mut eroded.as_mut(), radius, |&pixel| pixel, |pixel| pixel);
+			assert_eq!(eroded.buf(), &expected_min[..], "erode by {}", radius);
+
+			#[cfg(feature = "rayon")]
+			{
+				let mut dilated = input.clone();
+				crate::par_dilate(&mut dilated.as_mut(), radius, |&pixel| pixel, |pixel| pixel);
+				assert_eq!(dilated.buf(), &expected_max[..], "par_dilate by {}", radius);
+
+				let mut eroded = input.clone();
+				crate::par_erode(&mut eroded.as_mut(), radius, |&pixel| pixel, |pixel| pixel);
+				assert_eq!(eroded.buf(), &expected_min[..], "par_erode by {}", radius);
+			}
+		}
+	}
+}
+
+#[test]
+fn argb_switches_to_u64_past_the_u32_radius() {
+	use crate::color::{ArgbU32, ArgbU64};
+
+	const RADIUS: usize = crate::ARGB_U32_MAX_RADIUS + 1;
+	const LEN: usize = RADIUS * 2 + 7;
+
+	// The largest radius whose full kernel over white sums up within a `u32`.
+	let sum = |radius: usize| 255 * (radius as u64 + 1).pow(2);
+	assert!(sum(crate::ARGB_U32_MAX_RADIUS) <= u32::MAX as u64);
+	assert!(sum(RADIUS) > u32::MAX as u64);
+
+	// Lines long enough for the whole kernel to fit in the middle, in both
+	// directions. Noise keeps well within a `u32`, but white doesn't.
+	for (width, height) in [(LEN, 1), (1, LEN)] {
+		let white = ImgVec::new(vec![0xffffffff; LEN], width, height);
+
+		for input in [noise(width, height), white.clone()] {
+			let mut expected = input.clone();
+			crate::blur(&mut expected.as_mut(), RADIUS, |&pixel| ArgbU64::from_u32(pixel), ArgbU64::to_u32);
+			let mut actual = input.clone();
+			crate::blur_argb(&mut actual.as_mut(), RADIUS);
+			assert_eq!(actual.buf(), expected.buf(), "{}x{}", width, height);
+
+			#[cfg(feature = "rayon")]
+			{
+				let mut actual = input.clone();
+				crate::par_blur_argb(&mut actual.as_mut(), RADIUS);
+				assert_eq!(actual.buf(), expected.buf(), "par_blur_argb at {}x{}", width, height);
+			}
+		}
+
+		// Which is not what 32 bits per channel would have left.
+		let mut wrapped = white.clone();
+		crate::blur(&mut wrapped.as_mut(), RADIUS, |&pixel| ArgbU32::from_u32(pixel), ArgbU32::to_u32);
+		assert_ne!(wrapped.buf(), white.buf(), "{}x{}", width, height);
+	}
+}
+
+#[test]
+fn kernel_blur_matches_naive_convolution() {
+	use std::collections::VecDeque;
+	use crate::color::{ArgbU32, StackBlurrableU32};
+	use crate::iter::{EdgeMode, Kernel, KernelBlur, StackBlur};
+
+	let items = noise(40, 1).buf().iter().map(|&item| item & 0xff).collect::<Vec<_>>();
+	let mut ops = VecDeque::new();
+
+	for mode in [EdgeMode::Renormalize, EdgeMode::Clamp, EdgeMode::Mirror, EdgeMode::Wrap] {
+		for len in [1, 2, 7, 40] {
+			let line = &items[..len];
+
+			for radius in [0, 1, 2, 3, 5, 13, 50] {
+				let to_blurrable = || line.iter().map(|&item| StackBlurrableU32(item));
+
+				// The tent is exactly what a `StackBlur` does.
+				let tent = StackBlur::with_edge_mode(to_blurrable(), radius, mode, VecDeque::new()).map(|item| item.0).collect::<Vec<_>>();
+
+				for kernel in [Kernel::Box, Kernel::Tent, Kernel::Quadratic] {
+					let weights = kernel_weights(kernel, radius);
+					assert_eq!(weights.iter().sum::<u64>(), kernel.total_weight(radius) as u64, "{:?} at radius {}", kernel, radius);
+
+					let expected = naive_blur(line, radius, mode, |distance| weights[radius + distance]);
+					let mut blur = KernelBlur::with_edge_mode(to_blurrable(), radius, kernel, mode, ops);
+					let actual = blur.by_ref().map(|item| item.0).collect::<Vec<_>>();
+					assert_eq!(actual, expected, "{:?} with {:?} of {:?} at radius {}", kernel, mode, line, radius);
+
+					if kernel == Kernel::Tent {
+						assert_eq!(actual, tent, "{:?} of {:?} at radius {}", mode, line, radius);
+					}
+
+					ops = blur.into_ops();
+				}
+			}
+		}
+	}
+
+	// And in 2D, `blur_kernel` with a tent is `blur_xy`.
+	let input = noise(17, 12);
+	for (radius_x, radius_y) in [(0, 0), (1, 4), (6, 2), (30, 30)] {
+		let mut expected = input.clone();
+		crate::blur_xy(&mut expected.as_mut(), radius_x, radius_y, |&pixel| ArgbU32::from_u32(pixel), ArgbU32::to_u32);
+		let mut actual = input.clone();
+		crate::blur_kernel(&mut actual.as_mut(), radius_x, radius_y, Kernel::Tent, |&pixel| ArgbU32::from_u32(pixel), ArgbU32::to_u32);
+		assert_eq!(actual.buf(), expected.buf(), "radii {}, {}", radius_x, radius_y);
+	}
+}
+
+#[test]
+fn fractional_blur_moves_smoothly_between_radii() {
+	use std::collections::VecDeque;
+	use crate::color::StackBlurrableU32;
+	use crate::iter::{EdgeMode, FractionalStackBlur, StackBlur};
+
+	let line = noise(25, 1).buf().iter().map(|&item| item & 0xff).collect::<Vec<_>>();
+	let to_blurrable = || line.iter().map(|&item| StackBlurrableU32(item));
+	let mut ops = [VecDeque::new(), VecDeque::new()];
+
+	let mut blur = |radius: f32, edge_mode: EdgeMode| {
+		let mut blur = FractionalStackBlur::with_edge_mode(to_blurrable(), radius, edge_mode, std::mem::take(&mut ops));
+		let output = blur.by_ref().map(|item| item.0).collect::<Vec<_>>();
+		ops = blur.into_ops();
+		output
+	};
+
+	for edge_mode in [EdgeMode::Renormalize, EdgeMode::Clamp, EdgeMode::Mirror, EdgeMode::Wrap] {
+		let stack_blur = |radius: usize| StackBlur::with_edge_mode(to_blurrable(), radius, edge_mode, VecDeque::new()).map(|item| item.0).collect::<Vec<_>>();
+
+		for radius in [0, 1, 2, 5, 30] {
+			// A whole radius is exactly a `StackBlur`, and so is one that rounds
+			// up to the next.
+			let lower = stack_blur(radius);
+			let upper = stack_blur(radius + 1);
+			assert_eq!(blur(radius as f32, edge_mode), lower, "{:?} at radius {}", edge_mode, radius);
+			assert_eq!(blur(radius as f32 + 0.999, edge_mode), upper, "{:?} at radius {}.999", edge_mode, radius);
+
+			// In between, every item moves from one to the other in steps of at
+			// most a 256th of the way, and never turns back.
+			let mut previous = lower.clone();
+
+			for step in 1..=256 {
+				let current = blur(radius as f32 + step as f32 / 256.0, edge_mode);
+
+				for (index, ((&from, &to), (&before, &after))) in lower.iter().zip(&upper).zip(previous.iter().zip(&current)).enumerate() {
+					let at = || format!("{:?} at radius {} + {}/256, item {}", edge_mode, radius, step, index);
+					assert!(before.min(after) >= from.min(to) && before.max(after) <= from.max(to), "{} left {}..={}", at(), from, to);
+					assert!((from <= to) == (before <= after) || before == after, "{} turned back", at());
+					assert!(before.abs_diff(after) <= from.abs_diff(to) / 256 + 1, "{} jumped from {} to {}", at(), before, after);
+				}
+
+				previous = current;
+			}
+
+			assert_eq!(previous, upper, "{:?} at radius {} + 256/256", edge_mode, radius);
+		}
+	}
+}
+
+#[test]
+fn blur_xy_with_one_zero_radius_blurs_one_axis() {
+	use std::collections::VecDeque;
+	use imgref::ImgRefMut;
+	use crate::color::ArgbU32;
+	use crate::iter::StackBlur;
+
+	const WIDTH: usize = 16;
+	const HEIGHT: usize = 11;
+
+	let input = noise(WIDTH, HEIGHT);
+	let blur_line = |line: Vec<u32>, radius: usize| StackBlur::new(line.into_iter().map(ArgbU32::from_u32), radius, VecDeque::new()).map(ArgbU32::to_u32).collect::<Vec<_>>();
+
+	// Only the rows are blurred, so every row comes out as a `StackBlur` over
+	// that row alone.
+	let rows_only = input.rows().flat_map(|row| blur_line(row.to_vec(), 3)).collect::<Vec<_>>();
+
+	// Only the columns are blurred, likewise.
+	let columns = (0..WIDTH).map(|x| blur_line(input.rows().map(|row| row[x]).collect(), 3)).collect::<Vec<_>>();
+	let columns_only = (0..WIDTH * HEIGHT).map(|index| columns[index % WIDTH][index / WIDTH]).collect::<Vec<_>>();
+
+	for ((radius_x, radius_y), expected) in [((3, 0), &rows_only[..]), ((0, 3), &columns_only[..]), ((0, 0), input.buf())] {
+		let check = |name: &str, blur: &dyn Fn(&mut ImgRefMut<u32>)| {
+			let mut actual = input.clone();
+			blur(&mut actual.as_mut());
+			assert_eq!(actual.buf(), expected, "{} with radii {}, {}", name, radius_x, radius_y);
+		};
+
+		check("blur_xy", &|buffer| crate::blur_xy(buffer, radius_x, radius_y, |&pixel| ArgbU32::from_u32(pixel), ArgbU32::to_u32));
+		check("blur_argb_xy", &|buffer| crate::blur_argb_xy(buffer, radius_x, radius_y));
+
+		#[cfg(feature = "rayon")]
+		{
+			check("par_blur_xy", &|buffer| crate::par_blur_xy(buffer, radius_x, radius_y, |&pixel| ArgbU32::from_u32(pixel), ArgbU32::to_u32));
+			check("par_blur_argb_xy", &|buffer| crate::par_blur_argb_xy(buffer, radius_x, radius_y));
+		}
+
+		#[cfg(feature = "simd")]
+		check("simd_blur_argb_xy", &|buffer| crate::simd_blur_argb_xy::<8>(buffer, radius_x, radius_y));
+
+		#[cfg(all(feature = "simd", feature = "rayon"))]
+		check("par_simd_blur_argb_xy", &|buffer| crate::par_simd_blur_argb_xy::<8>(buffer, radius_x, radius_y));
+	}
+}